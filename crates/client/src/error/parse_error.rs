@@ -73,6 +73,10 @@ pub enum ErrorKind {
     #[error("proto error: {0}")]
     Proto(#[from] ProtoError),
 
+    /// Text in the zone file is not valid UTF-8
+    #[error("invalid UTF-8: {0}")]
+    Utf8(#[from] std::str::Utf8Error),
+
     /// A request timed out
     #[error("request timed out")]
     Timeout,
@@ -95,6 +99,7 @@ impl Clone for ErrorKind {
             Lexer(e) => Lexer(e.clone()),
             ParseInt(e) => ParseInt(e.clone()),
             Proto(e) => Proto(e.clone()),
+            Utf8(e) => Utf8(*e),
             Timeout => Timeout,
         }
     }
@@ -104,6 +109,7 @@ impl Clone for ErrorKind {
 #[derive(Error, Debug)]
 pub struct Error {
     kind: ErrorKind,
+    line: Option<usize>,
     #[cfg(feature = "backtrace")]
     backtrack: Option<ExtBacktrace>,
 }
@@ -113,10 +119,25 @@ impl Error {
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
     }
+
+    /// The line of the zone file on which the error occurred, if known
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Associates the error with the line on which it occurred, the first line recorded is kept
+    pub(crate) fn with_line(mut self, line: usize) -> Self {
+        self.line = self.line.or(Some(line));
+        self
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "error at line {}: ", line)?;
+        }
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "backtrace")] {
                 if let Some(ref backtrace) = self.backtrack {
@@ -136,6 +157,7 @@ impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Error {
         Error {
             kind,
+            line: None,
             #[cfg(feature = "backtrace")]
             backtrack: trace!(),
         }
@@ -196,6 +218,12 @@ impl From<ProtoError> for Error {
     }
 }
 
+impl From<std::str::Utf8Error> for Error {
+    fn from(e: std::str::Utf8Error) -> Error {
+        ErrorKind::from(e).into()
+    }
+}
+
 impl From<Error> for io::Error {
     fn from(e: Error) -> Self {
         match *e.kind() {
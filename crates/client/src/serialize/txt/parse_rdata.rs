@@ -16,13 +16,15 @@
 
 //! record data enum variants
 
+use std::str;
+
 use crate::error::*;
-use crate::rr::rdata::DNSSECRecordType;
+use crate::rr::rdata::{DNSSECRData, DNSSECRecordType};
 use crate::rr::{Name, RData, RecordType};
 use crate::serialize::txt::rdata_parsers::*;
 
 pub trait RDataParser: Sized {
-    fn parse<'i, I: Iterator<Item = &'i str>>(
+    fn parse<'i, I: Iterator<Item = &'i [u8]>>(
         record_type: RecordType,
         tokens: I,
         origin: Option<&Name>,
@@ -31,11 +33,21 @@ pub trait RDataParser: Sized {
 
 impl RDataParser for RData {
     /// Parse the RData from a set of Tokens
-    fn parse<'i, I: Iterator<Item = &'i str>>(
+    ///
+    /// TXT character-strings are parsed from the raw octets, all other tokens must be UTF-8
+    fn parse<'i, I: Iterator<Item = &'i [u8]>>(
         record_type: RecordType,
         tokens: I,
         origin: Option<&Name>,
     ) -> ParseResult<Self> {
+        if let RecordType::TXT = record_type {
+            return Ok(RData::TXT(txt::parse(tokens)?));
+        }
+
+        let tokens = tokens
+            .map(str::from_utf8)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
         let rdata = match record_type {
             RecordType::A => RData::A(a::parse(tokens)?),
             RecordType::AAAA => RData::AAAA(aaaa::parse(tokens)?),
@@ -45,6 +57,8 @@ impl RDataParser for RData {
             RecordType::AXFR => panic!("parsing AXFR doesn't make sense"), // valid panic, never should happen
             RecordType::CAA => caa::parse(tokens).map(RData::CAA)?,
             RecordType::CNAME => RData::CNAME(name::parse(tokens, origin)?),
+            RecordType::HINFO => RData::HINFO(hinfo::parse(tokens)?),
+            RecordType::LOC => RData::LOC(loc::parse(tokens)?),
             RecordType::IXFR => panic!("parsing IXFR doesn't make sense"), // valid panic, never should happen
             RecordType::MX => RData::MX(mx::parse(tokens, origin)?),
             RecordType::NAPTR => RData::NAPTR(naptr::parse(tokens, origin)?),
@@ -57,17 +71,24 @@ impl RDataParser for RData {
             RecordType::SRV => RData::SRV(srv::parse(tokens, origin)?),
             RecordType::SSHFP => RData::SSHFP(sshfp::parse(tokens)?),
             RecordType::TLSA => RData::TLSA(tlsa::parse(tokens)?),
-            RecordType::TXT => RData::TXT(txt::parse(tokens)?),
+            RecordType::TXT => unreachable!("TXT is parsed from octets above"),
+            RecordType::URI => RData::URI(uri::parse(tokens)?),
             RecordType::DNSSEC(DNSSECRecordType::SIG) => panic!("parsing SIG doesn't make sense"), // valid panic, never should happen
+            RecordType::DNSSEC(DNSSECRecordType::CDNSKEY) => {
+                RData::DNSSEC(DNSSECRData::CDNSKEY(dnskey::parse(tokens)?.into()))
+            }
+            RecordType::DNSSEC(DNSSECRecordType::CDS) => {
+                RData::DNSSEC(DNSSECRData::CDS(ds::parse(tokens)?.into()))
+            }
             RecordType::DNSSEC(DNSSECRecordType::DNSKEY) => {
-                panic!("DNSKEY should be dynamically generated")
-            } // valid panic, never should happen
+                RData::DNSSEC(DNSSECRData::DNSKEY(dnskey::parse(tokens)?))
+            }
             RecordType::DNSSEC(DNSSECRecordType::KEY) => {
                 panic!("KEY should be dynamically generated")
             } // valid panic, never should happen
             RecordType::DNSSEC(DNSSECRecordType::DS) => {
-                panic!("DS should be dynamically generated")
-            } // valid panic, never should happen
+                RData::DNSSEC(DNSSECRData::DS(ds::parse(tokens)?))
+            }
             RecordType::DNSSEC(DNSSECRecordType::NSEC) => {
                 panic!("NSEC should be dynamically generated")
             } // valid panic, never should happen
@@ -78,15 +99,15 @@ impl RDataParser for RData {
                 panic!("NSEC3PARAM should be dynamically generated")
             } // valid panic, never should happen
             RecordType::DNSSEC(DNSSECRecordType::RRSIG) => {
-                panic!("RRSIG should be dynamically generated")
-            } // valid panic, never should happen
+                RData::DNSSEC(DNSSECRData::SIG(rrsig::parse(tokens, origin)?))
+            }
             RecordType::DNSSEC(DNSSECRecordType::Unknown(code)) => {
                 panic!("Unknown dnssec record type, if you want to support this type, please file an issue against Trust-DNS: {}", code)
             } // valid panic, never should happen
-            RecordType::Unknown(code) => {
-                // TODO: add a way to associate generic record types to the zone
-                panic!("Unknown record type, if you want to support this type, please file an issue against Trust-DNS: {}", code)
-            }
+            RecordType::Unknown(code) => RData::Unknown {
+                code,
+                rdata: unknown::parse(tokens)?,
+            },
             RecordType::ZERO => RData::ZERO,
         };
 
@@ -27,5 +27,22 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<Ipv4
         .next()
         .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("ipv4 address".to_string())))
         .and_then(|s| Ipv4Addr::from_str(s).map_err(Into::into))?;
-    Ok(address)
+    Some(address)
+        .filter(|_| tokens.next().is_none())
+        .ok_or_else(|| ParseErrorKind::Message("too many fields for A").into())
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["192.0.2"].into_iter()).is_err());
+    assert!(parse(vec!["192.0.2.256"].into_iter()).is_err());
+    assert!(parse(vec!["192.0.2.1.5"].into_iter()).is_err());
+    assert!(parse(vec!["::1"].into_iter()).is_err());
+    assert!(parse(vec!["192.0.2.1", "192.0.2.2"].into_iter()).is_err());
+
+    assert_eq!(
+        parse(vec!["192.0.2.1"].into_iter()).unwrap(),
+        Ipv4Addr::new(192, 0, 2, 1)
+    );
 }
@@ -27,5 +27,23 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<Ipv6
         .next()
         .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("ipv6 address".to_string())))
         .and_then(|s| Ipv6Addr::from_str(s).map_err(Into::into))?;
-    Ok(address)
+    Some(address)
+        .filter(|_| tokens.next().is_none())
+        .ok_or_else(|| ParseErrorKind::Message("too many fields for AAAA").into())
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["2001:db8::1::2"].into_iter()).is_err());
+    assert!(parse(vec!["2001:db8:0:0:0:0:0:0:1"].into_iter()).is_err());
+    assert!(parse(vec!["192.0.2.1"].into_iter()).is_err());
+    assert!(parse(vec!["::1", "::2"].into_iter()).is_err());
+
+    let expanded = parse(vec!["2001:0db8:0000:0000:0000:0000:0000:0001"].into_iter()).unwrap();
+    let compressed = parse(vec!["2001:db8::1"].into_iter()).unwrap();
+    assert_eq!(expanded, Ipv6Addr::new(0x2001, 0x0db8, 0, 0, 0, 0, 0, 1));
+    assert_eq!(expanded, compressed);
+
+    assert_eq!(parse(vec!["::1"].into_iter()).unwrap(), Ipv6Addr::LOCALHOST);
 }
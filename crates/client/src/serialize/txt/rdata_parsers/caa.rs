@@ -55,6 +55,9 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<CAA>
     let value_str: &str = tokens
         .next()
         .ok_or_else(|| ParseError::from(ParseErrorKind::Message("caa value not present")))?;
+    if tokens.next().is_some() {
+        return Err(ParseErrorKind::Message("too many fields for CAA").into());
+    }
 
     // parse the flags
     let issuer_critical = {
@@ -103,6 +106,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<CAA>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::proto::rr::Name;
 
     #[test]
     fn test_parsing() {
@@ -110,5 +114,28 @@ mod tests {
         assert!(parse(vec!["0", "issue", ";"].into_iter()).is_ok());
         // certs         CAA 0 issuewild \"example.net\"
         assert!(parse(vec!["0", "issue", "example.net"].into_iter()).is_ok());
+
+        let rdata = parse(vec!["0", "issue", "letsencrypt.org"].into_iter()).unwrap();
+        assert!(!rdata.issuer_critical());
+        assert_eq!(*rdata.tag(), Property::Issue);
+        assert_eq!(
+            *rdata.value(),
+            Value::Issuer(Some(Name::parse("letsencrypt.org", None).unwrap()), vec![])
+        );
+
+        let rdata = parse(vec!["128", "iodef", "mailto:security@example.com"].into_iter()).unwrap();
+        assert!(rdata.issuer_critical());
+        assert_eq!(*rdata.tag(), Property::Iodef);
+        assert_eq!(
+            *rdata.value(),
+            Value::Url(caa::read_iodef(b"mailto:security@example.com").unwrap())
+        );
+
+        let rdata = parse(vec!["0", "tbs", "Unknown"].into_iter()).unwrap();
+        assert_eq!(*rdata.tag(), Property::Unknown("tbs".to_string()));
+        assert_eq!(*rdata.value(), Value::Unknown(b"Unknown".to_vec()));
+
+        assert!(parse(vec!["0", "issue"].into_iter()).is_err());
+        assert!(parse(vec!["0", "issue", "example.net", "extra"].into_iter()).is_err());
     }
 }
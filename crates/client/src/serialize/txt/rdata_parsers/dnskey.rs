@@ -0,0 +1,139 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! public key records for DNSSEC zone signing keys
+
+use std::str::FromStr;
+
+use super::ds::parse_algorithm;
+use crate::error::*;
+use crate::proto::serialize::txt::from_base64;
+use crate::rr::rdata::DNSKEY;
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 4034, DNSSEC Resource Records, March 2005](https://tools.ietf.org/html/rfc4034#section-2.2)
+///
+/// ```text
+/// 2.2.  The DNSKEY RR Presentation Format
+///
+///    The presentation format of the RDATA portion is as follows:
+///
+///    The Flag field MUST be represented as an unsigned decimal integer.
+///    Given the currently defined flags, the possible values are: 0, 256,
+///    and 257.
+///
+///    The Protocol Field MUST be represented as an unsigned decimal integer
+///    with a value of 3.
+///
+///    The Algorithm field MUST be represented either as an unsigned decimal
+///    integer or as an algorithm mnemonic as specified in Appendix A.1.
+///
+///    The Public Key field MUST be represented as a Base64 encoding of the
+///    Public Key.  Whitespace is allowed within the Base64 text.  For a
+///    definition of Base64 encoding, see [RFC3548].
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<DNSKEY> {
+    let flags: u16 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("flags".to_string())))
+        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+
+    let protocol: u8 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("protocol".to_string())))
+        .and_then(|s| u8::from_str(s).map_err(Into::into))?;
+    if protocol != 3 {
+        return Err(ParseErrorKind::Msg(format!("DNSKEY protocol must be 3: {}", protocol)).into());
+    }
+
+    let algorithm = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("algorithm".to_string())))
+        .and_then(parse_algorithm)?;
+
+    let public_key = tokens.collect::<String>();
+    if public_key.is_empty() {
+        return Err(ParseErrorKind::MissingToken("public key".to_string()).into());
+    }
+    let public_key = from_base64(&public_key)?;
+
+    Ok(DNSKEY::new(
+        flags & 0b0000_0001_0000_0000 != 0,
+        flags & 0b0000_0000_0000_0001 != 0,
+        flags & 0b0000_0000_1000_0000 != 0,
+        algorithm,
+        public_key,
+    ))
+}
+
+#[test]
+fn test_parsing() {
+    use crate::rr::dnssec::Algorithm;
+
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["256", "3", "5"].into_iter()).is_err());
+    assert!(parse(vec!["256", "2", "5", "AQPSKmynfzW4kyBv015MUG2DeIQ3"].into_iter()).is_err());
+    assert!(parse(vec!["256", "3", "5", "not base64!"].into_iter()).is_err());
+
+    // dskey.example.com. 86400 IN DNSKEY 256 3 5 ( AQOeiiR0GOMYkDshWoSKz9Xz
+    //                                           fwJr1AYtsmx3TGkJaNXVbfi/
+    //                                           ...
+    //                                           ) ;  key id = 60485
+    let tokens = vec![
+        "256",
+        "3",
+        "5",
+        "AQOeiiR0GOMYkDshWoSKz9Xz",
+        "fwJr1AYtsmx3TGkJaNXVbfi/",
+        "2pHm822aJ5iI9BMzNXxeYCmZ",
+        "DRD99WYwYqUSdjMmmAphXdvx",
+        "egXd/M5+X7OrzKBaMbCVdFLU",
+        "Uh6DhweJBjEVv5f2wwjM9Xzc",
+        "nOf+EPbtG9DMBmADjFDc2w/r",
+        "ljwvFw==",
+    ];
+    let rdata = parse(tokens.iter().copied()).expect("failed to parse DNSKEY");
+
+    assert!(rdata.zone_key());
+    assert!(!rdata.secure_entry_point());
+    assert!(!rdata.revoke());
+    assert_eq!(rdata.algorithm(), Algorithm::RSASHA1);
+    assert_eq!(rdata.calculate_key_tag().unwrap(), 60485);
+
+    // Display re-encodes the key as a single base64 token
+    assert_eq!(
+        rdata.to_string(),
+        format!("256 3 5 {}", tokens[3..].concat())
+    );
+    assert_eq!(parse(rdata.to_string().split(' ')).unwrap(), rdata);
+}
+
+#[test]
+#[cfg(any(feature = "openssl", feature = "ring"))]
+fn test_digest_matches_ds() {
+    use crate::rr::dnssec::DigestType;
+    use crate::rr::Name;
+
+    // the DS example from RFC 4034, section 5.4, with the algorithm as a mnemonic
+    let rdata = parse(
+        "256 3 RSASHA1 AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw=="
+            .split(' '),
+    )
+    .expect("failed to parse DNSKEY");
+
+    let digest = rdata
+        .to_digest(
+            &Name::from_ascii("dskey.example.com.").unwrap(),
+            DigestType::SHA1,
+        )
+        .unwrap();
+    assert_eq!(
+        data_encoding::HEXUPPER.encode(digest.as_ref()),
+        "2BB183AF5F22588179A53B0A98631FAD1A292118"
+    );
+}
@@ -0,0 +1,148 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! delegation signer records for linking a child zone's DNSKEY to its parent
+
+use std::str::FromStr;
+
+use crate::error::*;
+use crate::proto::serialize::txt::from_hex;
+use crate::rr::dnssec::{Algorithm, DigestType};
+use crate::rr::rdata::DS;
+
+/// Parses a DNSSEC algorithm, either as an unsigned decimal integer or as its mnemonic
+pub(crate) fn parse_algorithm(token: &str) -> ParseResult<Algorithm> {
+    if let Ok(value) = u8::from_str(token) {
+        return Ok(Algorithm::from_u8(value));
+    }
+
+    [
+        Algorithm::RSASHA1,
+        Algorithm::RSASHA1NSEC3SHA1,
+        Algorithm::RSASHA256,
+        Algorithm::RSASHA512,
+        Algorithm::ECDSAP256SHA256,
+        Algorithm::ECDSAP384SHA384,
+        Algorithm::ED25519,
+    ]
+    .iter()
+    .copied()
+    .find(|algorithm| algorithm.as_str().eq_ignore_ascii_case(token))
+    .ok_or_else(|| ParseErrorKind::Msg(format!("unknown DNSSEC algorithm: {}", token)).into())
+}
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 4034, DNSSEC Resource Records, March 2005](https://tools.ietf.org/html/rfc4034#section-5.3)
+///
+/// ```text
+/// 5.3.  The DS RR Presentation Format
+///
+///    The presentation format of the RDATA portion is as follows:
+///
+///    The Key Tag field MUST be represented as an unsigned decimal integer.
+///
+///    The Algorithm field MUST be represented either as an unsigned decimal
+///    integer or as an algorithm mnemonic specified in Appendix A.1.
+///
+///    The Digest Type field MUST be represented as an unsigned decimal
+///    integer.
+///
+///    The Digest MUST be represented as a sequence of case-insensitive
+///    hexadecimal digits.  Whitespace is allowed within the hexadecimal
+///    text.
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<DS> {
+    let key_tag: u16 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("key tag".to_string())))
+        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+
+    let algorithm = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("algorithm".to_string())))
+        .and_then(parse_algorithm)?;
+
+    let digest_type = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("digest type".to_string())))
+        .and_then(|s| u8::from_str(s).map_err(Into::into))
+        .map(DigestType::from)?;
+
+    let digest = tokens.collect::<String>();
+    if digest.is_empty() {
+        return Err(ParseErrorKind::MissingToken("digest".to_string()).into());
+    }
+    let digest = from_hex(&digest)?;
+
+    Ok(DS::new(key_tag, algorithm, digest_type, digest))
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["60485", "5", "1"].into_iter()).is_err());
+    assert!(parse(vec!["60485", "BOGUS", "1", "2BB183AF"].into_iter()).is_err());
+    assert!(parse(vec!["60485", "5", "1", "not hex"].into_iter()).is_err());
+
+    // dskey.example.com. 86400 IN DS 60485 5 1 ( 2BB183AF5F22588179A53B0A
+    //                                            98631FAD1A292118 )
+    assert_eq!(
+        parse(
+            vec![
+                "60485",
+                "5",
+                "1",
+                "2BB183AF5F22588179A53B0A",
+                "98631FAD1A292118"
+            ]
+            .into_iter()
+        )
+        .expect("failed to parse DS"),
+        DS::new(
+            60485,
+            Algorithm::RSASHA1,
+            DigestType::SHA1,
+            data_encoding::HEXUPPER
+                .decode(b"2BB183AF5F22588179A53B0A98631FAD1A292118")
+                .unwrap(),
+        )
+    );
+
+    // mnemonics and lowercase hex are accepted
+    assert_eq!(
+        parse(
+            vec![
+                "60485",
+                "rsasha1",
+                "1",
+                "2bb183af5f22588179a53b0a98631fad1a292118"
+            ]
+            .into_iter()
+        )
+        .expect("failed to parse DS")
+        .algorithm(),
+        Algorithm::RSASHA1
+    );
+}
+
+#[test]
+fn test_parse_display_round_trip() {
+    let presentation = "20326 8 2 E06D44B80B8F1D39A95C0B0D7C65D08458E880409BBC683457104237C7F8EC8D";
+    let rdata = parse(presentation.split(' ')).expect("failed to parse DS");
+    assert_eq!(rdata.key_tag(), 20326);
+    assert_eq!(rdata.algorithm(), Algorithm::RSASHA256);
+    assert_eq!(rdata.digest_type(), DigestType::SHA256);
+    assert_eq!(rdata.digest().len(), 32);
+    assert_eq!(rdata.to_string(), presentation);
+
+    // unknown digest types are preserved
+    let presentation = "20326 8 3 E06D44B80B8F1D39";
+    let rdata = parse(presentation.split(' ')).expect("failed to parse DS");
+    assert_eq!(rdata.digest_type(), DigestType::Unknown(3));
+    assert_eq!(rdata.to_string(), presentation);
+}
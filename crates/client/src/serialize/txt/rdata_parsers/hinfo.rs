@@ -0,0 +1,59 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! host information records for describing the CPU and OS of a host
+
+use crate::error::*;
+use crate::rr::rdata::HINFO;
+
+/// Parse the RData from a set of Tokens
+///
+/// ```text
+/// IN HINFO "PDP-11/70" "UNIX"
+/// ```
+///
+/// Each token is a single <character-string>, which may be at most 255 bytes.
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<HINFO> {
+    let mut character_string = |field: &str| -> ParseResult<Box<[u8]>> {
+        let s = tokens
+            .next()
+            .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken(field.to_string())))?;
+        if s.len() > 255 {
+            return Err(ParseErrorKind::Msg(format!(
+                "HINFO {} exceeds 255 bytes: {}",
+                field,
+                s.len()
+            ))
+            .into());
+        }
+
+        Ok(s.as_bytes().to_vec().into_boxed_slice())
+    };
+
+    let cpu = character_string("cpu")?;
+    let os = character_string("os")?;
+
+    Some(HINFO::new(cpu, os))
+        .filter(|_| tokens.next().is_none())
+        .ok_or_else(|| ParseErrorKind::Message("too many fields for HINFO").into())
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["PDP-11/70"].into_iter()).is_err());
+    assert!(parse(vec!["PDP-11/70", "UNIX", "extra"].into_iter()).is_err());
+    let too_long = "a".repeat(256);
+    assert!(parse(vec!["PDP-11/70", too_long.as_str()].into_iter()).is_err());
+
+    // IN HINFO "Intel Core i7" "Debian GNU/Linux"
+    let rdata = parse(vec!["Intel Core i7", "Debian GNU/Linux"].into_iter())
+        .expect("failed to parse HINFO");
+    assert_eq!(rdata.cpu(), b"Intel Core i7");
+    assert_eq!(rdata.os(), b"Debian GNU/Linux");
+    assert_eq!(rdata.to_string(), r#""Intel Core i7" "Debian GNU/Linux""#);
+}
@@ -0,0 +1,266 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! location records for the geographic position of a host or network
+
+use std::str::FromStr;
+
+use crate::error::*;
+use crate::rr::rdata::loc::{self, LOC};
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 1876, A Means for Expressing Location Information in the DNS, January 1996](https://tools.ietf.org/html/rfc1876#section-3)
+///
+/// ```text
+/// 3. Master File Format
+///
+///    The LOC record is expressed in a master file in the following format:
+///
+///    <owner> <TTL> <class> LOC ( d1 [m1 [s1]] {"N"|"S"} d2 [m2 [s2]]
+///                                {"E"|"W"} alt["m"] [siz["m"] [hp["m"]
+///                                [vp["m"]]]] )
+///
+///    where:
+///
+///        d1:     [0 .. 90]            (degrees latitude)
+///        d2:     [0 .. 180]           (degrees longitude)
+///        m1, m2: [0 .. 59]            (minutes latitude/longitude)
+///        s1, s2: [0 .. 59.999]        (seconds latitude/longitude)
+///        alt:    [-100000.00 .. 42849672.95] BY .01 (altitude in meters)
+///        siz, hp, vp: [0 .. 90000000.00] (size/precision in meters)
+///
+///    If omitted, minutes and seconds default to zero, size defaults to 1m,
+///    horizontal precision defaults to 10000m, and vertical precision
+///    defaults to 10m.
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<LOC> {
+    let latitude = parse_coordinate(&mut tokens, "latitude", "N", "S", 90)?;
+    let longitude = parse_coordinate(&mut tokens, "longitude", "E", "W", 180)?;
+
+    let altitude = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("altitude".to_string())))
+        .and_then(parse_altitude)?;
+
+    let mut precision = |default: u8| -> ParseResult<u8> {
+        match tokens.next() {
+            Some(token) => Ok(loc::precision_from_centimeters(parse_meters(token)?)?),
+            None => Ok(default),
+        }
+    };
+
+    let size = precision(loc::DEFAULT_SIZE)?;
+    let horiz_pre = precision(loc::DEFAULT_HORIZ_PRE)?;
+    let vert_pre = precision(loc::DEFAULT_VERT_PRE)?;
+
+    Some(LOC::new(
+        size, horiz_pre, vert_pre, latitude, longitude, altitude,
+    ))
+    .filter(|_| tokens.next().is_none())
+    .ok_or_else(|| ParseErrorKind::Message("too many fields for LOC").into())
+}
+
+/// Parses `d [m [s]] hemisphere` into the encoded form, thousandths of a second of arc relative
+///  to `loc::EQUATOR`
+fn parse_coordinate<'i, I: Iterator<Item = &'i str>>(
+    tokens: &mut I,
+    field: &str,
+    positive: &str,
+    negative: &str,
+    max_degrees: u64,
+) -> ParseResult<u32> {
+    // degrees, minutes and seconds, up to the hemisphere
+    let mut parts = Vec::with_capacity(3);
+    let is_positive = loop {
+        let token = tokens
+            .next()
+            .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken(field.to_string())))?;
+
+        if token.eq_ignore_ascii_case(positive) {
+            break true;
+        } else if token.eq_ignore_ascii_case(negative) {
+            break false;
+        } else if parts.len() == 3 {
+            return Err(ParseErrorKind::Msg(format!(
+                "expected {} or {} after the LOC {}: {}",
+                positive, negative, field, token
+            ))
+            .into());
+        }
+
+        parts.push(token);
+    };
+
+    let degrees = parts
+        .first()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken(field.to_string())))
+        .and_then(|s| u64::from_str(s).map_err(Into::into))?;
+    let minutes = parts
+        .get(1)
+        .map(|s| u64::from_str(s))
+        .transpose()?
+        .unwrap_or(0);
+    let seconds = parts
+        .get(2)
+        .map(|s| parse_decimal(s, 3))
+        .transpose()?
+        .unwrap_or(0);
+
+    let out_of_range = || {
+        ParseError::from(ParseErrorKind::Msg(format!(
+            "LOC {} out of range: {}",
+            field,
+            parts.join(" ")
+        )))
+    };
+
+    if degrees > max_degrees || minutes >= 60 || seconds >= 60_000 {
+        return Err(out_of_range());
+    }
+
+    let thousandths = ((degrees * 60 + minutes) * 60) * 1000 + seconds;
+    if thousandths > max_degrees * 3_600_000 {
+        return Err(out_of_range());
+    }
+
+    let equator = u64::from(loc::EQUATOR);
+    let encoded = if is_positive {
+        equator + thousandths
+    } else {
+        equator - thousandths
+    };
+
+    Ok(encoded as u32)
+}
+
+/// Parses `[-]meters[.centimeters][m]` into the encoded form, centimeters relative to
+///  `loc::REFERENCE_ALTITUDE`
+fn parse_altitude(token: &str) -> ParseResult<u32> {
+    let (is_negative, meters) = match token.strip_prefix('-') {
+        Some(meters) => (true, meters),
+        None => (false, token),
+    };
+
+    let centimeters = parse_meters(meters)?;
+    let reference = u64::from(loc::REFERENCE_ALTITUDE);
+    let encoded = if is_negative {
+        reference.checked_sub(centimeters)
+    } else {
+        reference.checked_add(centimeters)
+    };
+
+    encoded
+        .filter(|encoded| *encoded <= u64::from(u32::max_value()))
+        .map(|encoded| encoded as u32)
+        .ok_or_else(|| ParseErrorKind::Msg(format!("LOC altitude out of range: {}", token)).into())
+}
+
+/// Parses `meters[.centimeters][m]` into centimeters
+fn parse_meters(token: &str) -> ParseResult<u64> {
+    let meters = token
+        .strip_suffix('m')
+        .or_else(|| token.strip_suffix('M'))
+        .unwrap_or(token);
+
+    parse_decimal(meters, 2)
+}
+
+/// Parses a decimal with at most `places` fractional digits, scaled up by `10^places`
+fn parse_decimal(token: &str, places: usize) -> ParseResult<u64> {
+    let mut split = token.splitn(2, '.');
+    let whole = split.next().unwrap_or_default();
+    let fraction = split.next().unwrap_or_default();
+
+    if whole.is_empty()
+        || fraction.len() > places
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(ParseErrorKind::Msg(format!(
+            "invalid LOC decimal, at most {} places: {}",
+            places, token
+        ))
+        .into());
+    }
+
+    let fraction = u64::from_str(&format!("{:0<width$}", fraction, width = places))?;
+    u64::from_str(whole)?
+        .checked_mul(10_u64.pow(places as u32))
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(|| ParseErrorKind::Msg(format!("LOC value out of range: {}", token)).into())
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["42", "21", "54", "N"].into_iter()).is_err());
+    assert!(parse(vec!["42", "21", "54", "N", "71", "06", "18", "W"].into_iter()).is_err());
+    assert!(parse(vec!["42", "21", "54", "1", "N", "71", "W", "0m"].into_iter()).is_err());
+    assert!(parse(vec!["91", "N", "71", "W", "0m"].into_iter()).is_err());
+    assert!(parse(vec!["90", "0", "1", "N", "71", "W", "0m"].into_iter()).is_err());
+    assert!(parse(vec!["42", "60", "N", "71", "W", "0m"].into_iter()).is_err());
+    assert!(parse(vec!["42", "N", "181", "W", "0m"].into_iter()).is_err());
+    assert!(parse(vec!["42", "N", "71", "W", "-100000.01m"].into_iter()).is_err());
+    assert!(parse(vec!["42", "N", "71", "W", "1.001m"].into_iter()).is_err());
+    assert!(parse(vec!["42", "N", "71", "W", "0m", "1m", "1m", "1m", "1m"].into_iter()).is_err());
+
+    // cambridge-net.kei.com.        LOC   42 21 54 N 71 06 18 W -24m 30m
+    let rdata =
+        parse(vec!["42", "21", "54", "N", "71", "06", "18", "W", "-24m", "30m"].into_iter())
+            .expect("failed to parse LOC");
+    assert_eq!(
+        rdata,
+        LOC::new(
+            0x33,
+            0x16,
+            0x13,
+            loc::EQUATOR + 152_514_000,
+            loc::EQUATOR - 255_978_000,
+            loc::REFERENCE_ALTITUDE - 2400,
+        )
+    );
+    assert_eq!(
+        rdata.to_string(),
+        "42 21 54.000 N 71 6 18.000 W -24.00m 30m 10000m 10m"
+    );
+}
+
+#[test]
+fn test_parse_display_round_trip() {
+    let examples = [
+        // the remaining examples from RFC 1876
+        "42 21 43.952 N 71 5 6.344 W -24m 1m 200m",
+        "52 14 05 N 00 08 50 E 10m",
+        "32 7 19 S 116 2 25 E 10m",
+        "42 21 28.764 N 71 00 51.617 W -44m 2000m",
+        // minutes and seconds are optional, sizes may be fractional
+        "0 N 0 E 0.5 0.5m 90000000 10m",
+    ];
+
+    for example in &examples {
+        let rdata = parse(example.split(' ')).expect("failed to parse LOC");
+        let presentation = rdata.to_string();
+        let reparsed = parse(presentation.split(' ')).expect("failed to parse LOC display");
+        assert_eq!(rdata, reparsed, "{} != {}", example, presentation);
+    }
+
+    let rdata = parse("32 7 19 S 116 2 25 E 10m".split(' ')).unwrap();
+    assert!((rdata.latitude_degrees() + (32.0 + 7.0 / 60.0 + 19.0 / 3600.0)).abs() < 1e-9);
+    assert!((rdata.longitude_degrees() - (116.0 + 2.0 / 60.0 + 25.0 / 3600.0)).abs() < 1e-9);
+    assert!((rdata.altitude_meters() - 10.0).abs() < 1e-9);
+
+    let rdata = parse("0 N 0 E 0.5 0.5m 90000000 10m".split(' ')).unwrap();
+    assert_eq!(rdata.size(), 0x51);
+    assert_eq!(rdata.horiz_pre(), 0x99);
+    assert_eq!(
+        rdata.to_string(),
+        "0 0 0.000 N 0 0 0.000 E 0.50m 0.50m 90000000m 10m"
+    );
+}
@@ -22,13 +22,20 @@
 pub mod a;
 pub mod aaaa;
 pub mod caa;
+pub mod dnskey;
+pub mod ds;
+pub mod hinfo;
+pub mod loc;
 pub mod mx;
 pub mod name;
 pub mod naptr;
 pub mod null;
 pub mod openpgpkey;
+pub mod rrsig;
 pub mod soa;
 pub mod srv;
 pub mod sshfp;
 pub mod tlsa;
 pub mod txt;
+pub mod unknown;
+pub mod uri;
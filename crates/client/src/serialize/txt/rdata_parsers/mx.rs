@@ -36,3 +36,23 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
 
     Ok(MX::new(preference, exchange))
 }
+
+#[test]
+fn test_parsing() {
+    use std::str::FromStr;
+
+    let origin = Name::from_str("example.com.").unwrap();
+
+    assert!(parse(::std::iter::empty(), Some(&origin)).is_err());
+    assert!(parse(vec!["10"].into_iter(), Some(&origin)).is_err());
+    assert!(parse(vec!["-1", "mail"].into_iter(), Some(&origin)).is_err());
+
+    assert_eq!(
+        parse(vec!["10", "mail"].into_iter(), Some(&origin)).unwrap(),
+        MX::new(10, Name::from_str("mail.example.com.").unwrap())
+    );
+    assert_eq!(
+        parse(vec!["20", "mail.example.net."].into_iter(), Some(&origin)).unwrap(),
+        MX::new(20, Name::from_str("mail.example.net.").unwrap())
+    );
+}
@@ -61,7 +61,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
         .ok_or_else(|| ParseErrorKind::MissingToken("replacement".to_string()).into())
         .and_then(|s| Name::parse(s, origin).map_err(ParseError::from))?;
 
-    Ok(NAPTR::new(
+    Some(NAPTR::new(
         order,
         preference,
         flags,
@@ -69,6 +69,8 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
         regexp,
         replacement,
     ))
+    .filter(|_| tokens.next().is_none())
+    .ok_or_else(|| ParseErrorKind::Message("too many fields for NAPTR").into())
 }
 
 #[test]
@@ -94,6 +96,38 @@ fn test_parsing() {
     );
 }
 
+#[test]
+fn test_parsing_sip() {
+    // IN NAPTR 100 10 "S" "SIP+D2U" "!^.*$!sip:customer-service@example.com!" _sip._udp.example.com.
+    let rdata = parse(
+        vec![
+            "100",
+            "10",
+            "S",
+            "SIP+D2U",
+            "!^.*$!sip:customer-service@example.com!",
+            "_sip._udp.example.com.",
+        ]
+        .into_iter(),
+        None,
+    )
+    .expect("failed to parse NAPTR");
+
+    assert_eq!(rdata.order(), 100);
+    assert_eq!(rdata.preference(), 10);
+    assert_eq!(rdata.flags(), b"S");
+    assert_eq!(rdata.services(), b"SIP+D2U");
+    assert_eq!(rdata.regexp(), b"!^.*$!sip:customer-service@example.com!");
+    assert_eq!(
+        *rdata.replacement(),
+        Name::from_str("_sip._udp.example.com.").unwrap()
+    );
+    assert_eq!(
+        rdata.to_string(),
+        r#"100 10 "S" "SIP+D2U" "!^.*$!sip:customer-service@example.com!" _sip._udp.example.com."#
+    );
+}
+
 #[test]
 fn test_parsing_fails() {
     // IN NAPTR 100  50  "a"    "z3950+N2L+N2C"     ""   cidserver.example.com.
@@ -105,4 +139,9 @@ fn test_parsing_fails() {
         Some(&Name::from_str("example.com.").unwrap())
     )
     .is_err());
+    assert!(parse(
+        vec!["100", "50", "a", "z3950+N2L+N2C", "", "cidserver", "extra"].into_iter(),
+        Some(&Name::from_str("example.com.").unwrap())
+    )
+    .is_err());
 }
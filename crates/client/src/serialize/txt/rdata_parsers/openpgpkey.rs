@@ -8,6 +8,7 @@
 //! OPENPGPKEY records for OpenPGP public keys
 
 use crate::error::*;
+use crate::proto::serialize::txt::from_base64;
 use crate::rr::rdata::OPENPGPKEY;
 
 /// Parse the RData from a set of tokens.
@@ -26,7 +27,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<OPEN
     let encoded_public_key = tokens.next().ok_or(ParseErrorKind::Message(
         "OPENPGPKEY public key field is missing",
     ))?;
-    let public_key = data_encoding::BASE64.decode(encoded_public_key.as_bytes())?;
+    let public_key = from_base64(encoded_public_key)?;
     Some(OPENPGPKEY::new(public_key))
         .filter(|_| tokens.next().is_none())
         .ok_or_else(|| ParseErrorKind::Message("too many fields for OPENPGPKEY").into())
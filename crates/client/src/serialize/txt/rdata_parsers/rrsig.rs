@@ -0,0 +1,243 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! signature records for DNSSEC signed RRSets
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+
+use super::ds::parse_algorithm;
+use crate::error::*;
+use crate::proto::serialize::txt::from_base64;
+use crate::rr::rdata::SIG;
+use crate::rr::{Name, RecordType};
+
+/// Parses a signature timestamp, either as `YYYYMMDDHHmmSS` in UTC or as seconds since the epoch
+fn parse_timestamp(token: &str) -> ParseResult<u32> {
+    if token.len() == 14 && token.bytes().all(|b| b.is_ascii_digit()) {
+        let timestamp = NaiveDateTime::parse_from_str(token, "%Y%m%d%H%M%S")
+            .map_err(|_| ParseErrorKind::Msg(format!("invalid RRSIG timestamp: {}", token)))?
+            .timestamp();
+
+        return u32::try_from(timestamp).map_err(|_| {
+            ParseErrorKind::Msg(format!("RRSIG timestamp out of range: {}", token)).into()
+        });
+    }
+
+    u32::from_str(token).map_err(Into::into)
+}
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 4034, DNSSEC Resource Records, March 2005](https://tools.ietf.org/html/rfc4034#section-3.2)
+///
+/// ```text
+/// 3.2.  The RRSIG RR Presentation Format
+///
+///    The presentation format of the RDATA portion is as follows:
+///
+///    The Type Covered field is represented as an RR type mnemonic.  When
+///    the mnemonic is not known, the TYPE representation as described in
+///    [RFC3597], Section 5, MUST be used.
+///
+///    The Algorithm field value MUST be represented either as an unsigned
+///    decimal integer or as an algorithm mnemonic, as specified in Appendix
+///    A.1.
+///
+///    The Labels field value MUST be represented as an unsigned decimal
+///    integer.
+///
+///    The Original TTL field value MUST be represented as an unsigned
+///    decimal integer.
+///
+///    The Signature Expiration Time and Inception Time field values MUST be
+///    represented either as an unsigned decimal integer indicating seconds
+///    since 1 January 1970 00:00:00 UTC, or in the form YYYYMMDDHHmmSS in
+///    UTC, where:
+///
+///       YYYY is the year (0001-9999, but see Section 3.1.5);
+///       MM is the month number (01-12);
+///       DD is the day of the month (01-31);
+///       HH is the hour, in 24 hour notation (00-23);
+///       mm is the minute (00-59); and
+///       SS is the second (00-59).
+///
+///    Note that it is always possible to distinguish between these two
+///    formats because the YYYYMMDDHHmmSS format will always be exactly 14
+///    digits, while the decimal representation of a 32-bit unsigned
+///    integer can never be longer than 10 digits.
+///
+///    The Key Tag field MUST be represented as an unsigned decimal integer.
+///
+///    The Signer's Name field value MUST be represented as a domain name.
+///
+///    The Signature field is represented as a Base64 encoding of the
+///    signature.  Whitespace is allowed within the Base64 text.  See
+///    Section 3.1.8.
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(
+    mut tokens: I,
+    origin: Option<&Name>,
+) -> ParseResult<SIG> {
+    let type_covered: RecordType = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("type covered".to_string())))
+        .and_then(|s| RecordType::from_str(&s.to_ascii_uppercase()).map_err(Into::into))?;
+
+    let algorithm = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("algorithm".to_string())))
+        .and_then(parse_algorithm)?;
+
+    let num_labels: u8 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("labels".to_string())))
+        .and_then(|s| u8::from_str(s).map_err(Into::into))?;
+
+    let original_ttl: u32 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("original ttl".to_string())))
+        .and_then(|s| u32::from_str(s).map_err(Into::into))?;
+
+    let sig_expiration = tokens
+        .next()
+        .ok_or_else(|| {
+            ParseError::from(ParseErrorKind::MissingToken(
+                "signature expiration".to_string(),
+            ))
+        })
+        .and_then(parse_timestamp)?;
+
+    let sig_inception = tokens
+        .next()
+        .ok_or_else(|| {
+            ParseError::from(ParseErrorKind::MissingToken(
+                "signature inception".to_string(),
+            ))
+        })
+        .and_then(parse_timestamp)?;
+
+    let key_tag: u16 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("key tag".to_string())))
+        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+
+    let signer_name: Name = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("signer name".to_string())))
+        .and_then(|s| Name::parse(s, origin).map_err(ParseError::from))?;
+
+    let sig = tokens.collect::<String>();
+    if sig.is_empty() {
+        return Err(ParseErrorKind::MissingToken("signature".to_string()).into());
+    }
+    let sig = from_base64(&sig)?;
+
+    Ok(SIG::new(
+        type_covered,
+        algorithm,
+        num_labels,
+        original_ttl,
+        sig_expiration,
+        sig_inception,
+        key_tag,
+        signer_name,
+        sig,
+    ))
+}
+
+#[test]
+fn test_parse_timestamp() {
+    assert_eq!(parse_timestamp("20030322173103").unwrap(), 1_048_354_263);
+    assert_eq!(parse_timestamp("1048354263").unwrap(), 1_048_354_263);
+    assert_eq!(parse_timestamp("19700101000000").unwrap(), 0);
+    assert_eq!(parse_timestamp("21060207062815").unwrap(), u32::max_value());
+
+    // past the end of the 32-bit range
+    assert!(parse_timestamp("21060207062816").is_err());
+    // before the epoch
+    assert!(parse_timestamp("19691231235959").is_err());
+    // no 13th month
+    assert!(parse_timestamp("20031322173103").is_err());
+    assert!(parse_timestamp("4294967296").is_err());
+    assert!(parse_timestamp("2003-03-22").is_err());
+}
+
+#[test]
+fn test_parsing() {
+    use crate::rr::dnssec::Algorithm;
+
+    let origin = Name::from_str("example.").unwrap();
+
+    assert!(parse(::std::iter::empty(), None).is_err());
+    assert!(parse(
+        "A 5 3 86400 20030322173103 20030220173103 2642 example.".split(' '),
+        None
+    )
+    .is_err());
+    assert!(parse(
+        "A 5 3 86400 20030322173103 20030220173103 2642 example. not_base64!".split(' '),
+        None
+    )
+    .is_err());
+
+    // host.example.com. 86400 IN RRSIG A 5 3 86400 20030322173103 (
+    //                                  20030220173103 2642 example.com.
+    //                                  oJB1W6WNGv+ldvQ3WDG0MQkg5IEhjRip8WTr
+    //                                  ...
+    //                                  J5D6fwFm8nN+6pBzeDQfsS3Ap3o= )
+    let tokens = vec![
+        "A",
+        "5",
+        "3",
+        "86400",
+        "20030322173103",
+        "20030220173103",
+        "2642",
+        "example.com.",
+        "oJB1W6WNGv+ldvQ3WDG0MQkg5IEhjRip8WTr",
+        "PYGv07h108dUKGMeDPKijVCHX3DDKdfb+v6o",
+        "B9wfuh3DTJXUAfI/M0zmO/zz8bW0Rznl8O3t",
+        "GNazPwQKkRN20XPXV6nwwfoXmJQbsLNrLfkG",
+        "J5D6fwFm8nN+6pBzeDQfsS3Ap3o=",
+    ];
+    let rdata = parse(tokens.iter().copied(), Some(&origin)).expect("failed to parse RRSIG");
+
+    assert_eq!(rdata.type_covered(), RecordType::A);
+    assert_eq!(rdata.algorithm(), Algorithm::RSASHA1);
+    assert_eq!(rdata.num_labels(), 3);
+    assert_eq!(rdata.original_ttl(), 86400);
+    assert_eq!(rdata.sig_expiration(), 1_048_354_263);
+    assert_eq!(rdata.sig_inception(), 1_045_762_263);
+    assert_eq!(rdata.key_tag(), 2642);
+    assert_eq!(
+        rdata.signer_name(),
+        &Name::from_str("example.com.").unwrap()
+    );
+    assert_eq!(
+        data_encoding::BASE64.encode(rdata.sig()),
+        tokens[8..].concat()
+    );
+
+    // the same record, with a mnemonic algorithm and raw timestamps, and a relative signer
+    let rdata2 = parse(
+        "a RSASHA1 3 86400 1048354263 1045762263 2642 com"
+            .split(' ')
+            .chain(tokens[8..].iter().copied()),
+        Some(&origin),
+    )
+    .expect("failed to parse RRSIG");
+    assert_eq!(
+        rdata2.signer_name(),
+        &Name::from_str("com.example.").unwrap()
+    );
+
+    // round trip through the presentation format
+    assert_eq!(parse(rdata.to_string().split(' '), None).unwrap(), rdata);
+}
@@ -61,7 +61,57 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
         .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("minimum".to_string())))
         .and_then(|s| u32::from_str(s).map_err(Into::into))?;
 
-    Ok(SOA::new(
+    Some(SOA::new(
         mname, rname, serial, refresh, retry, expire, minimum,
     ))
+    .filter(|_| tokens.next().is_none())
+    .ok_or_else(|| ParseErrorKind::Message("too many fields for SOA").into())
+}
+
+#[test]
+fn test_parsing() {
+    let origin = Name::from_str("example.com.").unwrap();
+    let rdata = parse(
+        vec![
+            "ns1",
+            "hostmaster",
+            "2020111701",
+            "7200",
+            "3600",
+            "1209600",
+            "3600",
+        ]
+        .into_iter(),
+        Some(&origin),
+    )
+    .expect("failed to parse SOA");
+
+    assert_eq!(*rdata.mname(), Name::from_str("ns1.example.com.").unwrap());
+    assert_eq!(
+        *rdata.rname(),
+        Name::from_str("hostmaster.example.com.").unwrap()
+    );
+    assert_eq!(rdata.serial(), 2_020_111_701);
+    assert_eq!(rdata.refresh(), 7200);
+    assert_eq!(rdata.retry(), 3600);
+    assert_eq!(rdata.expire(), 1_209_600);
+    assert_eq!(rdata.minimum(), 3600);
+
+    let presentation = rdata.to_string();
+    assert_eq!(
+        presentation,
+        "ns1.example.com. hostmaster.example.com. 2020111701 7200 3600 1209600 3600"
+    );
+    assert_eq!(parse(presentation.split(' '), None).unwrap(), rdata);
+
+    assert!(parse(
+        vec!["ns1", "hostmaster", "1", "2", "3", "4"].into_iter(),
+        Some(&origin)
+    )
+    .is_err());
+    assert!(parse(
+        vec!["ns1", "hostmaster", "1", "2", "3", "4", "5", "6"].into_iter(),
+        Some(&origin)
+    )
+    .is_err());
 }
@@ -21,6 +21,49 @@ use crate::error::*;
 use crate::rr::domain::Name;
 use crate::rr::rdata::SRV;
 
+/// Well known service names and their ports, from the IANA service name and port number registry
+///
+/// This is a bundled subset rather than the system's services database, so that zone files parse
+///  the same everywhere.
+const SERVICE_PORTS: &[(&str, u16)] = &[
+    ("ftp", 21),
+    ("ssh", 22),
+    ("telnet", 23),
+    ("smtp", 25),
+    ("domain", 53),
+    ("http", 80),
+    ("kerberos", 88),
+    ("pop3", 110),
+    ("ntp", 123),
+    ("imap", 143),
+    ("snmp", 161),
+    ("ldap", 389),
+    ("https", 443),
+    ("submission", 587),
+    ("ldaps", 636),
+    ("domain-s", 853),
+    ("imaps", 993),
+    ("pop3s", 995),
+    ("sip", 5060),
+    ("sips", 5061),
+    ("xmpp-client", 5222),
+    ("xmpp-server", 5269),
+];
+
+/// Parses a port, either as a number or as a well known service name such as `http`
+fn parse_port(token: &str) -> ParseResult<u16> {
+    // numbers are never service names, report an out of range port as an integer error
+    if !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+        return u16::from_str(token).map_err(Into::into);
+    }
+
+    SERVICE_PORTS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(token))
+        .map(|(_, port)| *port)
+        .ok_or_else(|| ParseErrorKind::Msg(format!("unknown service for port: {}", token)).into())
+}
+
 /// Parse the RData from a set of Tokens
 pub fn parse<'i, I: Iterator<Item = &'i str>>(
     mut tokens: I,
@@ -39,7 +82,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
     let port: u16 = tokens
         .next()
         .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("port".to_string())))
-        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+        .and_then(parse_port)?;
 
     let target: Name = tokens
         .next()
@@ -48,3 +91,44 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(
 
     Ok(SRV::new(priority, weight, port, target))
 }
+
+#[test]
+fn test_parse_port() {
+    assert_eq!(parse_port("80").unwrap(), 80);
+    assert_eq!(parse_port("http").unwrap(), 80);
+    assert_eq!(parse_port("HTTPS").unwrap(), 443);
+    assert_eq!(parse_port("65535").unwrap(), 65535);
+
+    assert!(matches!(
+        parse_port("65536").unwrap_err().kind(),
+        ParseErrorKind::ParseInt(_)
+    ));
+    assert!(parse_port("no-such-service").is_err());
+    assert!(parse_port("").is_err());
+}
+
+#[test]
+fn test_parsing() {
+    let origin = Name::from_str("example.com.").unwrap();
+
+    let numeric = parse("0 5 80 www".split(' '), Some(&origin)).expect("failed to parse SRV");
+    let named = parse("0 5 http www".split(' '), Some(&origin)).expect("failed to parse SRV");
+
+    assert_eq!(numeric, named);
+    assert_eq!(named.port(), 80);
+    assert_eq!(named.target(), &Name::from_str("www.example.com.").unwrap());
+
+    assert!(parse("0 5 gopher-plus www".split(' '), Some(&origin)).is_err());
+}
+
+#[test]
+fn test_parse_root_target() {
+    let origin = Name::from_str("example.com.").unwrap();
+
+    // the service is decidedly not available at this domain, RFC 2782
+    let rdata = parse("0 0 0 .".split(' '), Some(&origin)).expect("failed to parse SRV");
+    assert!(rdata.target().is_root());
+    assert_eq!(rdata.to_string(), "0 0 0 .");
+
+    assert_eq!(parse(rdata.to_string().split(' '), None).unwrap(), rdata);
+}
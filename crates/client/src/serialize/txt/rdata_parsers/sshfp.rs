@@ -8,7 +8,8 @@
 //! SSHFP records for SSH public key fingerprints
 
 use crate::error::*;
-use crate::rr::rdata::{sshfp, SSHFP};
+use crate::proto::serialize::txt::from_hex;
+use crate::rr::rdata::SSHFP;
 
 /// Parse the RData from a set of Tokens
 ///
@@ -41,12 +42,11 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<SSHF
             parse_u8("fingerprint type")?.into(),
         )
     };
-    let fingerprint = sshfp::HEX.decode(
+    let fingerprint = from_hex(
         tokens
             .next()
             .filter(|fp| !fp.is_empty())
-            .ok_or_else(|| missing_field::<ParseError>("fingerprint"))?
-            .as_bytes(),
+            .ok_or_else(|| missing_field::<ParseError>("fingerprint"))?,
     )?;
     Some(SSHFP::new(algorithm, fingerprint_type, fingerprint))
         .filter(|_| tokens.next().is_none())
@@ -158,3 +158,19 @@ fn test_parsing() {
         ],
     );
 }
+
+#[test]
+fn test_parse_display_round_trip() {
+    use crate::rr::rdata::sshfp::{Algorithm, FingerprintType};
+
+    let presentation = "4 2 a87f1b687ac0e57d2a081a2f282672334d90ed316d2b818ca9580ea384d92401";
+    let rdata = parse(presentation.split(' ')).expect("failed to parse SSHFP");
+    assert_eq!(rdata.algorithm(), Algorithm::Ed25519);
+    assert_eq!(rdata.fingerprint_type(), FingerprintType::SHA256);
+    assert_eq!(rdata.fingerprint().len(), 32);
+    assert_eq!(rdata.to_string(), presentation);
+
+    // upper case hex is accepted, but always displayed in lower case
+    let rdata = parse(presentation.to_uppercase().split(' ')).expect("failed to parse SSHFP");
+    assert_eq!(rdata.to_string(), presentation);
+}
@@ -9,8 +9,9 @@
 //! tlsa records for storing TLS authentication records
 
 use crate::error::*;
+use crate::proto::serialize::txt::from_hex;
 use crate::rr::rdata::tlsa::CertUsage;
-use crate::rr::rdata::{sshfp, TLSA};
+use crate::rr::rdata::TLSA;
 
 fn to_u8(data: &str) -> ParseResult<u8> {
     u8::from_str_radix(data, 10).map_err(ParseError::from)
@@ -63,7 +64,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(tokens: I) -> ParseResult<TLSA> {
         cert_data.push_str(data);
         cert_data
     });
-    let cert_data = sshfp::HEX.decode(cert_data.as_bytes())?;
+    let cert_data = from_hex(&cert_data)?;
 
     if !cert_data.is_empty() {
         Ok(TLSA::new(usage, selector, matching, cert_data))
@@ -75,6 +76,7 @@ pub fn parse<'i, I: Iterator<Item = &'i str>>(tokens: I) -> ParseResult<TLSA> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rr::rdata::tlsa::{Matching, Selector};
 
     #[test]
     fn test_parsing() {
@@ -103,4 +105,31 @@ mod tests {
         )
         .is_ok());
     }
+
+    #[test]
+    fn test_parse_display_round_trip() {
+        let rdata = parse(
+            vec![
+                "3",
+                "1",
+                "1",
+                "D2ABDE240D7CD3EE6B4B28C54DF034B9",
+                "7983a1d16e8a410e4561cb106618e971",
+            ]
+            .into_iter(),
+        )
+        .expect("failed to parse TLSA");
+
+        assert_eq!(rdata.cert_usage(), CertUsage::DomainIssued);
+        assert_eq!(rdata.selector(), Selector::Spki);
+        assert_eq!(rdata.matching(), Matching::Sha256);
+        assert_eq!(rdata.cert_data().len(), 32);
+
+        let presentation = rdata.to_string();
+        assert_eq!(
+            presentation,
+            "3 1 1 d2abde240d7cd3ee6b4b28c54df034b97983a1d16e8a410e4561cb106618e971"
+        );
+        assert_eq!(parse(presentation.split(' ')).unwrap(), rdata);
+    }
 }
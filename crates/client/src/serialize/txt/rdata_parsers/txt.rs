@@ -20,7 +20,43 @@ use crate::error::*;
 use crate::rr::rdata::TXT;
 
 /// Parse the RData from a set of Tokens
-pub fn parse<'i, I: Iterator<Item = &'i str>>(tokens: I) -> ParseResult<TXT> {
-    let txt_data: Vec<String> = tokens.map(ToString::to_string).collect();
-    Ok(TXT::new(txt_data))
+///
+/// Each token is a single <character-string>, which may be at most 255 bytes. The tokens are
+///  octets, as `\DDD` escapes in quoted strings need not be UTF-8.
+pub fn parse<'i, I: Iterator<Item = &'i [u8]>>(tokens: I) -> ParseResult<TXT> {
+    let txt_data: Vec<&[u8]> = tokens
+        .map(|s| {
+            if s.len() > 255 {
+                Err(ParseErrorKind::Msg(format!(
+                    "TXT character-string exceeds 255 bytes: {}",
+                    s.len()
+                ))
+                .into())
+            } else {
+                Ok(s)
+            }
+        })
+        .collect::<ParseResult<_>>()?;
+    Ok(TXT::from_bytes(txt_data))
+}
+
+#[test]
+fn test_parsing() {
+    let txt = parse(vec![&b"I am a different"[..], b"txt record"].into_iter()).unwrap();
+    assert_eq!(
+        txt.txt_data(),
+        &[
+            b"I am a different".to_vec().into_boxed_slice(),
+            b"txt record".to_vec().into_boxed_slice()
+        ]
+    );
+
+    let txt = parse(vec![&[200, 0][..]].into_iter()).unwrap();
+    assert_eq!(txt.txt_data(), &[vec![200, 0].into_boxed_slice()]);
+
+    let max = "a".repeat(255);
+    assert!(parse(vec![max.as_bytes()].into_iter()).is_ok());
+
+    let too_long = "a".repeat(256);
+    assert!(parse(vec![&b"ok"[..], too_long.as_bytes()].into_iter()).is_err());
 }
@@ -0,0 +1,89 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! generic rdata for record types which are not otherwise supported
+
+use crate::error::*;
+use crate::proto::serialize::txt::from_hex;
+use crate::rr::rdata::NULL;
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 3597, Handling of Unknown DNS Resource Record (RR) Types, September 2003](https://tools.ietf.org/html/rfc3597#section-5)
+///
+/// ```text
+/// 5.  Text Representation
+///
+///    The RDATA section of an RR of unknown type is represented as a
+///    sequence of white space separated words as follows:
+///
+///       The special token \# (a backslash immediately followed by a hash
+///       sign), which identifies the RDATA as having the generic encoding
+///       defined herein rather than a traditional type-specific encoding.
+///
+///       An unsigned decimal integer specifying the RDATA length in octets.
+///
+///       Zero or more words of hexadecimal data encoding the actual RDATA
+///       field, each containing an even number of hexadecimal digits.
+///
+///    e.g. a.example.   CLASS32     TYPE731         \# 6 abcd (
+///                                                  ef 01 23 45 )
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<NULL> {
+    match tokens.next() {
+        Some("\\#") => (),
+        Some(token) => {
+            return Err(ParseErrorKind::Msg(format!(
+                "expected \\# for generic rdata, found: {}",
+                token
+            ))
+            .into())
+        }
+        None => return Err(ParseErrorKind::MissingToken("\\#".to_string()).into()),
+    }
+
+    let length: usize = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("length".to_string())))
+        .and_then(|s| s.parse().map_err(Into::into))?;
+
+    let hex: String = tokens.collect();
+    let data = from_hex(&hex)?;
+
+    if data.len() != length {
+        return Err(ParseErrorKind::Msg(format!(
+            "generic rdata length {} does not match data length {}",
+            length,
+            data.len()
+        ))
+        .into());
+    }
+
+    if data.is_empty() {
+        Ok(NULL::new())
+    } else {
+        Ok(NULL::with(data))
+    }
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["abcd"].into_iter()).is_err());
+    assert!(parse(vec!["\\#"].into_iter()).is_err());
+    assert!(parse(vec!["\\#", "3", "abcd"].into_iter()).is_err());
+    assert!(parse(vec!["\\#", "2", "abc"].into_iter()).is_err());
+
+    assert_eq!(parse(vec!["\\#", "0"].into_iter()).unwrap(), NULL::new());
+
+    let rdata = parse(vec!["\\#", "6", "abcd", "EF", "012345"].into_iter())
+        .expect("failed to parse generic rdata");
+    assert_eq!(
+        rdata.anything(),
+        Some(&[0xab, 0xcd, 0xef, 0x01, 0x23, 0x45][..])
+    );
+}
@@ -0,0 +1,74 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! uniform resource identifier records for mapping names to URIs
+
+use std::str::FromStr;
+
+use crate::error::*;
+use crate::rr::rdata::URI;
+
+/// Parse the RData from a set of Tokens
+///
+/// [RFC 7553, The Uniform Resource Identifier (URI) DNS Resource Record, June 2015](https://tools.ietf.org/html/rfc7553#section-4.4)
+///
+/// ```text
+/// 4.4.  URI RR Presentation Format
+///
+///    The presentation format of the RDATA portion is as follows:
+///
+///    o  The Priority field is represented as an integer.
+///
+///    o  The Weight field is represented as an integer.
+///
+///    o  The Target field is represented as a <character-string>
+///       enclosed in double-quote characters
+/// ```
+pub fn parse<'i, I: Iterator<Item = &'i str>>(mut tokens: I) -> ParseResult<URI> {
+    let priority: u16 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("priority".to_string())))
+        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+
+    let weight: u16 = tokens
+        .next()
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("weight".to_string())))
+        .and_then(|s| u16::from_str(s).map_err(Into::into))?;
+
+    let target = tokens
+        .next()
+        .filter(|target| !target.is_empty())
+        .ok_or_else(|| ParseError::from(ParseErrorKind::MissingToken("target".to_string())))?;
+
+    Some(URI::new(priority, weight, target.to_string()))
+        .filter(|_| tokens.next().is_none())
+        .ok_or_else(|| ParseErrorKind::Message("too many fields for URI").into())
+}
+
+#[test]
+fn test_parsing() {
+    assert!(parse(::std::iter::empty()).is_err());
+    assert!(parse(vec!["10", "1"].into_iter()).is_err());
+    assert!(parse(vec!["10", "1", ""].into_iter()).is_err());
+    assert!(parse(vec!["10", "-1", "ftp://ftp1.example.com/public"].into_iter()).is_err());
+    assert!(parse(vec!["10", "1", "ftp://ftp1.example.com/public", "extra"].into_iter()).is_err());
+
+    // _http._tcp IN URI 10 1 "https://www.example.com/path?a=1;b=2&c=%20#frag"
+    let rdata =
+        parse(vec!["10", "1", "https://www.example.com/path?a=1;b=2&c=%20#frag"].into_iter())
+            .expect("failed to parse URI");
+    assert_eq!(rdata.priority(), 10);
+    assert_eq!(rdata.weight(), 1);
+    assert_eq!(
+        rdata.target(),
+        "https://www.example.com/path?a=1;b=2&c=%20#frag"
+    );
+    assert_eq!(
+        rdata.to_string(),
+        r#"10 1 "https://www.example.com/path?a=1;b=2&c=%20#frag""#
+    );
+}
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 use std::collections::BTreeMap;
-use std::str::FromStr;
+use std::str::{self, FromStr};
 
 use crate::error::*;
 use crate::rr::{DNSClass, LowerName, Name, RData, Record, RecordSet, RecordType, RrKey};
@@ -122,12 +122,34 @@ use crate::serialize::txt::zone_lex::{Lexer, Token};
 ///                 the line is ignored.
 /// ```
 #[derive(Default)]
-pub struct Parser;
+pub struct Parser {
+    lenient: bool,
+    errors: Vec<ParseError>,
+}
 
 impl Parser {
-    /// Returns a new Zone file parser
+    /// Returns a new Zone file parser, which is strict by default
     pub fn new() -> Self {
-        Parser
+        Parser::default()
+    }
+
+    /// If set to true, records which fail to parse are skipped rather than failing the whole file
+    ///
+    /// Each skipped record is recorded, with its line, in `errors`. Errors in the structure of the
+    ///  file itself, such as a bad `$ORIGIN` or unknown record type, still fail the parse.
+    pub fn set_lenient(&mut self, lenient: bool) -> &mut Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Returns true if the parser skips records which fail to parse
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// The errors for records which were skipped during the last `parse`, only set when lenient
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
     }
 
     /// Parse a file from the Lexer
@@ -135,6 +157,8 @@ impl Parser {
     /// # Return
     ///
     /// A pair of the Zone origin name and a map of all Keys to RecordSets
+    ///
+    /// Errors are annotated with the line of the zone file on which they occurred.
     pub fn parse(
         &mut self,
         lexer: Lexer,
@@ -142,13 +166,29 @@ impl Parser {
         class: Option<DNSClass>,
     ) -> ParseResult<(Name, BTreeMap<RrKey, RecordSet>)> {
         let mut lexer = lexer;
+        self.errors.clear();
+        self.parse_lexer(&mut lexer, origin, class)
+            .map_err(|e| e.with_line(lexer.line()))
+    }
+
+    fn parse_lexer(
+        &mut self,
+        lexer: &mut Lexer,
+        origin: Option<Name>,
+        class: Option<DNSClass>,
+    ) -> ParseResult<(Name, BTreeMap<RrKey, RecordSet>)> {
         let mut records: BTreeMap<RrKey, RecordSet> = BTreeMap::new();
 
         let mut origin: Option<Name> = origin;
         let mut class: Option<DNSClass> = class;
         let mut current_name: Option<Name> = None;
         let mut rtype: Option<RecordType> = None;
+        // the TTL of the current record, if explicitly specified on its line
         let mut ttl: Option<u32> = None;
+        // the default TTL from $TTL, which takes precedence over the last stated TTL
+        let mut default_ttl: Option<u32> = None;
+        // without $TTL, the last stated TTL is used for records which do not specify one (RFC 1035)
+        let mut last_ttl: Option<u32> = None;
         let mut state = State::StartLine;
 
         while let Some(t) = lexer.next_token()? {
@@ -156,6 +196,7 @@ impl Parser {
                 State::StartLine => {
                     // current_name is not reset on the next line b/c it might be needed from the previous
                     rtype = None;
+                    ttl = None;
 
                     match t {
                         // if Dollar, then $INCLUDE or $ORIGIN
@@ -167,7 +208,8 @@ impl Parser {
 
                         // if CharData, then Name then ttl_class_type
                         Token::CharData(data) => {
-                            current_name = Some(Name::parse(&data, origin.as_ref())?);
+                            let name = str::from_utf8(&data)?;
+                            current_name = Some(Name::parse(name, origin.as_ref())?);
                             State::TtlClassType
                         }
 
@@ -185,7 +227,7 @@ impl Parser {
                 }
                 State::Ttl => match t {
                     Token::CharData(data) => {
-                        ttl = Some(Self::parse_time(&data)?);
+                        default_ttl = Some(Self::parse_time(str::from_utf8(&data)?)?);
                         State::StartLine
                     }
                     _ => return Err(ParseErrorKind::UnexpectedToken(t).into()),
@@ -193,8 +235,9 @@ impl Parser {
                 State::Origin => {
                     match t {
                         Token::CharData(data) => {
-                            // TODO an origin was specified, should this be legal? definitely confusing...
-                            origin = Some(Name::parse(&data, None)?);
+                            // a relative origin is appended to the current origin
+                            let name = str::from_utf8(&data)?;
+                            origin = Some(Name::parse(name, origin.as_ref())?);
                             State::StartLine
                         }
                         _ => return Err(ParseErrorKind::UnexpectedToken(t).into()),
@@ -208,7 +251,9 @@ impl Parser {
                         // if number, TTL
                         // Token::Number(ref num) => ttl = Some(*num),
                         // One of Class or Type (these cannot be overlapping!)
-                        Token::CharData(mut data) => {
+                        Token::CharData(data) => {
+                            let mut data = String::from_utf8(data).map_err(|e| e.utf8_error())?;
+
                             // if it's a number it's a ttl
                             let result: ParseResult<u32> = Self::parse_time(&data);
                             if result.is_ok() {
@@ -240,15 +285,18 @@ impl Parser {
                     //  tokens to pass into the processor
                     match t {
                         Token::EOL => {
-                            Self::flush_record(
+                            let mut record_ttl = ttl.or(default_ttl).or(last_ttl);
+                            let result = Self::flush_record(
                                 record_parts,
                                 &origin,
                                 &current_name,
                                 rtype,
-                                &mut ttl,
+                                &mut record_ttl,
                                 class,
                                 &mut records,
-                            )?;
+                            );
+                            self.skip_if_lenient(result, lexer)?;
+                            last_ttl = record_ttl;
                             State::StartLine
                         }
                         Token::CharData(part) => {
@@ -270,15 +318,17 @@ impl Parser {
 
         //Extra flush at the end for the case of missing endline
         if let State::Record(record_parts) = state {
-            Self::flush_record(
+            let mut record_ttl = ttl.or(default_ttl).or(last_ttl);
+            let result = Self::flush_record(
                 record_parts,
                 &origin,
                 &current_name,
                 rtype,
-                &mut ttl,
+                &mut record_ttl,
                 class,
                 &mut records,
-            )?;
+            );
+            self.skip_if_lenient(result, lexer)?;
         }
 
         //
@@ -289,8 +339,19 @@ impl Parser {
         Ok((origin, records))
     }
 
+    /// In lenient mode a failed record is recorded with its line and parsing continues
+    fn skip_if_lenient(&mut self, result: ParseResult<()>, lexer: &Lexer) -> ParseResult<()> {
+        match result {
+            Err(e) if self.lenient => {
+                self.errors.push(e.with_line(lexer.line()));
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
     fn flush_record(
-        record_parts: Vec<String>,
+        record_parts: Vec<Vec<u8>>,
         origin: &Option<Name>,
         current_name: &Option<Name>,
         rtype: Option<RecordType>,
@@ -447,7 +508,7 @@ enum State {
     StartLine,    // start of line, @, $<WORD>, Name, Blank
     TtlClassType, // [<TTL>] [<class>] <type>,
     Ttl,          // $TTL <time>
-    Record(Vec<String>),
+    Record(Vec<Vec<u8>>),
     Include, // $INCLUDE <filename>
     Origin,
 }
@@ -15,6 +15,8 @@ use crate::error::*;
 pub struct Lexer<'a> {
     txt: Peekable<Chars<'a>>,
     state: State,
+    line: usize,
+    token_line: usize,
 }
 
 impl<'a> Lexer<'a> {
@@ -23,13 +25,22 @@ impl<'a> Lexer<'a> {
         Lexer {
             txt: txt.chars().peekable(),
             state: State::StartLine,
+            line: 1,
+            token_line: 1,
         }
     }
 
+    /// Returns the line, starting at 1, on which the most recently returned token started
+    pub fn line(&self) -> usize {
+        self.token_line
+    }
+
     /// Return the next Token in the string
     pub fn next_token(&mut self) -> LexerResult<Option<Token>> {
-        let mut char_data_vec: Option<Vec<String>> = None;
-        let mut char_data: Option<String> = None;
+        self.token_line = self.line;
+
+        let mut char_data_vec: Option<Vec<Vec<u8>>> = None;
+        let mut char_data: Option<Vec<u8>> = None;
 
         for i in 0..4096 {
             // max chars in a single lex, helps with issues in the lexer...
@@ -62,7 +73,7 @@ impl<'a> Lexer<'a> {
                     match ch {
                         Some('@') => self.state = State::At,
                         Some('(') => {
-                            self.txt.next();
+                            self.next_char();
                             char_data_vec = Some(Vec::new());
                             self.state = State::List;
                         }
@@ -70,24 +81,24 @@ impl<'a> Lexer<'a> {
                             return Err(LexerErrorKind::IllegalCharacter(ch.unwrap_or(')')).into())
                         }
                         Some('$') => {
-                            self.txt.next();
-                            char_data = Some(String::new());
+                            self.next_char();
+                            char_data = Some(Vec::new());
                             self.state = State::Dollar;
                         }
                         Some('\r') | Some('\n') => {
                             self.state = State::EOL;
                         }
                         Some('"') => {
-                            self.txt.next();
-                            char_data = Some(String::new());
-                            self.state = State::Quote;
+                            self.next_char();
+                            char_data = Some(Vec::new());
+                            self.state = State::Quote { is_list: false };
                         }
                         Some(';') => self.state = State::Comment { is_list: false },
                         Some(ch) if ch.is_whitespace() => {
-                            self.txt.next();
+                            self.next_char();
                         } // gobble other whitespace
                         Some(ch) if !ch.is_control() && !ch.is_whitespace() => {
-                            char_data = Some(String::new());
+                            char_data = Some(Vec::new());
                             self.state = State::CharData { is_list: false };
                         }
                         Some(ch) => return Err(LexerErrorKind::UnrecognizedChar(ch).into()),
@@ -98,7 +109,7 @@ impl<'a> Lexer<'a> {
                 }
                 State::Blank => {
                     // consume the whitespace
-                    self.txt.next();
+                    self.next_char();
                     self.state = State::RestOfLine;
                     return Ok(Some(Token::Blank));
                 }
@@ -112,29 +123,40 @@ impl<'a> Lexer<'a> {
                             }
                         } // out of the comment
                         Some(_) => {
-                            self.txt.next();
+                            self.next_char();
                         } // advance the token by default and maintain state
                         None => {
                             self.state = State::EOF;
                         }
                     }
                 }
-                State::Quote => {
+                State::Quote { is_list } => {
                     match ch {
                         // end and gobble the '"'
                         Some('"') => {
-                            self.state = State::RestOfLine;
-                            self.txt.next();
-                            return Ok(Some(Token::CharData(
-                                char_data.take().unwrap_or_else(|| "".into()),
-                            )));
+                            self.next_char();
+                            let quoted = char_data.take().unwrap_or_default();
+                            if is_list {
+                                char_data_vec
+                                    .as_mut()
+                                    .ok_or_else(|| {
+                                        LexerError::from(LexerErrorKind::IllegalState(
+                                            "char_data_vec is None",
+                                        ))
+                                    })?
+                                    .push(quoted);
+                                self.state = State::List;
+                            } else {
+                                self.state = State::RestOfLine;
+                                return Ok(Some(Token::CharData(quoted)));
+                            }
                         }
                         Some('\\') => {
-                            Self::push_to_str(&mut char_data, self.escape_seq()?)?;
+                            self.escape_seq(&mut char_data)?;
                         }
                         Some(ch) => {
-                            self.txt.next();
-                            Self::push_to_str(&mut char_data, ch)?;
+                            self.next_char();
+                            Self::push_char(&mut char_data, ch)?;
                         }
                         None => return Err(LexerErrorKind::UnclosedQuotedString.into()),
                     }
@@ -143,28 +165,29 @@ impl<'a> Lexer<'a> {
                     match ch {
                         // even this is a little broad for what's actually possible in a dollar...
                         Some('A'..='Z') => {
-                            self.txt.next();
-                            Self::push_to_str(&mut char_data, ch.unwrap())?;
+                            self.next_char();
+                            Self::push_char(&mut char_data, ch.unwrap())?;
                         }
                         // finishes the Dollar...
                         Some(_) | None => {
                             self.state = State::RestOfLine;
-                            let dollar: String = char_data.take().ok_or_else(|| {
+                            let dollar: Vec<u8> = char_data.take().ok_or_else(|| {
                                 LexerError::from(LexerErrorKind::IllegalState(
                                     "char_data \
                                      is None",
                                 ))
                             })?;
 
-                            if "INCLUDE" == dollar {
+                            if b"INCLUDE" == &dollar[..] {
                                 return Ok(Some(Token::Include));
-                            } else if "ORIGIN" == dollar {
+                            } else if b"ORIGIN" == &dollar[..] {
                                 return Ok(Some(Token::Origin));
-                            } else if "TTL" == dollar {
+                            } else if b"TTL" == &dollar[..] {
                                 return Ok(Some(Token::Ttl));
                             } else {
+                                // only A-Z is collected, so this is always ASCII
                                 return Err(LexerErrorKind::UnrecognizedDollar(
-                                    char_data.take().unwrap_or_else(|| "".into()),
+                                    String::from_utf8_lossy(&dollar).into_owned(),
                                 )
                                 .into());
                             }
@@ -173,11 +196,11 @@ impl<'a> Lexer<'a> {
                 }
                 State::List => match ch {
                     Some(';') => {
-                        self.txt.next();
+                        self.next_char();
                         self.state = State::Comment { is_list: true }
                     }
                     Some(')') => {
-                        self.txt.next();
+                        self.next_char();
                         self.state = State::RestOfLine;
                         return char_data_vec
                             .take()
@@ -186,11 +209,16 @@ impl<'a> Lexer<'a> {
                             })
                             .map(|v| Some(Token::List(v)));
                     }
+                    Some('"') => {
+                        self.next_char();
+                        char_data = Some(Vec::new());
+                        self.state = State::Quote { is_list: true };
+                    }
                     Some(ch) if ch.is_whitespace() => {
-                        self.txt.next();
+                        self.next_char();
                     }
                     Some(ch) if !ch.is_control() && !ch.is_whitespace() => {
-                        char_data = Some(String::new());
+                        char_data = Some(Vec::new());
                         self.state = State::CharData { is_list: true }
                     }
                     Some(ch) => return Err(LexerErrorKind::UnrecognizedChar(ch).into()),
@@ -229,10 +257,10 @@ impl<'a> Lexer<'a> {
                             }
                         }
                         // TODO: this next one can be removed, but will keep unescaping for quoted strings
-                        //Some('\\') => { try!(Self::push_to_str(&mut char_data, try!(self.escape_seq()))); },
+                        //Some('\\') => { try!(self.escape_seq(&mut char_data)); },
                         Some(ch) if !ch.is_control() && !ch.is_whitespace() => {
-                            self.txt.next();
-                            Self::push_to_str(&mut char_data, ch)?;
+                            self.next_char();
+                            Self::push_char(&mut char_data, ch)?;
                         }
                         Some(ch) => return Err(LexerErrorKind::UnrecognizedChar(ch).into()),
                         None => {
@@ -247,16 +275,16 @@ impl<'a> Lexer<'a> {
                     }
                 }
                 State::At => {
-                    self.txt.next();
+                    self.next_char();
                     self.state = State::RestOfLine;
                     return Ok(Some(Token::At));
                 }
                 State::EOL => match ch {
                     Some('\r') => {
-                        self.txt.next();
+                        self.next_char();
                     }
                     Some('\n') => {
-                        self.txt.next();
+                        self.next_char();
                         self.state = State::StartLine;
                         return Ok(Some(Token::EOL));
                     }
@@ -265,7 +293,7 @@ impl<'a> Lexer<'a> {
                 },
                 // to exhaust all cases, this should never be run...
                 State::EOF => {
-                    self.txt.next(); // making sure we consume the last... it will always return None after.
+                    self.next_char(); // making sure we consume the last... it will always return None after.
                     return Ok(None);
                 }
             }
@@ -274,59 +302,62 @@ impl<'a> Lexer<'a> {
         unreachable!("The above match statement should have found a terminal state");
     }
 
-    fn push_to_str(collect: &mut Option<String>, ch: char) -> LexerResult<()> {
+    fn push_char(collect: &mut Option<Vec<u8>>, ch: char) -> LexerResult<()> {
+        Self::push_bytes(collect, ch.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+
+    fn push_bytes(collect: &mut Option<Vec<u8>>, bytes: &[u8]) -> LexerResult<()> {
         collect
             .as_mut()
             .ok_or_else(|| LexerErrorKind::IllegalState("collect is None").into())
             .map(|s| {
-                s.push(ch);
+                s.extend_from_slice(bytes);
             })
     }
 
-    fn escape_seq(&mut self) -> LexerResult<char> {
+    /// Decodes an escape into collect, a `\DDD` escape is pushed as the raw octet
+    fn escape_seq(&mut self, collect: &mut Option<Vec<u8>>) -> LexerResult<()> {
         // escaped character, let's decode it.
-        self.txt.next(); // consume the escape
+        self.next_char(); // consume the escape
         let ch = self
             .peek()
             .ok_or_else(|| LexerError::from(LexerErrorKind::EOF))?;
 
         if !ch.is_control() {
             if ch.is_numeric() {
-                // in this case it's an escaped octal: \DDD
+                // in this case it's an escaped decimal octet: \DDD
                 let d1: u32 = self
-                    .txt
-                    .next()
+                    .next_char()
                     .ok_or_else(|| LexerError::from(LexerErrorKind::EOF))
                     .map(|c| {
                         c.to_digit(10)
                             .ok_or_else(|| LexerError::from(LexerErrorKind::IllegalCharacter(c)))
                     })??; // gobble
                 let d2: u32 = self
-                    .txt
-                    .next()
+                    .next_char()
                     .ok_or_else(|| LexerError::from(LexerErrorKind::EOF))
                     .map(|c| {
                         c.to_digit(10)
                             .ok_or_else(|| LexerError::from(LexerErrorKind::IllegalCharacter(c)))
                     })??; // gobble
                 let d3: u32 = self
-                    .txt
-                    .next()
+                    .next_char()
                     .ok_or_else(|| LexerError::from(LexerErrorKind::EOF))
                     .map(|c| {
                         c.to_digit(10)
                             .ok_or_else(|| LexerError::from(LexerErrorKind::IllegalCharacter(c)))
                     })??; // gobble
 
-                let val: u32 = (d1 << 16) + (d2 << 8) + d3;
-                let ch: char = char::from_u32(val)
-                    .ok_or_else(|| LexerError::from(LexerErrorKind::UnrecognizedOctet(val)))?;
+                let val: u32 = d1 * 100 + d2 * 10 + d3;
+                if val > 255 {
+                    return Err(LexerErrorKind::UnrecognizedOctet(val).into());
+                }
 
-                Ok(ch)
+                Self::push_bytes(collect, &[val as u8])
             } else {
                 // this is an escaped char: \X
-                self.txt.next(); // gobble the char
-                Ok(ch)
+                self.next_char(); // gobble the char
+                Self::push_char(collect, ch)
             }
         } else {
             Err(LexerErrorKind::IllegalCharacter(ch).into())
@@ -336,6 +367,15 @@ impl<'a> Lexer<'a> {
     fn peek(&mut self) -> Option<char> {
         self.txt.peek().cloned()
     }
+
+    /// consumes the next char, counting lines
+    fn next_char(&mut self) -> Option<char> {
+        let ch = self.txt.next();
+        if ch == Some('\n') {
+            self.line += 1;
+        }
+        ch
+    }
 }
 
 #[doc(hidden)]
@@ -349,7 +389,7 @@ pub enum State {
     //  Name,              // CharData + '.' + CharData
     Comment { is_list: bool }, // ;.*
     At,                        // @
-    Quote,                     // ".*"
+    Quote { is_list: bool },   // ".*"
     Dollar,                    // $
     EOL,                       // \n or \r\n
     EOF,
@@ -361,9 +401,11 @@ pub enum Token {
     /// only if the first part of the line
     Blank,
     /// (..) TODO, this is probably wrong, List maybe should just skip line endings
-    List(Vec<String>),
+    List(Vec<Vec<u8>>),
     /// [a-zA-Z, non-control utf8, ., -, 0-9]+, ".*"
-    CharData(String),
+    ///
+    /// This is UTF-8 except for quoted strings, in which `\DDD` escapes are decoded to raw octets
+    CharData(Vec<u8>),
     /// @
     At,
     /// $INCLUDE
@@ -386,6 +428,31 @@ mod lex_test {
         result.unwrap()
     }
 
+    #[test]
+    fn line() {
+        let mut lexer = Lexer::new("a\n\nb (c\nd)\n");
+        assert_eq!(lexer.line(), 1);
+
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("a".into()));
+        assert_eq!(lexer.line(), 1);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
+        assert_eq!(lexer.line(), 1);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
+        assert_eq!(lexer.line(), 2);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("b".into()));
+        assert_eq!(lexer.line(), 3);
+
+        // a list is reported on the line it starts
+        assert_eq!(
+            next_token(&mut lexer).unwrap(),
+            Token::List(vec!["c".into(), "d".into()])
+        );
+        assert_eq!(lexer.line(), 3);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
+        assert_eq!(lexer.line(), 4);
+        assert_eq!(next_token(&mut lexer), None);
+    }
+
     #[test]
     fn blank() {
         // first blank
@@ -393,38 +460,38 @@ mod lex_test {
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("dead".to_string())
+            Token::CharData("dead".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("beef".to_string())
+            Token::CharData("beef".into())
         );
 
         // not the second blank
         let mut lexer = Lexer::new("dead beef");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("dead".to_string())
+            Token::CharData("dead".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("beef".to_string())
+            Token::CharData("beef".into())
         );
 
         let mut lexer = Lexer::new("dead beef\r\n after");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("dead".to_string())
+            Token::CharData("dead".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("beef".to_string())
+            Token::CharData("beef".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("after".to_string())
+            Token::CharData("after".into())
         );
 
         let mut lexer = Lexer::new(
@@ -433,18 +500,18 @@ mod lex_test {
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("dead".to_string())
+            Token::CharData("dead".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("beef".to_string())
+            Token::CharData("beef".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::List(vec![]));
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("after".to_string())
+            Token::CharData("after".into())
         );
     }
 
@@ -452,15 +519,15 @@ mod lex_test {
     fn escape() {
         assert_eq!(
             Lexer::new("a\\Aa").next_token().unwrap().unwrap(),
-            Token::CharData("a\\Aa".to_string())
+            Token::CharData("a\\Aa".into())
         );
         assert_eq!(
             Lexer::new("a\\$").next_token().unwrap().unwrap(),
-            Token::CharData("a\\$".to_string())
+            Token::CharData("a\\$".into())
         );
         assert_eq!(
             Lexer::new("a\\077").next_token().unwrap().unwrap(),
-            Token::CharData("a\\077".to_string())
+            Token::CharData("a\\077".into())
         );
     }
 
@@ -468,28 +535,52 @@ mod lex_test {
     fn quoted_txt() {
         assert_eq!(
             Lexer::new("\"Quoted\"").next_token().unwrap().unwrap(),
-            Token::CharData("Quoted".to_string())
+            Token::CharData("Quoted".into())
         );
         assert_eq!(
             Lexer::new("\";@$\"").next_token().unwrap().unwrap(),
-            Token::CharData(";@$".to_string())
+            Token::CharData(";@$".into())
         );
         assert_eq!(
             Lexer::new("\"some \\A\"").next_token().unwrap().unwrap(),
-            Token::CharData("some A".to_string())
+            Token::CharData("some A".into())
         );
         assert_eq!(
             Lexer::new("\"a\\Aa\"").next_token().unwrap().unwrap(),
-            Token::CharData("aAa".to_string())
+            Token::CharData("aAa".into())
         );
         assert_eq!(
             Lexer::new("\"a\\$\"").next_token().unwrap().unwrap(),
-            Token::CharData("a$".to_string())
+            Token::CharData("a$".into())
         );
         assert_eq!(
             Lexer::new("\"a\\077\"").next_token().unwrap().unwrap(),
-            Token::CharData("a\u{707}".to_string())
+            Token::CharData("aM".into())
+        );
+        assert_eq!(
+            Lexer::new("\"say \\\"hi\\\"\"")
+                .next_token()
+                .unwrap()
+                .unwrap(),
+            Token::CharData("say \"hi\"".into())
         );
+        assert_eq!(
+            Lexer::new("\"a\\\\b\"").next_token().unwrap().unwrap(),
+            Token::CharData("a\\b".into())
+        );
+        assert_eq!(
+            Lexer::new("\"a\\127\"").next_token().unwrap().unwrap(),
+            Token::CharData("a\u{7f}".into())
+        );
+        assert_eq!(
+            Lexer::new("\"a\\200\"").next_token().unwrap().unwrap(),
+            Token::CharData(vec![b'a', 200])
+        );
+        assert_eq!(
+            Lexer::new("\"\\195\\169\"").next_token().unwrap().unwrap(),
+            Token::CharData("é".into())
+        );
+        assert!(Lexer::new("\"a\\256\"").next_token().is_err());
 
         assert!(Lexer::new("\"a\\\"").next_token().is_err());
         assert!(Lexer::new("\"a\\0\"").next_token().is_err());
@@ -499,7 +590,7 @@ mod lex_test {
 
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("multi\nline\ntext".to_string())
+            Token::CharData("multi\nline\ntext".into())
         );
         assert_eq!(next_token(&mut lexer), None);
 
@@ -507,19 +598,33 @@ mod lex_test {
 
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("multi\r\nline\r\ntext".to_string())
+            Token::CharData("multi\r\nline\r\ntext".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer), None);
 
         assert!(Lexer::new("\"multi").next_token().is_err());
+
+        // a semicolon in a quoted string is not a comment, one after it is
+        let mut lexer = Lexer::new("TXT \"a;b\" ; comment \"c\"\nA");
+        assert_eq!(
+            next_token(&mut lexer).unwrap(),
+            Token::CharData("TXT".into())
+        );
+        assert_eq!(
+            next_token(&mut lexer).unwrap(),
+            Token::CharData("a;b".into())
+        );
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("A".into()));
+        assert_eq!(next_token(&mut lexer), None);
     }
 
     #[test]
     fn unicode() {
         assert_eq!(
             Lexer::new("♥").next_token().unwrap().unwrap(),
-            Token::CharData("♥".to_string())
+            Token::CharData("♥".into())
         );
     }
 
@@ -528,7 +633,7 @@ mod lex_test {
     fn lex() {
         assert_eq!(
             next_token(&mut Lexer::new(".")).unwrap(),
-            Token::CharData(".".to_string())
+            Token::CharData(".".into())
         );
         assert_eq!(
             next_token(&mut Lexer::new("            .")).unwrap(),
@@ -536,18 +641,18 @@ mod lex_test {
         );
         assert_eq!(
             next_token(&mut Lexer::new("abc")).unwrap(),
-            Token::CharData("abc".to_string())
+            Token::CharData("abc".into())
         );
         assert_eq!(
             next_token(&mut Lexer::new("abc.")).unwrap(),
-            Token::CharData("abc.".to_string())
+            Token::CharData("abc.".into())
         );
         assert_eq!(next_token(&mut Lexer::new(";abc")), None);
         assert_eq!(next_token(&mut Lexer::new(";;@$-\"")), None);
         assert_eq!(next_token(&mut Lexer::new("@")).unwrap(), Token::At);
         assert_eq!(
             next_token(&mut Lexer::new("123")).unwrap(),
-            Token::CharData("123".to_string())
+            Token::CharData("123".into())
         );
         assert_eq!(
             next_token(&mut Lexer::new("$INCLUDE")).unwrap(),
@@ -576,30 +681,39 @@ mod lex_test {
         let mut lexer = Lexer::new("(abc)");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::List(vec!["abc".to_string()])
+            Token::List(vec!["abc".into()])
         );
         assert_eq!(next_token(&mut lexer), None);
 
         let mut lexer = Lexer::new("(\nabc\n)");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::List(vec!["abc".to_string()])
+            Token::List(vec!["abc".into()])
         );
         assert_eq!(next_token(&mut lexer), None);
 
         let mut lexer = Lexer::new("(\nabc\nabc)");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::List(vec!["abc".to_string(), "abc".to_string()])
+            Token::List(vec!["abc".into(), "abc".into()])
         );
         assert_eq!(next_token(&mut lexer), None);
 
         let mut lexer = Lexer::new("(\nabc;comment\n)");
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::List(vec!["abc".to_string()])
+            Token::List(vec!["abc".into()])
         );
         assert_eq!(next_token(&mut lexer), None);
+
+        let mut lexer = Lexer::new("( \"v=DKIM1; k=rsa; \"\n  \"p=(abc)\" ; key\n)");
+        assert_eq!(
+            next_token(&mut lexer).unwrap(),
+            Token::List(vec!["v=DKIM1; k=rsa; ".into(), "p=(abc)".into()])
+        );
+        assert_eq!(next_token(&mut lexer), None);
+
+        assert!(Lexer::new("(\"abc)").next_token().is_err());
     }
 
     #[test]
@@ -636,28 +750,28 @@ $INCLUDE <SUBSYS>ISI-MAILBOXES.TXT",
         assert_eq!(next_token(&mut lexer).unwrap(), Token::At);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("IN".to_string())
+            Token::CharData("IN".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("SOA".to_string())
+            Token::CharData("SOA".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VENERA".to_string())
+            Token::CharData("VENERA".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("Action\\.domains".to_string())
+            Token::CharData("Action\\.domains".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
             Token::List(vec![
-                "20".to_string(),
-                "7200".to_string(),
-                "600".to_string(),
-                "3600000".to_string(),
-                "60".to_string(),
+                "20".into(),
+                "7200".into(),
+                "600".into(),
+                "3600000".into(),
+                "60".into(),
             ])
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
@@ -665,104 +779,92 @@ $INCLUDE <SUBSYS>ISI-MAILBOXES.TXT",
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("NS".to_string())
+            Token::CharData("NS".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("A.ISI.EDU.".to_string())
+            Token::CharData("A.ISI.EDU.".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("NS".to_string())
+            Token::CharData("NS".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VENERA".to_string())
+            Token::CharData("VENERA".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("NS".to_string())
+            Token::CharData("NS".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VAXA".to_string())
+            Token::CharData("VAXA".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("MX".to_string())
+            Token::CharData("MX".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("10".to_string())
+            Token::CharData("10".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VENERA".to_string())
+            Token::CharData("VENERA".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("MX".to_string())
+            Token::CharData("MX".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("20".to_string())
+            Token::CharData("20".into())
         );
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VAXA".to_string())
+            Token::CharData("VAXA".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("A".into()));
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("A".into()));
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("A".to_string())
-        );
-        assert_eq!(
-            next_token(&mut lexer).unwrap(),
-            Token::CharData("A".to_string())
-        );
-        assert_eq!(
-            next_token(&mut lexer).unwrap(),
-            Token::CharData("26.3.0.103".to_string())
+            Token::CharData("26.3.0.103".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("VENERA".to_string())
-        );
-        assert_eq!(
-            next_token(&mut lexer).unwrap(),
-            Token::CharData("A".to_string())
+            Token::CharData("VENERA".into())
         );
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("A".into()));
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("10.1.0.52".to_string())
+            Token::CharData("10.1.0.52".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Blank);
+        assert_eq!(next_token(&mut lexer).unwrap(), Token::CharData("A".into()));
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("A".to_string())
-        );
-        assert_eq!(
-            next_token(&mut lexer).unwrap(),
-            Token::CharData("128.9.0.32".to_string())
+            Token::CharData("128.9.0.32".into())
         );
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::EOL);
         assert_eq!(next_token(&mut lexer).unwrap(), Token::Include);
         assert_eq!(
             next_token(&mut lexer).unwrap(),
-            Token::CharData("<SUBSYS>ISI-MAILBOXES.TXT".to_string())
+            Token::CharData("<SUBSYS>ISI-MAILBOXES.TXT".into())
         );
         assert!(next_token(&mut lexer).is_none());
     }
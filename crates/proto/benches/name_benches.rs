@@ -6,7 +6,9 @@ use std::cmp::Ordering;
 
 use test::Bencher;
 
+use trust_dns_proto::rr::domain::NameRef;
 use trust_dns_proto::rr::*;
+use trust_dns_proto::serialize::binary::*;
 
 #[bench]
 fn name_cmp_short(b: &mut Bencher) {
@@ -157,3 +159,21 @@ fn name_no_lower_long(b: &mut Bencher) {
         assert_eq!(lower.num_labels(), 3);
     });
 }
+
+const WIRE_NAME: &[u8] = b"\x03www\x07example\x03com\x00";
+
+#[bench]
+fn name_read_owned(b: &mut Bencher) {
+    b.iter(|| {
+        let name = Name::read(&mut BinDecoder::new(WIRE_NAME)).unwrap();
+        assert_eq!(name.num_labels(), 3);
+    });
+}
+
+#[bench]
+fn name_read_borrowed(b: &mut Bencher) {
+    b.iter(|| {
+        let name = NameRef::read(&mut BinDecoder::new(WIRE_NAME)).unwrap();
+        assert_eq!(name.num_labels(), 3);
+    });
+}
@@ -124,6 +124,10 @@ pub enum ProtoErrorKind {
         ptr: u16,
     },
 
+    /// Too many compression pointers were followed while reading a name
+    #[error("too many label pointers followed: {0}")]
+    PointerJumpsExceeded(usize),
+
     /// The maximum buffer size was exceeded
     #[error("maximum buffer size exceeded: {0}")]
     MaxBufferSizeExceeded(usize),
@@ -164,10 +168,6 @@ pub enum ProtoErrorKind {
     #[error("dns class string unknown: {0}")]
     UnknownDnsClassStr(String),
 
-    /// An unknown dns class value was found
-    #[error("dns class value unknown: {0}")]
-    UnknownDnsClassValue(u16),
-
     /// An unknown record type string was found
     #[error("record type string unknown: {0}")]
     UnknownRecordTypeStr(String),
@@ -320,6 +320,12 @@ impl From<std::str::Utf8Error> for ProtoError {
     }
 }
 
+impl From<std::string::FromUtf8Error> for ProtoError {
+    fn from(e: std::string::FromUtf8Error) -> ProtoError {
+        ProtoErrorKind::from(e.utf8_error()).into()
+    }
+}
+
 impl From<std::num::ParseIntError> for ProtoError {
     fn from(e: std::num::ParseIntError) -> ProtoError {
         ProtoErrorKind::from(e).into()
@@ -406,6 +412,7 @@ impl Clone for ProtoErrorKind {
             IncorrectRDataLengthRead { read, len } => IncorrectRDataLengthRead { read, len },
             LabelBytesTooLong(len) => LabelBytesTooLong(len),
             PointerNotPriorToLabel { idx, ptr } => PointerNotPriorToLabel { idx, ptr },
+            PointerJumpsExceeded(jumps) => PointerJumpsExceeded(jumps),
             MaxBufferSizeExceeded(max) => MaxBufferSizeExceeded(max),
             Message(msg) => Message(msg),
             Msg(ref msg) => Msg(msg.clone()),
@@ -420,7 +427,6 @@ impl Clone for ProtoErrorKind {
             },
             UnknownAlgorithmTypeValue(value) => UnknownAlgorithmTypeValue(value),
             UnknownDnsClassStr(ref value) => UnknownDnsClassStr(value.clone()),
-            UnknownDnsClassValue(value) => UnknownDnsClassValue(value),
             UnknownRecordTypeStr(ref value) => UnknownRecordTypeStr(value.clone()),
             UnknownRecordTypeValue(value) => UnknownRecordTypeValue(value),
             UnrecognizedLabelCode(value) => UnrecognizedLabelCode(value),
@@ -449,3 +455,31 @@ impl Clone for ProtoErrorKind {
 pub trait FromProtoError: From<ProtoError> + std::error::Error + Clone {}
 
 impl<E> FromProtoError for E where E: From<ProtoError> + std::error::Error + Clone {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_utf8_errors() {
+        let bad = vec![b'a', 0xFF, b'b'];
+
+        let err = ProtoError::from(String::from_utf8(bad.clone()).unwrap_err());
+        match *err.kind() {
+            ProtoErrorKind::Utf8(e) => assert_eq!(e.valid_up_to(), 1),
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+
+        let err = ProtoError::from(std::str::from_utf8(&bad).unwrap_err());
+        match *err.kind() {
+            ProtoErrorKind::Utf8(e) => assert_eq!(e.valid_up_to(), 1),
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+
+        fn to_string(bytes: Vec<u8>) -> ProtoResult<String> {
+            Ok(String::from_utf8(bytes)?)
+        }
+        assert!(to_string(bad).is_err());
+        assert_eq!(to_string(b"ab".to_vec()).unwrap(), "ab");
+    }
+}
@@ -17,6 +17,8 @@
 
 //! Trust-DNS Protocol library
 
+extern crate alloc;
+
 use async_trait::async_trait;
 use futures_util::future::Future;
 
@@ -196,10 +196,7 @@ impl BinEncodable for Edns {
         // write the opts as rdata...
         let place = encoder.place::<u16>()?;
         opt::emit(encoder, &self.options)?;
-        let len = encoder.len_since_place(&place);
-        assert!(len <= u16::max_value() as usize);
-
-        place.replace(encoder, len as u16)?;
+        place.replace_with_len(encoder)?;
         Ok(())
     }
 }
@@ -540,3 +540,75 @@ fn test_write() {
 
     assert_eq!(bytes, expect);
 }
+
+#[test]
+fn test_flag_bits() {
+    fn flags(header: &Header) -> [u8; 2] {
+        let mut bytes = Vec::with_capacity(Header::len());
+        {
+            let mut encoder = BinEncoder::new(&mut bytes);
+            header.emit(&mut encoder).unwrap();
+        }
+
+        let read = Header::read(&mut BinDecoder::new(&bytes)).unwrap();
+        assert_eq!(&read, header);
+
+        [bytes[2], bytes[3]]
+    }
+
+    assert_eq!(flags(&Header::new()), [0b0000_0000, 0b0000_0000]);
+    assert_eq!(
+        flags(Header::new().set_message_type(MessageType::Response)),
+        [0b1000_0000, 0b0000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_op_code(OpCode::Update)),
+        [0b0010_1000, 0b0000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_authoritative(true)),
+        [0b0000_0100, 0b0000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_truncated(true)),
+        [0b0000_0010, 0b0000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_recursion_desired(true)),
+        [0b0000_0001, 0b0000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_recursion_available(true)),
+        [0b0000_0000, 0b1000_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_authentic_data(true)),
+        [0b0000_0000, 0b0010_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_checking_disabled(true)),
+        [0b0000_0000, 0b0001_0000]
+    );
+    assert_eq!(
+        flags(Header::new().set_response_code(ResponseCode::Refused)),
+        [0b0000_0000, 0b0000_0101]
+    );
+}
+
+#[test]
+fn test_read_truncated() {
+    let byte_vec = vec![
+        0x12, 0x34, 0x82, 0x80, // response, truncated, recursion available
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let header = Header::read(&mut BinDecoder::new(&byte_vec)).unwrap();
+    assert!(header.truncated());
+    assert_eq!(header.message_type(), MessageType::Response);
+    assert!(!header.recursion_desired());
+    assert!(header.recursion_available());
+
+    let mut header = header;
+    header.set_truncated(false);
+    assert!(!header.truncated());
+}
@@ -22,10 +22,11 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use log::debug;
+use rand::Rng;
 
 use super::{Edns, Header, MessageType, OpCode, Query, ResponseCode};
 use crate::error::*;
-use crate::rr::{Record, RecordType};
+use crate::rr::{Name, Record, RecordType};
 use crate::serialize::binary::{BinDecodable, BinDecoder, BinEncodable, BinEncoder, EncodeMode};
 
 #[cfg(feature = "dnssec")]
@@ -133,6 +134,55 @@ impl Message {
         }
     }
 
+    /// Returns a new query Message for a single question, with recursion desired
+    ///
+    /// The id is random, see `random_id`, use `set_id` to replace it. All other flags are left at
+    /// their defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name to lookup
+    /// * `query_type` - the record type to lookup, the class is always `IN`
+    pub fn query(name: Name, query_type: RecordType) -> Self {
+        Self::query_with_rng(name, query_type, &mut rand::thread_rng())
+    }
+
+    /// Same as `query`, but the id is drawn from the given random number generator
+    ///
+    /// This is intended for deterministic tests, see `random_id_with_rng` for the requirements
+    ///  on the generator otherwise.
+    pub fn query_with_rng<R: Rng + ?Sized>(
+        name: Name,
+        query_type: RecordType,
+        rng: &mut R,
+    ) -> Self {
+        let mut message = Message::new();
+        message
+            .add_query(Query::query(name, query_type))
+            .set_id(Self::random_id_with_rng(rng))
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .set_recursion_desired(true);
+
+        message
+    }
+
+    /// Returns a new random message id
+    ///
+    /// The id, along with the source port, is what makes an off-path attacker guess when spoofing
+    ///  a response, so it must be unpredictable. It is drawn from the thread local generator,
+    ///  which is a cryptographically secure RNG.
+    pub fn random_id() -> u16 {
+        Self::random_id_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Returns a new message id from the given random number generator
+    ///
+    /// Outside of tests this should be a cryptographically secure RNG, see `random_id`.
+    pub fn random_id_with_rng<R: Rng + ?Sized>(rng: &mut R) -> u16 {
+        rng.gen()
+    }
+
     /// Returns a Message constructed with error details to return to a client
     ///
     /// # Arguments
@@ -224,8 +274,18 @@ impl Message {
     }
 
     /// see `Header::set_response_code`
+    ///
+    /// The high 8 bits of an extended `ResponseCode`, such as `BADVERS`, are carried in the EDNS
+    ///  section, which will be created if necessary.
     pub fn set_response_code(&mut self, response_code: ResponseCode) -> &mut Self {
         self.header.set_response_code(response_code);
+
+        let rcode_high = response_code.high();
+        if rcode_high != 0 {
+            self.edns_mut().set_rcode_high(rcode_high);
+        } else if let Some(edns) = self.edns.as_mut() {
+            edns.set_rcode_high(0);
+        }
         self
     }
 
@@ -532,6 +592,14 @@ impl Message {
         }
     }
 
+    /// The length of the message when emitted without name compression
+    ///
+    /// Like `Record::max_encoded_len`, this is an upper bound on the emitted length. Adding a record
+    ///  grows it by exactly that record's `max_encoded_len`.
+    pub fn max_encoded_len(&self) -> ProtoResult<usize> {
+        self.to_canonical_vec().map(|bytes| bytes.len())
+    }
+
     /// # Return value
     ///
     /// the version as defined in the EDNS record
@@ -658,6 +726,22 @@ impl Message {
         Ok(buffer)
     }
 
+    /// Encodes the Message into a buffer with all names in canonical form
+    ///
+    /// Names are not compressed, and owner names, query names and the names in the RDATA of the
+    ///  types listed in RFC 4034 section 6.2 are lowercased. This is the form used for data that is
+    ///  to be signed or verified, it is the same as emitting with `BinEncoder::set_canonical_names`.
+    pub fn to_canonical_vec(&self) -> Result<Vec<u8>, ProtoError> {
+        let mut buffer = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut buffer);
+            encoder.set_canonical_names(true);
+            self.emit(&mut encoder)?;
+        }
+
+        Ok(buffer)
+    }
+
     /// Finalize the message prior to sending.
     ///
     /// Subsequent to calling this, the Message should not change.
@@ -691,6 +775,48 @@ impl Deref for Message {
         &self.header
     }
 }
+
+/// The room left in a message's `max_payload` as records are added to it
+///
+/// The message is encoded once when the budget is created, after that each record is only
+///  encoded to get its own `max_encoded_len`. Both are sized without name compression, so this
+///  never underestimates, a record that fits here will fit when the message is emitted.
+#[derive(Clone, Copy, Debug)]
+pub struct PayloadBudget {
+    encoded_len: usize,
+    max_payload: usize,
+}
+
+impl PayloadBudget {
+    /// Creates a budget for adding records to `message`, the records already in it are counted
+    pub fn new(message: &Message) -> ProtoResult<Self> {
+        Ok(PayloadBudget {
+            encoded_len: message.max_encoded_len()?,
+            max_payload: usize::from(message.max_payload()),
+        })
+    }
+
+    /// Returns true, and counts `record` against the budget, if it can be added without exceeding
+    ///  `max_payload`
+    ///
+    /// Nothing is counted when the record doesn't fit. When adding records to a UDP response, stop
+    ///  and set the truncated flag at the first one that doesn't fit.
+    pub fn try_add(&mut self, record: &Record) -> ProtoResult<bool> {
+        let encoded_len = self.encoded_len + record.max_encoded_len()?;
+        if encoded_len > self.max_payload {
+            return Ok(false);
+        }
+
+        self.encoded_len = encoded_len;
+        Ok(true)
+    }
+
+    /// The length of the message with the records added so far, see `Message::max_encoded_len`
+    pub fn encoded_len(&self) -> usize {
+        self.encoded_len
+    }
+}
+
 /// A trait for performing final amendments to a Message before it is sent.
 ///
 /// An example of this is a SIG0 signer, which needs the final form of the message,
@@ -914,6 +1040,297 @@ fn test_emit_and_read_records() {
     test_emit_and_read(message);
 }
 
+#[test]
+fn test_emit_and_read_srv_answer() {
+    use std::str::FromStr;
+
+    use crate::rr::rdata::SRV;
+    use crate::rr::{DNSClass, RData};
+
+    let name = Name::from_str("_ldap._tcp.example.com.").unwrap();
+
+    let mut query = Query::new();
+    query
+        .set_name(name.clone())
+        .set_query_type(RecordType::SRV)
+        .set_query_class(DNSClass::IN);
+
+    let mut message = Message::new();
+    message
+        .set_id(0x1234)
+        .set_message_type(MessageType::Response)
+        .set_op_code(OpCode::Query)
+        .set_recursion_desired(true)
+        .add_query(query)
+        .add_answer(Record::from_rdata(
+            name,
+            300,
+            RData::SRV(SRV::new(
+                0,
+                5,
+                389,
+                Name::from_str("ldap.example.com.").unwrap(),
+            )),
+        ));
+
+    let bytes = message.to_vec().unwrap();
+    // id, flags, then 1 query, 1 answer, 0 name servers and 0 additionals
+    assert_eq!(
+        &bytes[..12],
+        &[0x12, 0x34, 0x81, 0x00, 0, 1, 0, 1, 0, 0, 0, 0]
+    );
+
+    let got = Message::from_vec(&bytes).unwrap();
+    assert_eq!(got.id(), 0x1234);
+    assert_eq!(got.queries(), message.queries());
+    assert_eq!(got.answers(), message.answers());
+    assert!(got.name_servers().is_empty());
+    assert!(got.additionals().is_empty());
+}
+
+#[test]
+fn test_payload_budget() {
+    use crate::rr::rdata::SRV;
+    use crate::rr::RData;
+
+    let service = Name::from_ascii("_sip._tcp.example.com.").unwrap();
+    let srv = |n: usize| {
+        // SRV targets aren't compressed, so each of these takes the full length of its target
+        let label = format!("{}{}", n, "a".repeat(50));
+        let target = Name::from_ascii(format!("{0}.{0}.{0}.example.com.", label)).unwrap();
+        Record::from_rdata(
+            service.clone(),
+            300,
+            RData::SRV(SRV::new(0, 0, 5060, target)),
+        )
+    };
+
+    let mut message = Message::new();
+    message
+        .set_message_type(MessageType::Response)
+        .add_query(Query::query(service.clone(), RecordType::SRV));
+    assert_eq!(message.max_payload(), 512);
+
+    let mut added = 0;
+    let mut budget = PayloadBudget::new(&message).unwrap();
+    let rejected = loop {
+        let record = srv(added);
+        if !budget.try_add(&record).unwrap() {
+            message.set_truncated(true);
+            break record;
+        }
+
+        message.add_answer(record);
+        added += 1;
+    };
+
+    // the running length matches encoding the whole message, the rejected record isn't counted
+    assert_eq!(budget.encoded_len(), message.max_encoded_len().unwrap());
+
+    // each record is more than 200 bytes uncompressed, so only two fit in 512
+    assert_eq!(added, 2);
+    assert!(message.truncated());
+    assert!(message.to_vec().unwrap().len() <= 512);
+    assert!(rejected.max_encoded_len().unwrap() > 200);
+
+    // adding the rejected record does go over
+    message.add_answer(rejected);
+    assert!(message.to_vec().unwrap().len() > 512);
+
+    // a larger EDNS payload has room for it
+    message.answers.pop();
+    message.edns_mut().set_max_payload(1232);
+    let mut budget = PayloadBudget::new(&message).unwrap();
+    assert!(budget.try_add(&srv(added)).unwrap());
+}
+
+#[test]
+fn test_to_canonical_vec() {
+    use crate::rr::RData;
+
+    let www = Name::from_ascii("WWW.Example.COM.").unwrap();
+    let mail = Name::from_ascii("Mail.Example.COM.").unwrap();
+
+    let mut message = Message::new();
+    message
+        .add_query(Query::query(www.clone(), RecordType::A))
+        .add_answer(Record::from_rdata(www, 300, RData::CNAME(mail.clone())))
+        .add_answer(Record::from_rdata(
+            mail,
+            300,
+            RData::A("10.0.0.1".parse().unwrap()),
+        ));
+
+    let normal = message.to_vec().unwrap();
+    let canonical = message.to_canonical_vec().unwrap();
+
+    // case is preserved and later names are compressed
+    assert!(normal.windows(3).any(|w| w == b"WWW"));
+    assert!(normal.contains(&0xC0));
+    assert!(canonical.len() > normal.len());
+
+    // all names are lowercase, and written out in full
+    assert!(!canonical.windows(3).any(|w| w == b"WWW"));
+    assert!(!canonical.windows(4).any(|w| w == b"Mail"));
+    assert!(!canonical.contains(&0xC0));
+
+    let got = Message::from_vec(&canonical).unwrap();
+    assert_eq!(got.queries(), message.queries());
+    assert_eq!(got.answers(), message.answers());
+
+    let www = Name::from_ascii("www.example.com.").unwrap();
+    let mail = Name::from_ascii("mail.example.com.").unwrap();
+    assert!(got.queries()[0].name().eq_case(&www));
+    assert!(got.answers()[0].name().eq_case(&www));
+    assert!(got.answers()[1].name().eq_case(&mail));
+    match got.answers()[0].rdata() {
+        RData::CNAME(name) => assert!(name.eq_case(&mail)),
+        rdata => panic!("expected CNAME: {:?}", rdata),
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_read_interned_names() {
+    use std::str::FromStr;
+
+    use crate::rr::RData;
+
+    let name = Name::from_str("www.example.com.").unwrap();
+
+    let mut message = Message::new();
+    message
+        .add_query(Query::query(name.clone(), RecordType::A))
+        .add_answer(Record::from_rdata(
+            name.clone(),
+            300,
+            RData::A("192.0.2.1".parse().unwrap()),
+        ))
+        .add_answer(Record::from_rdata(
+            name,
+            300,
+            RData::A("192.0.2.2".parse().unwrap()),
+        ));
+    let bytes = message.to_vec().unwrap();
+
+    let mut decoder = BinDecoder::new(&bytes);
+    decoder.set_intern_names(true);
+    let got = Message::read(&mut decoder).unwrap();
+    assert_eq!(got.queries(), message.queries());
+    assert_eq!(got.answers(), message.answers());
+
+    // the labels of the owner names are shared, rather than copied for each record
+    let label_ptr = |name: &Name| name[0].as_bytes().as_ptr();
+    let query_name = label_ptr(got.queries()[0].name());
+    assert_eq!(query_name, label_ptr(got.answers()[0].name()));
+    assert_eq!(query_name, label_ptr(got.answers()[1].name()));
+
+    let got = Message::from_vec(&bytes).unwrap();
+    assert_ne!(
+        label_ptr(got.answers()[0].name()),
+        label_ptr(got.answers()[1].name())
+    );
+}
+
+#[test]
+fn test_query() {
+    use std::str::FromStr;
+
+    use crate::rr::DNSClass;
+
+    let name = Name::from_str("www.example.com.").unwrap();
+    let mut message = Message::query(name.clone(), RecordType::AAAA);
+
+    assert_eq!(message.message_type(), MessageType::Query);
+    assert_eq!(message.op_code(), OpCode::Query);
+    assert!(message.recursion_desired());
+    assert!(!message.recursion_available());
+    assert!(!message.checking_disabled());
+    assert_eq!(message.queries().len(), 1);
+    assert_eq!(message.queries()[0].name(), &name);
+    assert_eq!(message.queries()[0].query_type(), RecordType::AAAA);
+    assert_eq!(message.queries()[0].query_class(), DNSClass::IN);
+    assert!(message.answers().is_empty());
+
+    message.set_id(42).set_checking_disabled(true);
+    let got = Message::from_vec(&message.to_vec().unwrap()).unwrap();
+    assert_eq!(got.id(), 42);
+    assert!(got.recursion_desired());
+    assert!(got.checking_disabled());
+    assert_eq!(got.queries(), message.queries());
+}
+
+#[test]
+fn test_random_id_with_rng() {
+    use std::str::FromStr;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let ids = |seed: u64| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..4)
+            .map(|_| Message::random_id_with_rng(&mut rng))
+            .collect::<Vec<u16>>()
+    };
+
+    assert_eq!(ids(1), ids(1));
+    assert_ne!(ids(1), ids(2));
+
+    let name = Name::from_str("www.example.com.").unwrap();
+    let first = Message::query_with_rng(name.clone(), RecordType::A, &mut StdRng::seed_from_u64(7));
+    let second = Message::query_with_rng(name, RecordType::A, &mut StdRng::seed_from_u64(7));
+    assert_eq!(first.id(), second.id());
+    assert_eq!(
+        first.id(),
+        Message::random_id_with_rng(&mut StdRng::seed_from_u64(7))
+    );
+}
+
+#[test]
+#[rustfmt::skip]
+fn test_read_extended_response_code() {
+    let buf: Vec<u8> = vec![
+        0x10, 0x00, 0x81, 0x87, // id = 4096, response, recursion desired and available, rcode low = 7
+        0x00, 0x00, 0x00, 0x00, // 0 queries, 0 answers
+        0x00, 0x00, 0x00, 0x01, // 0 name servers, 1 additional
+
+        0x00,                   // root
+        0x00, 0x29, 0x10, 0x00, // OPT, max payload 4096
+        0x01, 0x00, 0x00, 0x00, // extended rcode high = 1, version 0, no flags
+        0x00, 0x00,             // no options
+    ];
+
+    let message = Message::from_vec(&buf).unwrap();
+    assert_eq!(message.header().response_code(), 7);
+    assert_eq!(message.edns().unwrap().rcode_high(), 1);
+    assert_eq!(message.response_code(), ResponseCode::BADCOOKIE);
+}
+
+#[test]
+fn test_emit_extended_response_code() {
+    let mut message = Message::error_msg(10, OpCode::Query, ResponseCode::BADVERS);
+    assert_eq!(message.header().response_code(), 0);
+    assert_eq!(message.edns().map(Edns::rcode_high), Some(1));
+
+    // 16 is shared by BADVERS and BADSIG, and reads as the latter
+    let got = Message::from_vec(&message.to_vec().unwrap()).unwrap();
+    assert_eq!(
+        u16::from(got.response_code()),
+        u16::from(ResponseCode::BADVERS)
+    );
+
+    // setting a response code without high bits clears them
+    message.set_response_code(ResponseCode::Refused);
+    let got = Message::from_vec(&message.to_vec().unwrap()).unwrap();
+    assert_eq!(got.response_code(), ResponseCode::Refused);
+    assert_eq!(got.edns().map(Edns::rcode_high), Some(0));
+
+    // without high bits no EDNS section is added
+    let message = Message::error_msg(10, OpCode::Query, ResponseCode::ServFail);
+    assert!(message.edns().is_none());
+}
+
 #[cfg(test)]
 fn test_emit_and_read(message: Message) {
     let mut byte_vec: Vec<u8> = Vec::with_capacity(512);
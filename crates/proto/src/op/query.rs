@@ -163,7 +163,8 @@ impl Query {
 
 impl BinEncodable for Query {
     fn emit(&self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
-        self.name.emit(encoder)?;
+        let is_canonical_names = encoder.is_canonical_names();
+        self.name.emit_with_lowercase(encoder, is_canonical_names)?;
         self.query_type.emit(encoder)?;
 
         #[cfg(not(feature = "mdns"))]
@@ -184,7 +185,7 @@ impl BinEncodable for Query {
 
 impl<'r> BinDecodable<'r> for Query {
     fn read(decoder: &mut BinDecoder<'r>) -> ProtoResult<Self> {
-        let name = Name::read(decoder)?;
+        let name = decoder.read_name()?;
         let query_type = RecordType::read(decoder)?;
 
         #[cfg(feature = "mdns")]
@@ -196,12 +197,12 @@ impl<'r> BinDecodable<'r> for Query {
         #[cfg(feature = "mdns")]
         let query_class = {
             let query_class_value =
-                decoder.read_u16()?.unverified(/*DNSClass::from_u16 will verify the value*/);
+                decoder.read_u16()?.unverified(/*DNSClass::from is always valid*/);
             if query_class_value & MDNS_UNICAST_RESPONSE > 0 {
                 mdns_unicast_response = true;
-                DNSClass::from_u16(query_class_value & !MDNS_UNICAST_RESPONSE)?
+                DNSClass::from(query_class_value & !MDNS_UNICAST_RESPONSE)
             } else {
-                DNSClass::from_u16(query_class_value)?
+                DNSClass::from(query_class_value)
             }
         };
 
@@ -257,6 +258,59 @@ fn test_read_and_emit() {
     assert_eq!(got, expect);
 }
 
+#[test]
+fn test_read_and_emit_srv() {
+    let expect = Query::query(
+        Name::from_ascii("_ldap._tcp.example.com.").unwrap(),
+        RecordType::SRV,
+    );
+    assert_eq!(expect.query_class(), DNSClass::IN);
+
+    let mut byte_vec: Vec<u8> = Vec::with_capacity(512);
+    {
+        let mut encoder = BinEncoder::new(&mut byte_vec);
+        expect.emit(&mut encoder).unwrap();
+    }
+
+    #[rustfmt::skip]
+    let expected_bytes: &[u8] = &[
+        5, b'_', b'l', b'd', b'a', b'p', 4, b'_', b't', b'c', b'p',
+        7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
+        0x00, 0x21, // SRV
+        0x00, 0x01, // IN
+    ];
+    assert_eq!(&byte_vec[..], expected_bytes);
+
+    let mut decoder = BinDecoder::new(&byte_vec);
+    let got = Query::read(&mut decoder).unwrap();
+    assert_eq!(got, expect);
+    assert!(decoder.is_empty());
+}
+
+#[test]
+fn test_read_compressed_name() {
+    // a second question for _tcp.example.com. as a pointer into the first
+    let mut byte_vec: Vec<u8> = Vec::with_capacity(512);
+    {
+        let mut encoder = BinEncoder::new(&mut byte_vec);
+        Query::query(
+            Name::from_ascii("_ldap._tcp.example.com.").unwrap(),
+            RecordType::SRV,
+        )
+        .emit(&mut encoder)
+        .unwrap();
+    }
+    byte_vec.extend_from_slice(&[0xC0, 0x06, 0x00, 0x21, 0x00, 0x01]);
+
+    let mut decoder = BinDecoder::new(&byte_vec);
+    Query::read(&mut decoder).unwrap();
+    let got = Query::read(&mut decoder).unwrap();
+    assert_eq!(got.name(), &Name::from_ascii("_tcp.example.com.").unwrap());
+    assert_eq!(got.query_type(), RecordType::SRV);
+    assert_eq!(got.query_class(), DNSClass::IN);
+    assert!(decoder.is_empty());
+}
+
 #[cfg(feature = "mdns")]
 #[test]
 fn test_mdns_unicast_response_bit_handling() {
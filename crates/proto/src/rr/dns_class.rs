@@ -33,6 +33,8 @@ pub enum DNSClass {
     /// Special class for OPT Version, it was overloaded for EDNS - RFC 6891
     /// From the RFC: `Values lower than 512 MUST be treated as equal to 512`
     OPT(u16),
+    /// Unknown DNSClass was parsed
+    Unknown(u16),
 }
 
 impl FromStr for DNSClass {
@@ -48,36 +50,28 @@ impl FromStr for DNSClass {
     /// assert_eq!(DNSClass::IN, var);
     /// ```
     fn from_str(str: &str) -> ProtoResult<Self> {
-        debug_assert!(str.chars().all(|x| char::is_ascii_uppercase(&x)));
+        debug_assert!(!str.chars().any(|x| char::is_ascii_lowercase(&x)));
         match str {
             "IN" => Ok(DNSClass::IN),
             "CH" => Ok(DNSClass::CH),
             "HS" => Ok(DNSClass::HS),
             "NONE" => Ok(DNSClass::NONE),
             "ANY" | "*" => Ok(DNSClass::ANY),
+            // RFC 3597 generic class names, e.g. CLASS32
+            _ if str.starts_with("CLASS") => str[5..]
+                .parse::<u16>()
+                .map(DNSClass::from)
+                .map_err(|_| ProtoErrorKind::UnknownDnsClassStr(str.to_string()).into()),
             _ => Err(ProtoErrorKind::UnknownDnsClassStr(str.to_string()).into()),
         }
     }
 }
 
 impl DNSClass {
-    /// Convert from `u16` to `DNSClass`
-    ///
-    /// ```
-    /// use trust_dns_proto::rr::dns_class::DNSClass;
-    ///
-    /// let var = DNSClass::from_u16(1).unwrap();
-    /// assert_eq!(DNSClass::IN, var);
-    /// ```
+    /// Convert from `u16` to `DNSClass`, this never fails
+    #[deprecated(note = "use From/Into")]
     pub fn from_u16(value: u16) -> ProtoResult<Self> {
-        match value {
-            1 => Ok(DNSClass::IN),
-            3 => Ok(DNSClass::CH),
-            4 => Ok(DNSClass::HS),
-            254 => Ok(DNSClass::NONE),
-            255 => Ok(DNSClass::ANY),
-            _ => Err(ProtoErrorKind::UnknownDnsClassValue(value).into()),
-        }
+        Ok(DNSClass::from(value))
     }
 
     /// Return the OPT version from value
@@ -96,9 +90,33 @@ impl BinEncodable for DNSClass {
 
 impl<'r> BinDecodable<'r> for DNSClass {
     fn read(decoder: &mut BinDecoder<'_>) -> ProtoResult<Self> {
-        Self::from_u16(
+        Ok(Self::from(
             decoder.read_u16()?.unverified(/*DNSClass is verified as safe in processing this*/),
-        )
+        ))
+    }
+}
+
+/// Convert from `u16` to `DNSClass`, unrecognized values are `DNSClass::Unknown`
+///
+/// ```
+/// use trust_dns_proto::rr::dns_class::DNSClass;
+///
+/// let var = DNSClass::from(3);
+/// assert_eq!(DNSClass::CH, var);
+///
+/// let var = DNSClass::from(32);
+/// assert_eq!(DNSClass::Unknown(32), var);
+/// ```
+impl From<u16> for DNSClass {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => DNSClass::IN,
+            3 => DNSClass::CH,
+            4 => DNSClass::HS,
+            254 => DNSClass::NONE,
+            255 => DNSClass::ANY,
+            _ => DNSClass::Unknown(value),
+        }
     }
 }
 
@@ -121,6 +139,7 @@ impl From<DNSClass> for &'static str {
             DNSClass::NONE => "NONE",
             DNSClass::ANY => "ANY",
             DNSClass::OPT(_) => "OPT",
+            DNSClass::Unknown(_) => "Unknown",
         }
     }
 }
@@ -143,6 +162,7 @@ impl From<DNSClass> for u16 {
             DNSClass::ANY => 255,
             // see https://tools.ietf.org/html/rfc6891#section-6.1.2
             DNSClass::OPT(max_payload_len) => max_payload_len.max(512),
+            DNSClass::Unknown(value) => value,
         }
     }
 }
@@ -161,7 +181,11 @@ impl Ord for DNSClass {
 
 impl Display for DNSClass {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(Into::<&str>::into(*self))
+        match *self {
+            // RFC 3597 generic class names, so that the class survives a round trip through text
+            DNSClass::Unknown(value) => write!(f, "CLASS{}", value),
+            _ => f.write_str(Into::<&str>::into(*self)),
+        }
     }
 }
 
@@ -186,3 +210,32 @@ fn test_order() {
 
     assert_eq!(unordered, ordered);
 }
+
+#[test]
+fn test_conversions() {
+    let known = &[
+        (1, DNSClass::IN, "IN"),
+        (3, DNSClass::CH, "CH"),
+        (4, DNSClass::HS, "HS"),
+        (254, DNSClass::NONE, "NONE"),
+        (255, DNSClass::ANY, "ANY"),
+    ];
+
+    for (value, class, name) in known {
+        assert_eq!(DNSClass::from(*value), *class);
+        assert_eq!(u16::from(*class), *value);
+        assert_eq!(DNSClass::from_str(name).unwrap(), *class);
+        assert_eq!(class.to_string(), *name);
+    }
+
+    assert_eq!(DNSClass::from(32), DNSClass::Unknown(32));
+    assert_eq!(u16::from(DNSClass::Unknown(32)), 32);
+    assert_eq!(DNSClass::Unknown(32).to_string(), "CLASS32");
+    assert_eq!(
+        DNSClass::from_str("CLASS32").unwrap(),
+        DNSClass::Unknown(32)
+    );
+    assert_eq!(DNSClass::from_str("CLASS1").unwrap(), DNSClass::IN);
+    assert!(DNSClass::from_str("CLASS").is_err());
+    assert!(DNSClass::from_str("XX").is_err());
+}
@@ -49,20 +49,15 @@ pub enum DigestType {
     SHA512,
     /// This is a passthrough digest as ED25519 is self-packaged
     ED25519,
+    /// An unknown or unsupported digest type, preserved so that records using it can still be read
+    Unknown(u8),
 }
 
 impl DigestType {
-    /// TODO: add an Unknown DigestType and make this infallible
-    /// http://www.iana.org/assignments/dns-sec-alg-numbers/dns-sec-alg-numbers.xhtml
+    /// This never fails, unrecognized values are returned as `DigestType::Unknown`
+    #[deprecated(note = "use From/Into")]
     pub fn from_u8(value: u8) -> ProtoResult<Self> {
-        match value {
-            1 => Ok(DigestType::SHA1),
-            2 => Ok(DigestType::SHA256),
-            //  3  => Ok(DigestType::GOSTR34_11_94),
-            4 => Ok(DigestType::SHA384),
-            5 => Ok(DigestType::ED25519),
-            _ => Err(ProtoErrorKind::UnknownAlgorithmTypeValue(value).into()),
-        }
+        Ok(DigestType::from(value))
     }
 
     /// The OpenSSL counterpart for the digest
@@ -150,6 +145,22 @@ impl From<Algorithm> for DigestType {
     }
 }
 
+/// Unrecognized values are returned as `DigestType::Unknown`
+///
+/// http://www.iana.org/assignments/dns-sec-alg-numbers/dns-sec-alg-numbers.xhtml
+impl From<u8> for DigestType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => DigestType::SHA1,
+            2 => DigestType::SHA256,
+            //  3  => DigestType::GOSTR34_11_94,
+            4 => DigestType::SHA384,
+            5 => DigestType::ED25519,
+            _ => DigestType::Unknown(value),
+        }
+    }
+}
+
 impl From<DigestType> for u8 {
     fn from(a: DigestType) -> u8 {
         match a {
@@ -159,6 +170,7 @@ impl From<DigestType> for u8 {
             DigestType::SHA384 => 4,
             DigestType::ED25519 => 5,
             DigestType::SHA512 => 255,
+            DigestType::Unknown(value) => value,
         }
     }
 }
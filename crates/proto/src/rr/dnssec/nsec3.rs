@@ -152,7 +152,8 @@ impl Nsec3HashAlgorithm {
                 {
                     let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut buf);
                     encoder.set_canonical_names(true);
-                    name.emit(&mut encoder).expect("could not encode Name");
+                    name.emit_with_lowercase(&mut encoder, true)
+                        .expect("could not encode Name");
                 }
 
                 Self::sha1_recursive_hash(salt, buf, iterations)
@@ -289,7 +290,7 @@ fn test_known_hashes() {
 #[cfg(test)]
 #[cfg(any(feature = "openssl", feature = "ring"))]
 fn hash_with_base32(name: &str) -> String {
-    use data_encoding::BASE32_DNSSEC;
+    use crate::serialize::txt::to_base32hex;
 
     // NSEC3PARAM 1 0 12 aabbccdd
     let known_name = Name::parse(name, Some(&Name::new())).unwrap();
@@ -297,5 +298,5 @@ fn hash_with_base32(name: &str) -> String {
     let hash = Nsec3HashAlgorithm::SHA1
         .hash(&known_salt, &known_name, 12)
         .unwrap();
-    BASE32_DNSSEC.encode(hash.as_ref())
+    to_base32hex(hash.as_ref())
 }
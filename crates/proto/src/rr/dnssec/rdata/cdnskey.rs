@@ -0,0 +1,179 @@
+/*
+ * Copyright (C) 2020 Benjamin Fry <benjaminfry@me.com>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! child copy of the DNSKEY record, published for the parent to pick up
+
+use std::fmt;
+use std::ops::Deref;
+
+use crate::error::*;
+use crate::rr::dnssec::rdata::dnskey::{self, DNSKEY};
+use crate::rr::dnssec::Algorithm;
+use crate::serialize::binary::*;
+
+/// [RFC 7344, Automating DNSSEC Delegation Trust Maintenance, September 2014](https://tools.ietf.org/html/rfc7344#section-3.2)
+///
+/// ```text
+/// 3.2.  CDNSKEY Resource Record Format
+///
+///    The wire and presentation format of the CDNSKEY ("Child DNSKEY")
+///    resource record is identical to the DNSKEY record.  IANA has
+///    allocated RR code 60 for the CDNSKEY resource record via Expert
+///    Review.  The CDNSKEY RR uses the same registries as DNSKEY for its
+///    fields.
+///
+///    No special processing is performed by authoritative servers or by
+///    resolvers, when serving or resolving.  For all practical purposes,
+///    CDNSKEY is a regular RR type.
+/// ```
+///
+/// [RFC 8078, Managing DS Records from the Parent via CDS/CDNSKEY, March 2017](https://tools.ietf.org/html/rfc8078#section-4)
+///
+/// ```text
+/// 4.  DNSSEC Delete Algorithm
+///
+///    The CDNSKEY record that signals the delete operation is:
+///
+///       CDNSKEY 0 3 0 AA==
+///
+///    Note that there is at least one octet in the public key.
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CDNSKEY(DNSKEY);
+
+impl CDNSKEY {
+    /// Construct a new CDNSKEY RData
+    ///
+    /// # Arguments
+    ///
+    /// * `zone_key` - this key is used to sign Zone resource records
+    /// * `secure_entry_point` - this key is used to sign DNSKeys that sign the Zone records
+    /// * `revoke` - this key has been revoked
+    /// * `algorithm` - specifies the algorithm which this Key uses to sign records
+    /// * `public_key` - the public key material, in native endian, the emitter will perform any necessary conversion
+    ///
+    /// # Return
+    ///
+    /// A new CDNSKEY RData for use in a Resource Record
+    pub fn new(
+        zone_key: bool,
+        secure_entry_point: bool,
+        revoke: bool,
+        algorithm: Algorithm,
+        public_key: Vec<u8>,
+    ) -> CDNSKEY {
+        CDNSKEY(DNSKEY::new(
+            zone_key,
+            secure_entry_point,
+            revoke,
+            algorithm,
+            public_key,
+        ))
+    }
+
+    /// The CDNSKEY requesting that the parent remove all DS records for the child, `0 3 0 AA==`
+    pub fn delete() -> CDNSKEY {
+        CDNSKEY::new(false, false, false, Algorithm::Unknown(0), vec![0])
+    }
+
+    /// Returns true if this is the delete form, see `CDNSKEY::delete`
+    pub fn is_delete(&self) -> bool {
+        self.flags() == 0 && u8::from(self.algorithm()) == 0 && self.public_key() == [0]
+    }
+
+    /// The DNSKEY the parent should create a DS record for, unless this is the delete form
+    pub fn dnskey(&self) -> &DNSKEY {
+        &self.0
+    }
+}
+
+impl From<DNSKEY> for CDNSKEY {
+    fn from(key: DNSKEY) -> Self {
+        CDNSKEY(key)
+    }
+}
+
+impl Deref for CDNSKEY {
+    type Target = DNSKEY;
+
+    fn deref(&self) -> &DNSKEY {
+        &self.0
+    }
+}
+
+/// Read the RData from the given Decoder
+pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoResult<CDNSKEY> {
+    dnskey::read(decoder, rdata_length).map(CDNSKEY)
+}
+
+/// Write the RData from the given Decoder
+pub fn emit(encoder: &mut BinEncoder<'_>, rdata: &CDNSKEY) -> ProtoResult<()> {
+    dnskey::emit(encoder, rdata)
+}
+
+impl fmt::Display for CDNSKEY {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::dbg_macro, clippy::print_stdout)]
+
+    use super::*;
+
+    fn round_trip(rdata: &CDNSKEY) -> CDNSKEY {
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, rdata).is_ok());
+        let bytes = encoder.into_bytes();
+
+        println!("bytes: {:?}", bytes);
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("error decoding")
+    }
+
+    #[test]
+    pub fn test() {
+        let rdata = CDNSKEY::new(
+            true,
+            true,
+            false,
+            Algorithm::RSASHA256,
+            vec![0, 1, 2, 3, 4, 5, 6, 7],
+        );
+
+        assert_eq!(rdata, round_trip(&rdata));
+        assert!(!rdata.is_delete());
+    }
+
+    #[test]
+    pub fn test_delete() {
+        let rdata = CDNSKEY::delete();
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, &rdata).is_ok());
+        assert_eq!(encoder.into_bytes(), &[0, 0, 3, 0, 0]);
+
+        let read_rdata = round_trip(&rdata);
+        assert_eq!(rdata, read_rdata);
+        assert!(read_rdata.is_delete());
+        assert_eq!(read_rdata.to_string(), "0 3 0 AA==");
+    }
+}
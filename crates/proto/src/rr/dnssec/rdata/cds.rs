@@ -0,0 +1,179 @@
+/*
+ * Copyright (C) 2020 Benjamin Fry <benjaminfry@me.com>
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! child copy of the DS record, published for the parent to pick up
+
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+
+use crate::error::*;
+use crate::rr::dnssec::rdata::ds::{self, DS};
+use crate::rr::dnssec::{Algorithm, DigestType};
+use crate::serialize::binary::*;
+
+/// [RFC 7344, Automating DNSSEC Delegation Trust Maintenance, September 2014](https://tools.ietf.org/html/rfc7344#section-3.1)
+///
+/// ```text
+/// 3.1.  CDS Resource Record Format
+///
+///    The wire and presentation format of the Child DS (CDS) resource
+///    record is identical to the DS record [RFC4034].  IANA has allocated
+///    RR code 59 for the CDS resource record via Expert Review
+///    [DNSEXT-ASSIGNMENT].  The CDS RR uses the same registries as DS for
+///    its fields.
+///
+///    No special processing is performed by authoritative servers or by
+///    resolvers, when serving or resolving.  For all practical purposes,
+///    CDS is a regular RR type.
+/// ```
+///
+/// [RFC 8078, Managing DS Records from the Parent via CDS/CDNSKEY, March 2017](https://tools.ietf.org/html/rfc8078#section-4)
+///
+/// ```text
+/// 4.  DNSSEC Delete Algorithm
+///
+///    The DNSKEY algorithm registry contains two reserved values: 0 and
+///    255 [RFC4034].  The CERT record [RFC4398] also uses the same values
+///    and uses value 0 to mean the algorithm is in the CERT record itself.
+///    This document reassigns DNSKEY algorithm 0 as follows:
+///
+///    0  Delete DS
+///
+///    The CDS record that signals the delete operation is:
+///
+///       CDS 0 0 0 00
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CDS(DS);
+
+impl CDS {
+    /// Constructs a new CDS RData
+    ///
+    /// # Arguments
+    ///
+    /// * `key_tag` - the key_tag associated to the DNSKEY
+    /// * `algorithm` - algorithm as specified in the DNSKEY
+    /// * `digest_type` - hash algorithm used to validate the DNSKEY
+    /// * `digest` - hash of the DNSKEY
+    ///
+    /// # Returns
+    ///
+    /// the CDS RDATA for use in a Resource Record
+    pub fn new(
+        key_tag: u16,
+        algorithm: Algorithm,
+        digest_type: DigestType,
+        digest: Vec<u8>,
+    ) -> CDS {
+        CDS(DS::new(key_tag, algorithm, digest_type, digest))
+    }
+
+    /// The CDS requesting that the parent remove all DS records for the child, `0 0 0 00`
+    pub fn delete() -> CDS {
+        CDS::new(0, Algorithm::Unknown(0), DigestType::Unknown(0), vec![0])
+    }
+
+    /// Returns true if this is the delete form, see `CDS::delete`
+    pub fn is_delete(&self) -> bool {
+        self.key_tag() == 0
+            && u8::from(self.algorithm()) == 0
+            && u8::from(self.digest_type()) == 0
+            && self.digest() == [0]
+    }
+
+    /// The DS record the parent should publish, unless this is the delete form
+    pub fn ds(&self) -> &DS {
+        &self.0
+    }
+}
+
+impl From<DS> for CDS {
+    fn from(ds: DS) -> Self {
+        CDS(ds)
+    }
+}
+
+impl Deref for CDS {
+    type Target = DS;
+
+    fn deref(&self) -> &DS {
+        &self.0
+    }
+}
+
+/// Read the RData from the given Decoder
+pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoResult<CDS> {
+    ds::read(decoder, rdata_length).map(CDS)
+}
+
+/// Write the RData from the given Decoder
+pub fn emit(encoder: &mut BinEncoder<'_>, rdata: &CDS) -> ProtoResult<()> {
+    ds::emit(encoder, rdata)
+}
+
+impl Display for CDS {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::dbg_macro, clippy::print_stdout)]
+
+    use super::*;
+
+    fn round_trip(rdata: &CDS) -> CDS {
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, rdata).is_ok());
+        let bytes = encoder.into_bytes();
+
+        println!("bytes: {:?}", bytes);
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let restrict = Restrict::new(bytes.len() as u16);
+        read(&mut decoder, restrict).expect("Decoding error")
+    }
+
+    #[test]
+    pub fn test() {
+        let rdata = CDS::new(
+            0xF00F,
+            Algorithm::RSASHA256,
+            DigestType::SHA256,
+            vec![5, 6, 7, 8],
+        );
+
+        assert_eq!(rdata, round_trip(&rdata));
+        assert!(!rdata.is_delete());
+    }
+
+    #[test]
+    pub fn test_delete() {
+        let rdata = CDS::delete();
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, &rdata).is_ok());
+        assert_eq!(encoder.into_bytes(), &[0, 0, 0, 0, 0]);
+
+        let read_rdata = round_trip(&rdata);
+        assert_eq!(rdata, read_rdata);
+        assert!(read_rdata.is_delete());
+        assert_eq!(read_rdata.to_string(), "0 0 0 00");
+    }
+}
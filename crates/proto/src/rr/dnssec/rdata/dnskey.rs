@@ -25,6 +25,7 @@ use crate::rr::Name;
 use crate::serialize::binary::{
     BinDecodable, BinDecoder, BinEncodable, BinEncoder, Restrict, RestrictedMath,
 };
+use crate::serialize::txt::to_base64;
 
 /// [RFC 4034](https://tools.ietf.org/html/rfc4034#section-2), DNSSEC Resource Records, March 2005
 ///
@@ -445,8 +446,8 @@ impl fmt::Display for DNSKEY {
             f,
             "{flags} 3 {alg} {key}",
             flags = self.flags(),
-            alg = self.algorithm,
-            key = data_encoding::BASE64.encode(&self.public_key)
+            alg = u8::from(self.algorithm),
+            key = to_base64(&self.public_key)
         )
     }
 }
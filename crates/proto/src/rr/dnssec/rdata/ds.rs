@@ -193,7 +193,7 @@ pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoR
     let key_tag: u16 = decoder.read_u16()?.unverified(/*key_tag is valid as any u16*/);
     let algorithm: Algorithm = Algorithm::read(decoder)?;
     let digest_type: DigestType =
-        DigestType::from_u8(decoder.read_u8()?.unverified(/*DigestType is verified as safe*/))?;
+        DigestType::from(decoder.read_u8()?.unverified(/*DigestType is verified as safe*/));
 
     let bytes_read = decoder.index() - start_idx;
     let left: usize = rdata_length
@@ -266,7 +266,7 @@ impl Display for DS {
             f,
             "{tag} {alg} {ty} {digest}",
             tag = self.key_tag,
-            alg = self.algorithm,
+            alg = u8::from(self.algorithm),
             ty = u8::from(self.digest_type),
             digest = data_encoding::HEXUPPER_PERMISSIVE.encode(&self.digest)
         )
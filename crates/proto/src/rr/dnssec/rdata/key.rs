@@ -21,6 +21,7 @@ use crate::error::*;
 use crate::rr::dnssec::Algorithm;
 use crate::rr::record_data::RData;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_base64;
 
 /// [RFC 2535](https://tools.ietf.org/html/rfc2535#section-3), Domain Name System Security Extensions, March 1999
 ///
@@ -911,7 +912,7 @@ impl fmt::Display for KEY {
             flags = self.flags(),
             proto = u8::from(self.protocol),
             alg = self.algorithm,
-            key = data_encoding::BASE64.encode(&self.public_key)
+            key = to_base64(&self.public_key)
         )
     }
 }
@@ -21,6 +21,8 @@ use std::fmt;
 // TODO: these should each be it's own struct, it would make parsing and decoding a little cleaner
 //  and also a little more ergonomic when accessing.
 // each of these module's has the parser for that rdata embedded, to keep the file sizes down...
+pub mod cdnskey;
+pub mod cds;
 pub mod dnskey;
 pub mod ds;
 pub mod key;
@@ -41,6 +43,8 @@ use crate::rr::rdata::null;
 use crate::rr::rdata::NULL;
 use crate::serialize::binary::*;
 
+pub use self::cdnskey::CDNSKEY;
+pub use self::cds::CDS;
 pub use self::dnskey::DNSKEY;
 pub use self::ds::DS;
 pub use self::key::KEY;
@@ -53,8 +57,10 @@ pub use self::sig::SIG;
 #[cfg_attr(feature = "serde-config", derive(Deserialize, Serialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum DNSSECRecordType {
-    //  CDS,        //	59	RFC 7344	Child DS
-    //  CDNSKEY,    //	60	RFC 7344	Child DNSKEY
+    /// RFC 7344 Child DNSKEY
+    CDNSKEY,
+    /// RFC 7344 Child DS
+    CDS,
     //  DLV,        //	32769	RFC 4431	DNSSEC Lookaside Validation record
     /// RFC 4034 DNS Key record: RSASHA256 and RSASHA512, RFC5702
     DNSKEY,
@@ -85,6 +91,8 @@ impl FromStr for DNSSECRecordType {
 
     fn from_str(str: &str) -> ProtoResult<Self> {
         match str {
+            "CDNSKEY" => Ok(DNSSECRecordType::CDNSKEY),
+            "CDS" => Ok(DNSSECRecordType::CDS),
             "DNSKEY" => Ok(DNSSECRecordType::DNSKEY),
             "DS" => Ok(DNSSECRecordType::DS),
             "KEY" => Ok(DNSSECRecordType::KEY),
@@ -101,6 +109,8 @@ impl FromStr for DNSSECRecordType {
 impl From<u16> for DNSSECRecordType {
     fn from(value: u16) -> Self {
         match value {
+            60 => DNSSECRecordType::CDNSKEY,
+            59 => DNSSECRecordType::CDS,
             48 => DNSSECRecordType::DNSKEY,
             43 => DNSSECRecordType::DS,
             25 => DNSSECRecordType::KEY,
@@ -117,6 +127,8 @@ impl From<u16> for DNSSECRecordType {
 impl From<DNSSECRecordType> for &'static str {
     fn from(rt: DNSSECRecordType) -> &'static str {
         match rt {
+            DNSSECRecordType::CDNSKEY => "CDNSKEY",
+            DNSSECRecordType::CDS => "CDS",
             DNSSECRecordType::DNSKEY => "DNSKEY",
             DNSSECRecordType::DS => "DS",
             DNSSECRecordType::KEY => "KEY",
@@ -133,6 +145,8 @@ impl From<DNSSECRecordType> for &'static str {
 impl From<DNSSECRecordType> for u16 {
     fn from(rt: DNSSECRecordType) -> Self {
         match rt {
+            DNSSECRecordType::CDNSKEY => 60,
+            DNSSECRecordType::CDS => 59,
             DNSSECRecordType::KEY => 25,
             DNSSECRecordType::DNSKEY => 48,
             DNSSECRecordType::DS => 43,
@@ -149,6 +163,26 @@ impl From<DNSSECRecordType> for u16 {
 /// Record data enum variants for DNSSEC-specific records.
 #[derive(Debug, EnumAsInner, PartialEq, Clone, Eq)]
 pub enum DNSSECRData {
+    /// ```text
+    /// RFC 7344              Delegation Trust Maintenance        September 2014
+    ///
+    /// 3.2.  CDNSKEY Resource Record Format
+    ///
+    ///    The wire and presentation format of the CDNSKEY ("Child DNSKEY")
+    ///    resource record is identical to the DNSKEY record.
+    /// ```
+    CDNSKEY(CDNSKEY),
+
+    /// ```text
+    /// RFC 7344              Delegation Trust Maintenance        September 2014
+    ///
+    /// 3.1.  CDS Resource Record Format
+    ///
+    ///    The wire and presentation format of the Child DS (CDS) resource
+    ///    record is identical to the DS record [RFC4034].
+    /// ```
+    CDS(CDS),
+
     /// ```text
     /// RFC 4034                DNSSEC Resource Records               March 2005
     ///
@@ -490,6 +524,14 @@ impl DNSSECRData {
         rdata_length: Restrict<u16>,
     ) -> ProtoResult<Self> {
         match record_type {
+            DNSSECRecordType::CDNSKEY => {
+                trace!("reading CDNSKEY");
+                cdnskey::read(decoder, rdata_length).map(DNSSECRData::CDNSKEY)
+            }
+            DNSSECRecordType::CDS => {
+                trace!("reading CDS");
+                cds::read(decoder, rdata_length).map(DNSSECRData::CDS)
+            }
             DNSSECRecordType::DNSKEY => {
                 trace!("reading DNSKEY");
                 dnskey::read(decoder, rdata_length).map(DNSSECRData::DNSKEY)
@@ -531,6 +573,12 @@ impl DNSSECRData {
 
     pub(crate) fn emit(&self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
         match *self {
+            DNSSECRData::CDNSKEY(ref cdnskey) => {
+                encoder.with_canonical_names(|encoder| cdnskey::emit(encoder, cdnskey))
+            }
+            DNSSECRData::CDS(ref cds) => {
+                encoder.with_canonical_names(|encoder| cds::emit(encoder, cds))
+            }
             DNSSECRData::DS(ref ds) => {
                 encoder.with_canonical_names(|encoder| ds::emit(encoder, ds))
             }
@@ -549,9 +597,7 @@ impl DNSSECRData {
             DNSSECRData::NSEC3PARAM(ref nsec3param) => {
                 encoder.with_canonical_names(|encoder| nsec3param::emit(encoder, nsec3param))
             }
-            DNSSECRData::SIG(ref sig) => {
-                encoder.with_canonical_names(|encoder| sig::emit(encoder, sig))
-            }
+            DNSSECRData::SIG(ref sig) => sig::emit(encoder, sig),
             DNSSECRData::Unknown { ref rdata, .. } => {
                 encoder.with_canonical_names(|encoder| null::emit(encoder, rdata))
             }
@@ -560,6 +606,8 @@ impl DNSSECRData {
 
     pub(crate) fn to_record_type(&self) -> DNSSECRecordType {
         match *self {
+            DNSSECRData::CDNSKEY(..) => DNSSECRecordType::CDNSKEY,
+            DNSSECRData::CDS(..) => DNSSECRecordType::CDS,
             DNSSECRData::DS(..) => DNSSECRecordType::DS,
             DNSSECRData::KEY(..) => DNSSECRecordType::KEY,
             DNSSECRData::DNSKEY(..) => DNSSECRecordType::DNSKEY,
@@ -579,6 +627,8 @@ impl fmt::Display for DNSSECRData {
         };
 
         match self {
+            DNSSECRData::CDNSKEY(cdnskey) => w(f, cdnskey),
+            DNSSECRData::CDS(cds) => w(f, cds),
             DNSSECRData::DS(ds) => w(f, ds),
             DNSSECRData::KEY(key) => w(f, key),
             DNSSECRData::DNSKEY(key) => w(f, key),
@@ -128,6 +128,11 @@ impl NSEC {
     pub fn type_bit_maps(&self) -> &[RecordType] {
         &self.type_bit_maps
     }
+
+    /// Returns true if an RRSet of the given type exists at the owner name of this NSEC
+    pub fn contains_type(&self, record_type: RecordType) -> bool {
+        self.type_bit_maps.contains(&record_type)
+    }
 }
 
 /// Read the RData from the given Decoder
@@ -241,4 +246,102 @@ mod tests {
         let read_rdata = read(&mut decoder, restrict).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_type_bit_maps_round_trip() {
+        use crate::rr::dnssec::rdata::DNSSECRecordType;
+        use std::str::FromStr;
+
+        let types = vec![
+            RecordType::A,
+            RecordType::AAAA,
+            RecordType::DNSSEC(DNSSECRecordType::RRSIG),
+            RecordType::DNSSEC(DNSSECRecordType::NSEC),
+        ];
+        // out of order and with a duplicate, neither of which survive the bitmap
+        let rdata = NSEC::new(
+            Name::from_str("host.example.com.").unwrap(),
+            vec![types[3], types[1], types[0], types[2], types[1]],
+        );
+        assert!(rdata.contains_type(RecordType::AAAA));
+        assert!(!rdata.contains_type(RecordType::MX));
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        emit(&mut encoder, &rdata).expect("failed to emit NSEC");
+        let bytes = encoder.into_bytes();
+
+        // window 0, 6 octets, trailing zero octets trimmed after NSEC (47)
+        let name_len = bytes.len() - 8;
+        assert_eq!(
+            &bytes[name_len..],
+            &[0x00, 0x06, 0x40, 0x00, 0x00, 0x08, 0x00, 0x03]
+        );
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let read_rdata =
+            read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("Decoding error");
+        assert_eq!(read_rdata.type_bit_maps(), &types[..]);
+        for ty in &types {
+            assert!(read_rdata.contains_type(*ty));
+        }
+    }
+
+    #[test]
+    fn test_rfc4034_example() {
+        use crate::rr::dnssec::rdata::DNSSECRecordType;
+
+        // alfa.example.com. 86400 IN NSEC host.example.com. (
+        //                                 A MX RRSIG NSEC TYPE1234 )
+        #[rustfmt::skip]
+        let bytes = [
+            0x04, b'h', b'o', b's', b't',
+            0x07, b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+            0x03, b'c', b'o', b'm', 0x00,
+            0x00, 0x06, 0x40, 0x01, 0x00, 0x00, 0x00, 0x03,
+            0x04, 0x1b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20,
+        ];
+
+        let mut decoder = BinDecoder::new(&bytes);
+        let rdata = read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("Decoding error");
+        assert_eq!(
+            rdata.type_bit_maps(),
+            &[
+                RecordType::A,
+                RecordType::MX,
+                RecordType::DNSSEC(DNSSECRecordType::RRSIG),
+                RecordType::DNSSEC(DNSSECRecordType::NSEC),
+                RecordType::Unknown(1234),
+            ]
+        );
+        assert_eq!(
+            rdata.to_string(),
+            "host.example.com. A MX RRSIG NSEC TYPE1234"
+        );
+
+        let mut emitted = Vec::new();
+        let mut encoder = BinEncoder::new(&mut emitted);
+        emit(&mut encoder, &rdata).expect("failed to emit NSEC");
+        assert_eq!(&emitted[..], &bytes[..]);
+    }
+
+    #[test]
+    fn test_read_bad_type_bit_maps() {
+        let name = [0x00];
+        for bit_maps in &[
+            // zero length bitmap
+            &[0x00, 0x00][..],
+            // bitmaps are at most 32 octets
+            &[0x00, 0x21][..],
+            // truncated window
+            &[0x00, 0x02, 0x40][..],
+            &[0x00][..],
+        ] {
+            let bytes = [&name[..], bit_maps].concat();
+            let mut decoder = BinDecoder::new(&bytes);
+            assert!(read(&mut decoder, Restrict::new(bytes.len() as u16)).is_err());
+        }
+    }
 }
@@ -19,9 +19,14 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::error::*;
+#[cfg(any(feature = "openssl", feature = "ring"))]
+use crate::rr::dnssec::Digest;
 use crate::rr::dnssec::Nsec3HashAlgorithm;
+#[cfg(any(feature = "openssl", feature = "ring"))]
+use crate::rr::Name;
 use crate::rr::RecordType;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_base32hex;
 
 /// [RFC 5155](https://tools.ietf.org/html/rfc5155#section-3), NSEC3, March 2008
 ///
@@ -238,6 +243,11 @@ impl NSEC3 {
         &self.type_bit_maps
     }
 
+    /// Returns true if an RRSet of the given type exists at the original owner name of this NSEC3
+    pub fn contains_type(&self, record_type: RecordType) -> bool {
+        self.type_bit_maps.contains(&record_type)
+    }
+
     /// Flags for encoding
     pub fn flags(&self) -> u8 {
         let mut flags: u8 = 0;
@@ -246,6 +256,15 @@ impl NSEC3 {
         };
         flags
     }
+
+    /// Hashes the name with the algorithm, salt and iterations of this NSEC3
+    ///
+    /// The result can be compared against the hashed owner name of other NSEC3 records in the
+    ///  zone, or this record's `next_hashed_owner_name`, to prove or disprove the name exists.
+    #[cfg(any(feature = "openssl", feature = "ring"))]
+    pub fn hash_name(&self, name: &Name) -> ProtoResult<Digest> {
+        self.hash_algorithm.hash(&self.salt, name, self.iterations)
+    }
 }
 
 /// Read the RData from the given Decoder
@@ -377,11 +396,20 @@ pub(crate) fn decode_type_bit_maps(
             BitMapReadState::Window => BitMapReadState::Len {
                 window: current_byte.unverified(/*window is any valid u8,*/),
             },
-            BitMapReadState::Len { window } => BitMapReadState::RecordType {
-                window,
-                len: current_byte,
-                left: current_byte,
-            },
+            BitMapReadState::Len { window } => {
+                // a window always has between 1 and 32 octets of bitmap
+                let len = current_byte
+                    .verify_unwrap(|len| (1..=32).contains(len))
+                    .map_err(|len| {
+                        ProtoError::from(format!("invalid bitmap length in NSEC(3): {}", len))
+                    })?;
+
+                BitMapReadState::RecordType {
+                    window,
+                    len: Restrict::new(len),
+                    left: Restrict::new(len),
+                }
+            }
             BitMapReadState::RecordType { window, len, left } => {
                 // window is the Window Block # from above
                 // len is the Bitmap Length
@@ -400,7 +428,11 @@ pub(crate) fn decode_type_bit_maps(
                             .map_err(|_| "block len or left out of bounds in NSEC(3)")?
                             .unverified(/*any u8 is valid at this point*/);
                         let rr_type: u16 = (u16::from(window) << 8) | u16::from(low_byte);
-                        record_types.push(RecordType::from(rr_type));
+
+                        // bit 0 of window 0 is the non-existent type 0, and is ignored
+                        if rr_type != 0 {
+                            record_types.push(RecordType::from(rr_type));
+                        }
                     }
                     // shift left and look at the next bit
                     bit_map <<= 1;
@@ -421,7 +453,10 @@ pub(crate) fn decode_type_bit_maps(
         };
     }
 
-    Ok(record_types)
+    match state {
+        BitMapReadState::Window => Ok(record_types),
+        _ => Err("type bit maps ended in the middle of a window in NSEC(3)".into()),
+    }
 }
 
 enum BitMapReadState {
@@ -544,7 +579,7 @@ impl fmt::Display for NSEC3 {
             flags = self.flags(),
             iterations = self.iterations,
             salt = salt,
-            owner = data_encoding::BASE32_NOPAD.encode(&self.next_hashed_owner_name)
+            owner = to_base32hex(&self.next_hashed_owner_name)
         )?;
 
         for ty in &self.type_bit_maps {
@@ -560,6 +595,7 @@ mod tests {
     #![allow(clippy::dbg_macro, clippy::print_stdout)]
 
     use super::*;
+    use crate::serialize::txt::from_base32hex;
 
     #[test]
     pub fn test() {
@@ -637,4 +673,79 @@ mod tests {
         let read_rdata = read(&mut decoder, restrict).expect("Decoding error");
         assert_eq!(rdata_wo, read_rdata);
     }
+
+    #[test]
+    fn test_display() {
+        use crate::rr::dnssec::rdata::DNSSECRecordType;
+
+        // 0p9mhaveqvm6t7vbl5lop2u3t2rp3tom.example. NSEC3 1 1 12 aabbccdd (
+        //                       2t7b4g4vsa5smi47k61mv5bv1a22bojr MX DNSKEY NS
+        //                       SOA NSEC3PARAM RRSIG )
+        let next_hashed_owner_name = from_base32hex("2t7b4g4vsa5smi47k61mv5bv1a22bojr").unwrap();
+        assert_eq!(next_hashed_owner_name.len(), 20);
+
+        let rdata = NSEC3::new(
+            Nsec3HashAlgorithm::SHA1,
+            true,
+            12,
+            vec![0xaa, 0xbb, 0xcc, 0xdd],
+            next_hashed_owner_name,
+            vec![
+                RecordType::NS,
+                RecordType::SOA,
+                RecordType::MX,
+                RecordType::DNSSEC(DNSSECRecordType::RRSIG),
+                RecordType::DNSSEC(DNSSECRecordType::DNSKEY),
+                RecordType::DNSSEC(DNSSECRecordType::NSEC3PARAM),
+            ],
+        );
+        assert!(rdata.contains_type(RecordType::SOA));
+        assert!(!rdata.contains_type(RecordType::A));
+
+        // the next hashed owner name is base32hex, not the standard base32 alphabet
+        assert_eq!(
+            rdata.to_string(),
+            "1 1 12 AABBCCDD 2t7b4g4vsa5smi47k61mv5bv1a22bojr NS SOA MX RRSIG DNSKEY NSEC3PARAM"
+        );
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        emit(&mut encoder, &rdata).expect("failed to emit NSEC3");
+        let bytes = encoder.into_bytes();
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let read_rdata =
+            read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("Decoding error");
+        assert_eq!(rdata, read_rdata);
+    }
+
+    #[test]
+    #[cfg(any(feature = "openssl", feature = "ring"))]
+    fn test_hash_name() {
+        use std::str::FromStr;
+
+        // RFC 5155, Appendix A, the NSEC3 at the apex of example. points at H(ns1.example)
+        let rdata = NSEC3::new(
+            Nsec3HashAlgorithm::SHA1,
+            true,
+            12,
+            vec![0xaa, 0xbb, 0xcc, 0xdd],
+            from_base32hex("2t7b4g4vsa5smi47k61mv5bv1a22bojr").unwrap(),
+            vec![RecordType::NS, RecordType::SOA],
+        );
+
+        let apex = rdata
+            .hash_name(&Name::from_str("example.").unwrap())
+            .unwrap();
+        assert_eq!(
+            to_base32hex(apex.as_ref()),
+            "0p9mhaveqvm6t7vbl5lop2u3t2rp3tom"
+        );
+
+        // names are hashed in canonical form, so case doesn't matter
+        let next = rdata
+            .hash_name(&Name::from_str("NS1.Example.").unwrap())
+            .unwrap();
+        assert_eq!(next.as_ref(), rdata.next_hashed_owner_name());
+    }
 }
@@ -21,6 +21,7 @@ use crate::error::*;
 use crate::rr::dnssec::Algorithm;
 use crate::rr::{Name, RecordType};
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_base64;
 
 /// [RFC 2535](https://tools.ietf.org/html/rfc2535#section-4), Domain Name System Security Extensions, March 1999
 ///
@@ -516,8 +517,12 @@ pub fn emit(encoder: &mut BinEncoder<'_>, sig: &SIG) -> ProtoResult<()> {
     encoder.emit_u32(sig.sig_expiration())?;
     encoder.emit_u32(sig.sig_inception())?;
     encoder.emit_u16(sig.key_tag())?;
-    sig.signer_name()
-        .emit_with_lowercase(encoder, is_canonical_names)?;
+    // the signer's name is never compressed, RFC 4034 section 3.1.7, but is only lowercased for
+    //  canonical form
+    encoder.with_canonical_names(|encoder| {
+        sig.signer_name()
+            .emit_with_lowercase(encoder, is_canonical_names)
+    })?;
     encoder.emit_vec(sig.sig())?;
     Ok(())
 }
@@ -601,7 +606,7 @@ impl fmt::Display for SIG {
             inception = self.sig_inception,
             tag = self.key_tag,
             signer = self.signer_name,
-            sig = data_encoding::BASE64.encode(&self.sig)
+            sig = to_base64(&self.sig)
         )
     }
 }
@@ -87,21 +87,10 @@ pub fn rrset_tbs(
     signer_name: &Name,
     records: &[Record],
 ) -> ProtoResult<TBS> {
-    // TODO: change this to a BTreeSet so that it's preordered, no sort necessary
-    let mut rrset: Vec<&Record> = Vec::new();
-
-    // collect only the records for this rrset
-    for record in records {
-        if dns_class == record.dns_class()
-            && type_covered == record.rr_type()
-            && name == record.name()
-        {
-            rrset.push(record);
-        }
-    }
-
-    // put records in canonical order
-    rrset.sort();
+    // collect only the records for this rrset, in canonical order
+    let rrset = canonical_rrset(records.iter().filter(|record| {
+        dns_class == record.dns_class() && type_covered == record.rr_type() && name == record.name()
+    }))?;
 
     let name: Name = if let Some(name) = determine_name(name, num_labels) {
         name
@@ -173,6 +162,56 @@ pub fn rrset_tbs(
     Ok(TBS(buf))
 }
 
+/// Returns the records of an RRset in canonical order
+///
+/// The records are sorted by the canonical form of their RDATA, with names in the RDATA fully
+///  expanded and lowercased. Records with identical canonical RDATA are duplicates and all but
+///  one of them is removed.
+///
+/// [RFC 4034](https://tools.ietf.org/html/rfc4034#section-6.3), DNSSEC Resource Records, March 2005
+///
+/// ```text
+/// 6.3.  Canonical RR Ordering within an RRset
+///
+///    For the purposes of DNS security, RRs with the same owner name,
+///    class, and type are sorted by treating the RDATA portion of the
+///    canonical form of each RR as a left-justified unsigned octet sequence
+///    in which the absence of an octet sorts before a zero octet.
+///
+///    [RFC2181] specifies that an RRset is not allowed to contain duplicate
+///    records (multiple RRs with the same owner name, class, type, and
+///    RDATA).  Therefore, if an implementation detects duplicate RRs when
+///    putting the RRset in canonical form, it MUST treat this as a protocol
+///    error.  If the implementation chooses to handle this protocol error
+///    in the spirit of the robustness principle (being liberal in what it
+///    accepts), it MUST remove all but one of the duplicate RR(s) for the
+///    purposes of calculating the canonical form of the RRset.
+/// ```
+pub fn canonical_rrset<'r, I>(records: I) -> ProtoResult<Vec<&'r Record>>
+where
+    I: IntoIterator<Item = &'r Record>,
+{
+    let mut rrset = records
+        .into_iter()
+        .map(|record| {
+            let mut rdata = Vec::new();
+            {
+                let mut encoder = BinEncoder::new(&mut rdata);
+                encoder.set_canonical_names(true);
+                record.rdata().emit(&mut encoder)?;
+            }
+
+            Ok((rdata, record))
+        })
+        .collect::<ProtoResult<Vec<(Vec<u8>, &Record)>>>()?;
+
+    // the sort is stable, so the first of any duplicates is the one kept
+    rrset.sort_by(|(left, _), (right, _)| left.cmp(right));
+    rrset.dedup_by(|(left, _), (right, _)| left == right);
+
+    Ok(rrset.into_iter().map(|(_, record)| record).collect())
+}
+
 /// Returns the to-be-signed serialization of the given record set using the information
 /// provided from the RRSIG record.
 ///
@@ -290,3 +329,86 @@ pub fn determine_name(name: &Name, num_labels: u8) -> Option<Name> {
     // TODO: this should be an error
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::rr::rdata::MX;
+
+    fn exchanges(rrset: &[&Record]) -> Vec<(u16, String)> {
+        rrset
+            .iter()
+            .map(|record| match record.rdata() {
+                RData::MX(mx) => (mx.preference(), mx.exchange().to_lowercase().to_string()),
+                _ => panic!("expected MX"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_canonical_rrset() {
+        let name = Name::from_str("example.com.").unwrap();
+        let mx = |preference, exchange| {
+            Record::from_rdata(
+                name.clone(),
+                3600,
+                RData::MX(MX::new(preference, Name::from_ascii(exchange).unwrap())),
+            )
+        };
+
+        let mut records = vec![
+            mx(20, "a.example.com."),
+            mx(10, "c.example.com."),
+            // uppercase sorts before lowercase on the wire, but not in canonical form
+            mx(10, "B.example.com."),
+            mx(10, "a.example.com."),
+            // a duplicate of the above in canonical form
+            mx(10, "A.Example.com."),
+            // a longer label sorts after a shorter one, the length octet comes first
+            mx(10, "aa.example.com."),
+        ];
+
+        let expected = vec![
+            (10, "a.example.com.".to_string()),
+            (10, "b.example.com.".to_string()),
+            (10, "c.example.com.".to_string()),
+            (10, "aa.example.com.".to_string()),
+            (20, "a.example.com.".to_string()),
+        ];
+        assert_eq!(exchanges(&canonical_rrset(&records).unwrap()), expected);
+
+        // the order doesn't depend on the order the records were given in
+        records.reverse();
+        assert_eq!(exchanges(&canonical_rrset(&records).unwrap()), expected);
+        records.swap(0, 3);
+        assert_eq!(exchanges(&canonical_rrset(&records).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_canonical_rrset_addresses() {
+        let name = Name::from_str("example.com.").unwrap();
+        let records = [3, 1, 200, 20, 1]
+            .iter()
+            .map(|last| {
+                Record::from_rdata(
+                    name.clone(),
+                    3600,
+                    RData::A(Ipv4Addr::new(192, 0, 2, *last)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let rrset = canonical_rrset(&records).unwrap();
+        let addresses = rrset
+            .iter()
+            .map(|record| record.rdata().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            vec!["192.0.2.1", "192.0.2.3", "192.0.2.20", "192.0.2.200"]
+        );
+    }
+}
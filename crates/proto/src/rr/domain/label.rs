@@ -38,7 +38,7 @@ impl Label {
     /// Generally users should use `from_str` or `from_ascii`
     pub fn from_raw_bytes(bytes: &[u8]) -> ProtoResult<Self> {
         if bytes.len() > 63 {
-            return Err(format!("Label exceeds maximum length 63: {}", bytes.len()).into());
+            return Err(ProtoErrorKind::LabelBytesTooLong(bytes.len()).into());
         };
         Ok(Label(Rc::from(bytes)))
     }
@@ -172,7 +172,7 @@ impl Label {
             f: &mut W,
             is_first: bool,
         ) -> Result<(), fmt::Error> {
-            let to_triple_escape = |ch: u8| format!("\\{:03o}", ch);
+            let to_triple_escape = |ch: u8| format!("\\{:03}", ch);
             let to_single_escape = |ch: char| format!("\\{}", ch);
 
             match char::from(byte) {
@@ -228,6 +228,9 @@ impl Display for Label {
     ///
     /// if the string is punycode, i.e. starts with `xn--`, otherwise it translates to a safe ascii string
     ///   escaping characters as necessary.
+    ///
+    /// The unicode form is only used when it needs no escapes, e.g. an embedded `.` would be
+    ///   ambiguous, so such a label is written in its escaped ascii form instead.
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
         if self.as_bytes().starts_with(IDNA_PREFIX) {
             // this should never be outside the ascii codes...
@@ -238,9 +241,17 @@ impl Display for Label {
                 .verify_dns_length(false)
                 .to_unicode(&label);
 
-            if e.is_ok() {
+            let needs_escape = label.chars().enumerate().any(|(i, c)| {
+                if c.is_ascii() {
+                    !is_safe_ascii(c, i == 0, true)
+                } else {
+                    c.is_control() || c.is_whitespace()
+                }
+            });
+
+            if e.is_ok() && !needs_escape {
                 return f.write_str(&label);
-            } else {
+            } else if e.is_err() {
                 debug!(
                     "xn-- prefixed string did not translate via IDNA properly: {:?}",
                     e
@@ -485,19 +496,43 @@ mod tests {
 
     #[test]
     fn test_ascii_escape() {
+        assert_eq!(Label::from_raw_bytes(&[128]).unwrap().to_string(), "\\128");
+        assert_eq!(Label::from_raw_bytes(&[1]).unwrap().to_string(), "\\001");
+        assert_eq!(Label::from_ascii(".").unwrap().to_ascii(), "\\.");
         assert_eq!(
-            Label::from_raw_bytes(&[0o200]).unwrap().to_string(),
-            "\\200"
+            Label::from_ascii("ben.fry").unwrap().to_string(),
+            "ben\\.fry"
         );
+        assert_eq!(Label::from_raw_bytes(&[128]).unwrap().to_ascii(), "\\128");
+        assert_eq!(Label::from_raw_bytes(b"a\\b").unwrap().to_ascii(), "a\\\\b");
+
+        // the unicode form of an IDNA label is only used when it needs no escapes
         assert_eq!(
-            Label::from_raw_bytes(&[0o001]).unwrap().to_string(),
-            "\\001"
+            Label::from_raw_bytes(b"xn--a.b").unwrap().to_string(),
+            "xn--a\\.b"
         );
-        assert_eq!(Label::from_ascii(".").unwrap().to_ascii(), "\\.");
         assert_eq!(
-            Label::from_ascii("ben.fry").unwrap().to_string(),
-            "ben\\.fry"
+            Label::from_raw_bytes(b"xn--a b").unwrap().to_string(),
+            "xn--a\\032b"
         );
-        assert_eq!(Label::from_raw_bytes(&[0o200]).unwrap().to_ascii(), "\\200");
+    }
+
+    #[test]
+    fn test_label_max_len() {
+        let max = "a".repeat(63);
+        assert_eq!(Label::from_ascii(&max).unwrap().len(), 63);
+        assert_eq!(Label::from_utf8(&max).unwrap().len(), 63);
+
+        let too_long = "a".repeat(64);
+        for result in vec![
+            Label::from_raw_bytes(too_long.as_bytes()),
+            Label::from_ascii(&too_long),
+        ] {
+            match *result.unwrap_err().kind() {
+                ProtoErrorKind::LabelBytesTooLong(64) => (),
+                ref kind => panic!("wrong error kind: {:?}", kind),
+            }
+        }
+        assert!(Label::from_utf8(&too_long).is_err());
     }
 }
@@ -9,9 +9,11 @@
 
 mod label;
 mod name;
+mod name_ref;
 mod try_parse_ip;
 pub mod usage;
 
 pub use self::label::{IntoLabel, Label};
-pub use self::name::{IntoName, LabelIter, Name};
+pub use self::name::{CaseMatch, IntoName, LabelIter, Name};
+pub use self::name_ref::{BorrowedLabelIter, BorrowedName, NameRef};
 pub use self::try_parse_ip::TryParseIp;
@@ -22,6 +22,7 @@ use crate::rr::domain::label::{CaseInsensitive, CaseSensitive, IntoLabel, Label,
 use crate::rr::domain::usage::LOCALHOST as LOCALHOST_usage;
 use crate::serialize::binary::*;
 use ipnet::{IpNet, Ipv4Net, Ipv6Net};
+use rand::Rng;
 #[cfg(feature = "serde-config")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -94,7 +95,18 @@ impl Name {
         self.is_fqdn = val
     }
 
-    /// Returns an iterator over the labels
+    /// Returns an iterator over the labels, the root is not included
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use trust_dns_proto::rr::domain::Name;
+    ///
+    /// let name = Name::from_str("www.example.com.").unwrap();
+    /// let labels: Vec<&[u8]> = name.iter().collect();
+    /// assert_eq!(labels, vec![b"www" as &[u8], b"example", b"com"]);
+    /// ```
     pub fn iter(&self) -> LabelIter<'_> {
         LabelIter(self.labels.iter())
     }
@@ -116,6 +128,33 @@ impl Name {
         if self.labels.len() > 255 {
             return Err("labels exceed maximum length of 255".into());
         };
+        if self.len() > 255 {
+            return Err(ProtoErrorKind::DomainNameTooLong(self.len()).into());
+        };
+        Ok(self)
+    }
+
+    /// Prepends the label to the beginning of this name
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use trust_dns_proto::rr::domain::Name;
+    ///
+    /// let name = Name::from_str("example.com.").unwrap();
+    /// let name = name.prepend_label("_tcp").unwrap().prepend_label("_sip").unwrap();
+    /// assert_eq!(name, Name::from_str("_sip._tcp.example.com.").unwrap());
+    /// assert!(name.is_fqdn());
+    /// ```
+    pub fn prepend_label<L: IntoLabel>(mut self, label: L) -> ProtoResult<Self> {
+        self.labels.insert(0, label.into_label()?);
+        if self.labels.len() > 255 {
+            return Err("labels exceed maximum length of 255".into());
+        };
+        if self.len() > 255 {
+            return Err(ProtoErrorKind::DomainNameTooLong(self.len()).into());
+        };
         Ok(self)
     }
 
@@ -123,7 +162,9 @@ impl Name {
     ///
     /// # Arguments
     ///
-    /// * `labels` - vector of items which will be stored as Strings.
+    /// * `labels` - items which will be converted into labels, `&[u8]` and `Vec<u8>` are taken
+    ///              as raw bytes, without requiring UTF-8, while strings are IDNA encoded. Each
+    ///              label must be at most 63 bytes, and the whole name at most 255.
     ///
     /// # Examples
     ///
@@ -161,10 +202,14 @@ impl Name {
             return Err(format!("error converting some labels: {:?}", errors).into());
         };
 
-        Ok(Name {
+        let name = Name {
             is_fqdn: true,
             labels,
-        })
+        };
+        if name.len() > 255 {
+            return Err(ProtoErrorKind::DomainNameTooLong(name.len()).into());
+        };
+        Ok(name)
     }
 
     /// Appends `other` to `self`, returning a new `Name`
@@ -202,6 +247,36 @@ impl Name {
         self
     }
 
+    /// Appends `other` to `self`, returning a new `Name`
+    ///
+    /// Unlike `append_name`, this fails if the resulting name would exceed 255 octets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::str::FromStr;
+    /// use trust_dns_proto::rr::domain::Name;
+    ///
+    /// let local = Name::from_str("www").unwrap();
+    /// let domain = Name::from_str("example.com.").unwrap();
+    /// let name = local.append(&domain).unwrap();
+    /// assert_eq!(name, Name::from_str("www.example.com.").unwrap());
+    /// assert!(name.is_fqdn());
+    ///
+    /// let long = Name::from_str(&"a.".repeat(127)).unwrap();
+    /// assert!(long.append(&domain).is_err());
+    /// ```
+    pub fn append(&self, other: &Self) -> ProtoResult<Self> {
+        let name = self.clone().append_name(other);
+        if name.labels.len() > 255 {
+            return Err("labels exceed maximum length of 255".into());
+        };
+        if name.len() > 255 {
+            return Err(ProtoErrorKind::DomainNameTooLong(name.len()).into());
+        };
+        Ok(name)
+    }
+
     /// Appends the `domain` to `self`, making the new `Name` an FQDN
     ///
     /// This is an alias for `append_name` with the added effect of marking the new `Name` as
@@ -300,35 +375,13 @@ impl Name {
 
     /// same as `zone_of` allows for case sensitive call
     pub fn zone_of_case(&self, name: &Self) -> bool {
-        let self_len = self.labels.len();
-        let name_len = name.labels.len();
-        if self_len == 0 {
-            return true;
-        }
-        if name_len == 0 {
-            // self_len != 0
-            return false;
-        }
-        if self_len > name_len {
-            return false;
-        }
-
-        let self_iter = self.iter().rev();
-        let name_iter = name.iter().rev();
-
-        let zip_iter = self_iter.zip(name_iter);
-
-        for (self_label, name_label) in zip_iter {
-            if self_label != name_label {
-                return false;
-            }
-        }
-
-        true
+        self.zone_of_with_f::<CaseSensitive>(name)
     }
 
     /// returns true if the name components of self are all present at the end of name
     ///
+    /// Labels are compared case-insensitively, see `zone_of_case` for a case sensitive version.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -336,7 +389,6 @@ impl Name {
     /// use trust_dns_proto::rr::domain::Name;
     ///
     /// let name = Name::from_str("www.example.com").unwrap();
-    /// let name = Name::from_str("www.example.com").unwrap();
     /// let zone = Name::from_str("example.com").unwrap();
     /// let another = Name::from_str("example.net").unwrap();
     /// assert!(zone.zone_of(&name));
@@ -344,10 +396,22 @@ impl Name {
     /// assert!(!another.zone_of(&name));
     /// ```
     pub fn zone_of(&self, name: &Self) -> bool {
-        let self_lower = self.to_lowercase();
-        let name_lower = name.to_lowercase();
+        self.zone_of_with_f::<CaseInsensitive>(name)
+    }
+
+    fn zone_of_with_f<F: LabelCmp>(&self, name: &Self) -> bool {
+        if self.labels.len() > name.labels.len() {
+            return false;
+        }
 
-        self_lower.zone_of_case(&name_lower)
+        // compare from the root towards the leaf, all of self must be a suffix of name
+        self.labels
+            .iter()
+            .rev()
+            .zip(name.labels.iter().rev())
+            .all(|(self_label, name_label)| {
+                self_label.cmp_with_f::<F>(name_label) == Ordering::Equal
+            })
     }
 
     /// Returns the number of labels in the name, discounting `*`.
@@ -447,7 +511,7 @@ impl Name {
     /// assert_eq!(bytes_name, name);
     ///
     /// let bytes_name = Name::from_labels(vec!["bad.char".as_bytes(), "example".as_bytes(), "com".as_bytes()]).unwrap();
-    /// let name = Name::from_ascii("bad\\056char.example.com.").unwrap();
+    /// let name = Name::from_ascii("bad\\046char.example.com.").unwrap();
     ///
     /// assert_eq!(bytes_name, name);
     /// ```
@@ -504,7 +568,8 @@ impl Name {
 
     fn from_encoded_str<E: LabelEnc>(local: &str, origin: Option<&Self>) -> ProtoResult<Self> {
         let mut name = Name::new();
-        let mut label = String::new();
+        let mut label: Vec<u8> = Vec::new();
+        let mut escaped = false;
 
         let mut state = ParseState::Label;
 
@@ -514,68 +579,87 @@ impl Name {
             return Ok(name);
         }
 
+        // escaped labels are taken as the raw octets, as described in RFC 1035 section 5.1
+        let to_label = |label: &[u8], escaped: bool| -> ProtoResult<Label> {
+            if escaped {
+                Label::from_raw_bytes(label)
+            } else {
+                E::to_label(std::str::from_utf8(label).expect("label is built from chars"))
+            }
+        };
+
         // TODO: it would be nice to relocate this to Label, but that is hard because the label boundary can only be detected after processing escapes...
         // evaluate all characters
         for ch in local.chars() {
             match state {
                 ParseState::Label => match ch {
                     '.' => {
-                        name.labels.push(E::to_label(&label)?);
+                        name.labels.push(to_label(&label, escaped)?);
                         label.clear();
+                        escaped = false;
+                    }
+                    '\\' => {
+                        escaped = true;
+                        state = ParseState::Escape1
+                    }
+                    ch if !ch.is_control() && !ch.is_whitespace() => {
+                        label.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes())
+                    }
+                    // whitespace is never part of a name, e.g. `example.com garbage` is an error
+                    _ => {
+                        return Err(
+                            format!("unrecognized char {:?} in name: {:?}", ch, local).into()
+                        )
                     }
-                    '\\' => state = ParseState::Escape1,
-                    ch if !ch.is_control() && !ch.is_whitespace() => label.push(ch),
-                    _ => return Err(format!("unrecognized char: {}", ch).into()),
                 },
                 ParseState::Escape1 => {
-                    if ch.is_numeric() {
-                        state =
-                            ParseState::Escape2(ch.to_digit(8).ok_or_else(|| {
-                                ProtoError::from(format!("illegal char: {}", ch))
-                            })?);
+                    if let Some(d) = ch.to_digit(10) {
+                        state = ParseState::Escape2(d);
                     } else {
                         // it's a single escaped char
-                        label.push(ch);
+                        label.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
                         state = ParseState::Label;
                     }
                 }
                 ParseState::Escape2(i) => {
-                    if ch.is_numeric() {
-                        state = ParseState::Escape3(
-                            i,
-                            ch.to_digit(8)
-                                .ok_or_else(|| ProtoError::from(format!("illegal char: {}", ch)))?,
-                        );
-                    } else {
-                        return Err(ProtoError::from(format!("unrecognized char: {}", ch)));
-                    }
+                    let ii = ch
+                        .to_digit(10)
+                        .ok_or_else(|| ProtoError::from(format!("unrecognized char: {}", ch)))?;
+                    state = ParseState::Escape3(i, ii);
                 }
                 ParseState::Escape3(i, ii) => {
-                    if ch.is_numeric() {
-                        // octal conversion
-                        let val: u32 = (i * 8 * 8)
-                            + (ii * 8)
-                            + ch.to_digit(8)
-                                .ok_or_else(|| ProtoError::from(format!("illegal char: {}", ch)))?;
-                        let new: char = char::from_u32(val)
-                            .ok_or_else(|| ProtoError::from(format!("illegal char: {}", ch)))?;
-                        label.push(new);
-                        state = ParseState::Label;
-                    } else {
-                        return Err(format!("unrecognized char: {}", ch).into());
+                    // decimal conversion, \DDD
+                    let iii = ch
+                        .to_digit(10)
+                        .ok_or_else(|| ProtoError::from(format!("unrecognized char: {}", ch)))?;
+                    let val: u32 = (i * 100) + (ii * 10) + iii;
+                    if val > u32::from(u8::max_value()) {
+                        return Err(format!("escaped value out of range: \\{:03}", val).into());
                     }
+
+                    label.push(val as u8);
+                    state = ParseState::Label;
                 }
             }
         }
 
+        if !matches!(state, ParseState::Label) {
+            return Err(format!("incomplete escape at end of name: {}", local).into());
+        }
+
+        // an escaped trailing dot, e.g. `a\.`, is part of the final label and does not make this an fqdn
+        let is_fqdn = label.is_empty() && local.ends_with('.');
+
         if !label.is_empty() {
-            name.labels.push(E::to_label(&label)?);
+            name.labels.push(to_label(&label, escaped)?);
         }
 
-        if local.ends_with('.') {
+        if is_fqdn {
             name.set_fqdn(true);
         } else if let Some(other) = origin {
-            return Ok(name.append_domain(other));
+            let mut name = name.append(other)?;
+            name.set_fqdn(true);
+            return Ok(name);
         }
 
         Ok(name)
@@ -703,6 +787,58 @@ impl Name {
         self.cmp_with_f::<CaseSensitive>(other) == Ordering::Equal
     }
 
+    /// Returns a copy of the name with the case of each ASCII letter chosen at random
+    ///
+    /// This is the 0x20 encoding of
+    ///  [draft-vixie-dnsext-dns0x20](https://tools.ietf.org/html/draft-vixie-dnsext-dns0x20-00).
+    ///  A query for the randomized name is answered with the name exactly as asked, so a spoofed
+    ///  response has to guess the case as well as the query id. Use `match_0x20` to check the
+    ///  name in the response.
+    ///
+    /// ```
+    /// use trust_dns_proto::rr::Name;
+    ///
+    /// let name = Name::from_ascii("www.example.com.").unwrap();
+    /// let randomized = name.randomize_case(&mut rand::thread_rng());
+    /// assert_eq!(randomized, name);
+    /// ```
+    pub fn randomize_case<R: Rng + ?Sized>(&self, rng: &mut R) -> Self {
+        let mut labels = Vec::with_capacity(self.labels.len());
+        for label in &self.labels {
+            let bytes: Vec<u8> = label
+                .as_bytes()
+                .iter()
+                .map(|b| {
+                    if rng.gen::<bool>() {
+                        b.to_ascii_uppercase()
+                    } else {
+                        b.to_ascii_lowercase()
+                    }
+                })
+                .collect();
+            labels.push(Label::from_raw_bytes(&bytes).expect("label length is unchanged"));
+        }
+
+        Name {
+            is_fqdn: self.is_fqdn,
+            labels,
+        }
+    }
+
+    /// Compares a name echoed in a response with this name, as sent in the query
+    ///
+    /// See `randomize_case`, a `CaseMatch::CaseMismatch` is the same name but not the same case, as
+    ///  may be the case in a spoofed response, or from a server that does not preserve case.
+    pub fn match_0x20(&self, response: &Self) -> CaseMatch {
+        if self.eq_case(response) {
+            CaseMatch::Exact
+        } else if self == response {
+            CaseMatch::CaseMismatch
+        } else {
+            CaseMatch::Different
+        }
+    }
+
     /// Converts this name into an ascii safe string.
     ///
     /// If the name is an IDNA name, then the name labels will be returned with the `xn--` prefix.
@@ -866,6 +1002,45 @@ impl Name {
 
         self
     }
+
+    /// True if this is a wildcard name, and `query` is a name it would be synthesized for
+    ///
+    /// [RFC 4592](https://tools.ietf.org/html/rfc4592#section-2.1.1), The Role of Wildcards in the Domain Name System, July 2006
+    ///
+    /// The `*` must be the left most label, and it stands in for one or more labels, so
+    ///  `*.example.com.` matches `www.example.com.` and `a.b.example.com.`, but not `example.com.`,
+    ///  nor `*.example.com.` itself.
+    ///  Labels are compared case-insensitively. This doesn't know which names exist in the zone,
+    ///  any name that exists at or above `query` blocks the match, see section 2.2.1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use trust_dns_proto::rr::Name;
+    ///
+    /// let wildcard = Name::from_str("*.example.com.").unwrap();
+    /// assert!(wildcard.matches_wildcard(&Name::from_str("www.example.com.").unwrap()));
+    /// assert!(wildcard.matches_wildcard(&Name::from_str("a.b.example.com.").unwrap()));
+    /// assert!(!wildcard.matches_wildcard(&Name::from_str("example.com.").unwrap()));
+    /// assert!(!wildcard.matches_wildcard(&Name::from_str("www.example.net.").unwrap()));
+    /// assert!(!wildcard.matches_wildcard(&wildcard));
+    /// ```
+    pub fn matches_wildcard(&self, query: &Self) -> bool {
+        // a query for the wildcard's own name matches it as an existing name, section 2.2.1
+        if !self.is_wildcard() || self == query {
+            return false;
+        }
+
+        // the labels below the `*` must all be at the end of query, with at least one more before them
+        let parent = &self.labels[1..];
+        query.labels.len() > parent.len()
+            && parent
+                .iter()
+                .rev()
+                .zip(query.labels.iter().rev())
+                .all(|(parent_label, query_label)| parent_label == query_label)
+    }
 }
 
 trait LabelEnc {
@@ -904,6 +1079,10 @@ impl<'a> Iterator for LabelIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.next().map(Borrow::borrow)
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
 impl<'a> ExactSizeIterator for LabelIter<'a> {}
@@ -913,6 +1092,17 @@ impl<'a> DoubleEndedIterator for LabelIter<'a> {
     }
 }
 
+/// The result of `Name::match_0x20`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaseMatch {
+    /// The names are identical, including case
+    Exact,
+    /// The names are equal ignoring case, but the case is not the same
+    CaseMismatch,
+    /// The names are not equal
+    Different,
+}
+
 impl<'a> IntoIterator for &'a Name {
     type Item = &'a [u8];
     type IntoIter = LabelIter<'a>;
@@ -1010,7 +1200,7 @@ impl PartialEq<Name> for Name {
 
 impl Hash for Name {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.is_fqdn.hash(state);
+        // is_fqdn is not hashed, PartialEq does not consider it, e.g. `example.com` == `example.com.`
 
         // this needs to be CaseInsensitive like PartialEq
         for l in self.labels.iter().map(Label::to_lowercase) {
@@ -1039,11 +1229,21 @@ impl<'r> BinDecodable<'r> for Name {
     ///  all names will be stored lowercase internally.
     /// This will consume the portions of the `Vec` which it is reading...
     fn read(decoder: &mut BinDecoder<'r>) -> ProtoResult<Name> {
-        read_inner(decoder, None)
+        read_inner(decoder, None, 0)
     }
 }
 
-fn read_inner<'r>(decoder: &mut BinDecoder<'r>, max_idx: Option<usize>) -> ProtoResult<Name> {
+/// The maximum number of compression pointers that will be followed while reading a single name
+///
+/// Every pointer must already point backwards, so this only bounds the recursion depth of long
+///  (but acyclic) pointer chains, a valid name never needs more pointers than it has labels.
+const MAX_POINTER_JUMPS: usize = 128;
+
+fn read_inner<'r>(
+    decoder: &mut BinDecoder<'r>,
+    max_idx: Option<usize>,
+    jumps: usize,
+) -> ProtoResult<Name> {
     let mut state: LabelParseState = LabelParseState::LabelLengthOrPointer;
     let mut labels: Vec<Label> = Vec::with_capacity(3); // most labels will be around three, e.g. www.example.com
     let name_start = decoder.index();
@@ -1138,8 +1338,12 @@ fn read_inner<'r>(decoder: &mut BinDecoder<'r>, max_idx: Option<usize>) -> Proto
                         })
                     })?;
 
+                if jumps >= MAX_POINTER_JUMPS {
+                    return Err(ProtoErrorKind::PointerJumpsExceeded(jumps + 1).into());
+                }
+
                 let mut pointer = decoder.clone(location);
-                let pointed = read_inner(&mut pointer, Some(name_start))?;
+                let pointed = read_inner(&mut pointer, Some(name_start), jumps + 1)?;
 
                 for l in &*pointed.labels {
                     if !l.is_empty() {
@@ -1338,6 +1542,25 @@ mod tests {
         assert_eq!(Name::from_str("a.b.c").unwrap().num_labels(), 3);
     }
 
+    #[test]
+    fn test_label_iter() {
+        let name = Name::from_str("www.example.com.").unwrap();
+        let mut iter = name.iter();
+
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next(), Some(b"www" as &[u8]));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(b"com" as &[u8]));
+        assert_eq!(iter.next(), Some(b"example" as &[u8]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(name.num_labels(), 3);
+
+        let root = Name::root();
+        assert_eq!(root.iter().len(), 0);
+        assert_eq!(root.iter().next(), None);
+        assert_eq!(root.num_labels(), 0);
+    }
+
     #[test]
     fn test_read() {
         test_read_data_set(get_data(), |ref mut d| Name::read(d));
@@ -1454,6 +1677,41 @@ mod tests {
         assert!(Name::read(&mut d).is_err());
     }
 
+    #[test]
+    fn test_pointer_jumps_bounded() {
+        // a self-referential pointer is rejected rather than followed
+        let bytes = vec![0x01, 0x41, 0xC0, 0x02];
+        let mut d = BinDecoder::new(&bytes);
+        d.read_slice(2).unwrap();
+
+        match Name::read(&mut d).unwrap_err().kind() {
+            ProtoErrorKind::PointerNotPriorToLabel { idx: 2, ptr: 2 } => (),
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        // a chain of pointers, each to the one before it, all pointing backwards
+        let mut bytes = vec![0x00];
+        for i in 0..=(MAX_POINTER_JUMPS as u16) {
+            let ptr = if i == 0 { 0 } else { 1 + (i - 1) * 2 };
+            bytes.extend_from_slice(&(0xC000 | ptr).to_be_bytes());
+        }
+
+        // the last pointer in the chain has too many jumps to the root
+        let mut d = BinDecoder::new(&bytes);
+        d.read_slice(bytes.len() - 2).unwrap();
+        match Name::read(&mut d).unwrap_err().kind() {
+            ProtoErrorKind::PointerJumpsExceeded(jumps) => {
+                assert_eq!(*jumps, MAX_POINTER_JUMPS + 1)
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        // the one just prior is within the limit
+        let mut d = BinDecoder::new(&bytes);
+        d.read_slice(bytes.len() - 4).unwrap();
+        assert!(Name::read(&mut d).unwrap().is_root());
+    }
+
     #[test]
     fn test_bin_overlap_enforced() {
         let mut bytes: Vec<u8> = Vec::with_capacity(512);
@@ -1486,6 +1744,54 @@ mod tests {
         assert!(Name::read(&mut d).is_err());
     }
 
+    #[test]
+    fn test_label_too_long() {
+        let max = "a".repeat(63);
+        let too_long = "a".repeat(64);
+
+        assert!(Name::parse(&format!("{}.example.com.", max), None).is_ok());
+        assert!(Name::parse(&format!("{}.example.com.", too_long), None).is_err());
+        assert!(Name::from_ascii(format!("{}.example.com.", too_long)).is_err());
+        assert!(Name::from_str(&format!("www.{}.com.", too_long)).is_err());
+        assert!(Name::root().append_label(too_long.as_bytes()).is_err());
+
+        // a length byte over 63 is not a valid label on the wire
+        let mut bytes = vec![64];
+        bytes.extend(iter::repeat(b'a').take(64));
+        bytes.push(0);
+        let mut d = BinDecoder::new(&bytes);
+        assert!(Name::read(&mut d).is_err());
+    }
+
+    #[test]
+    fn test_append_and_prepend() {
+        let origin = Name::from_str("example.com.").unwrap();
+        let relative = Name::from_str("www").unwrap();
+        assert!(!relative.is_fqdn());
+
+        let name = relative.append_domain(&origin);
+        assert!(name.is_fqdn());
+        assert_eq!(name.to_string(), "www.example.com.");
+
+        let srv = origin.clone().prepend_label("_tcp").unwrap();
+        let srv = srv.prepend_label("_ldap").unwrap();
+        assert!(srv.is_fqdn());
+        assert_eq!(srv.to_string(), "_ldap._tcp.example.com.");
+
+        // four 63 byte labels are 256 octets with the dots, over the limit
+        let long_label = "a".repeat(63);
+        let name = Name::root()
+            .append_label(long_label.as_str())
+            .unwrap()
+            .append_label(long_label.as_str())
+            .unwrap()
+            .append_label(long_label.as_str())
+            .unwrap();
+        assert!(name.clone().append_label(long_label.as_str()).is_err());
+        assert!(name.clone().prepend_label(long_label.as_str()).is_err());
+        assert!(name.prepend_label("b").is_ok());
+    }
+
     #[test]
     fn test_base_name() {
         let zone = Name::from_str("example.com.").unwrap();
@@ -1507,6 +1813,13 @@ mod tests {
         assert!(!zone.zone_of(&none));
         assert!(root.zone_of(&zone));
         assert!(!zone.zone_of(&root));
+
+        // only whole labels match
+        let org = Name::from_str("example.org").unwrap();
+        let sub = Name::from_str("www.notexample.com").unwrap();
+        assert!(!zone.zone_of(&org));
+        assert!(!zone.zone_of(&sub));
+        assert!(!www.zone_of(&zone));
     }
 
     #[test]
@@ -1517,7 +1830,11 @@ mod tests {
 
         assert!(zone.zone_of(&zone));
         assert!(zone.zone_of(&www));
-        assert!(!zone.zone_of(&none))
+        assert!(!zone.zone_of(&none));
+
+        assert!(zone.zone_of_case(&zone));
+        assert!(!zone.zone_of_case(&www));
+        assert!(Name::from_str("example.com").unwrap().zone_of_case(&www));
     }
 
     #[test]
@@ -1599,6 +1916,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hash_ignore_case() {
+        use std::collections::HashSet;
+
+        let mixed = Name::from_ascii("WWW.Example.COM.").unwrap();
+        let lower = Name::from_ascii("www.example.com.").unwrap();
+        let relative = Name::from_ascii("www.example.com").unwrap();
+
+        assert_eq!(mixed, lower);
+        assert!(!mixed.eq_case(&lower));
+        assert!(mixed.to_lowercase().eq_case(&lower));
+        assert_eq!(mixed, relative);
+
+        let mut names = HashSet::new();
+        assert!(names.insert(mixed));
+        assert!(!names.insert(lower.clone()));
+        assert!(!names.insert(relative));
+        assert!(names.contains(&Name::from_ascii("www.EXAMPLE.com").unwrap()));
+        assert_eq!(names.len(), 1);
+
+        // the original case is preserved in the set
+        assert_eq!(names.iter().next().unwrap().to_ascii(), "WWW.Example.COM.");
+        assert!(!names.contains(&Name::from_ascii("example.com.").unwrap()));
+    }
+
     #[test]
     fn test_from_ipv4() {
         let ip = IpAddr::V4(Ipv4Addr::new(26, 3, 0, 103));
@@ -1630,6 +1972,94 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_randomize_case() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let name = Name::from_ascii("_sip._tcp.www-1.example.com.").unwrap();
+
+        let mut any_changed = false;
+        for seed in 0..8 {
+            let randomized = name.randomize_case(&mut StdRng::seed_from_u64(seed));
+            assert_eq!(randomized, name);
+            assert!(randomized.is_fqdn());
+            assert_eq!(randomized.to_lowercase().to_ascii(), name.to_ascii());
+            any_changed |= !randomized.eq_case(&name);
+
+            // the same generator gives the same case
+            let again = name.randomize_case(&mut StdRng::seed_from_u64(seed));
+            assert!(again.eq_case(&randomized));
+
+            assert_eq!(randomized.match_0x20(&randomized.clone()), CaseMatch::Exact);
+            assert_eq!(
+                randomized.match_0x20(&randomized.to_lowercase()),
+                if randomized.eq_case(&randomized.to_lowercase()) {
+                    CaseMatch::Exact
+                } else {
+                    CaseMatch::CaseMismatch
+                }
+            );
+        }
+        assert!(any_changed);
+
+        let randomized = name.randomize_case(&mut StdRng::seed_from_u64(0));
+        assert_eq!(
+            randomized.match_0x20(&Name::from_ascii("_sip._udp.www-1.example.com.").unwrap()),
+            CaseMatch::Different
+        );
+        assert!(Name::root()
+            .randomize_case(&mut StdRng::seed_from_u64(0))
+            .is_root());
+    }
+
+    #[test]
+    fn test_emit_preserves_case() {
+        let name = Name::from_ascii("www.ExAmPlE.com.").unwrap();
+
+        // emit never changes the case
+        for canonical in &[false, true] {
+            let mut bytes = Vec::new();
+            let mut encoder = BinEncoder::new(&mut bytes);
+            encoder.set_canonical_names(*canonical);
+            name.emit(&mut encoder).unwrap();
+            assert_eq!(bytes, b"\x03www\x07ExAmPlE\x03com\x00");
+        }
+
+        // emit_with_lowercase only lowercases when asked, as the rdata emitters do for canonical form
+        let mut bytes = Vec::new();
+        name.emit_with_lowercase(&mut BinEncoder::new(&mut bytes), false)
+            .unwrap();
+        assert_eq!(bytes, b"\x03www\x07ExAmPlE\x03com\x00");
+
+        let mut bytes = Vec::new();
+        name.emit_with_lowercase(&mut BinEncoder::new(&mut bytes), true)
+            .unwrap();
+        assert_eq!(bytes, b"\x03www\x07example\x03com\x00");
+    }
+
+    #[test]
+    fn test_from_labels_raw_bytes() {
+        // not valid UTF-8, kept exactly as given
+        let name = Name::from_labels(vec![&[0xff, 0x00, b'A'] as &[u8], b"example"]).unwrap();
+        assert_eq!(name.num_labels(), 2);
+        assert_eq!(name[0].as_bytes(), &[0xff, 0x00, b'A']);
+        assert_eq!(name[1].as_bytes(), b"example");
+        assert!(name.is_fqdn());
+
+        let name = Name::from_labels(vec![vec![0x80_u8, 0x81], b"com".to_vec()]).unwrap();
+        assert_eq!(name[0].as_bytes(), &[0x80, 0x81]);
+
+        // at most 63 bytes in a label
+        assert!(Name::from_labels(vec![&[b'a'; 63] as &[u8]]).is_ok());
+        assert!(Name::from_labels(vec![&[b'a'; 64] as &[u8]]).is_err());
+
+        // and 255 in the name
+        let label = [b'a'; 63];
+        assert!(Name::from_labels(vec![&label as &[u8]; 3]).is_ok());
+        assert!(Name::from_labels(vec![&label as &[u8]; 5]).is_err());
+    }
+
     #[test]
     fn test_fqdn() {
         assert!(Name::root().is_fqdn());
@@ -1643,6 +2073,49 @@ mod tests {
         assert!(!Name::from_str("www.example.com").unwrap().is_fqdn());
         assert!(!Name::from_str("www.example").unwrap().is_fqdn());
         assert!(!Name::from_str("www").unwrap().is_fqdn());
+
+        // escaped trailing dots are part of the label
+        let name = Name::from_ascii("www\\.").unwrap();
+        assert!(!name.is_fqdn());
+        assert_eq!(name.num_labels(), 1);
+        assert_eq!(name[0].as_bytes(), b"www.");
+
+        let name = Name::from_ascii("www\\..").unwrap();
+        assert!(name.is_fqdn());
+        assert_eq!(name[0].as_bytes(), b"www.");
+    }
+
+    #[test]
+    fn test_root() {
+        assert!(Name::root().is_root());
+        assert!(Name::from_str(".").unwrap().is_root());
+        assert!(Name::from_labels(Vec::<&str>::new()).unwrap().is_root());
+
+        assert!(!Name::new().is_root());
+        assert!(!Name::from_str("com.").unwrap().is_root());
+        assert!(!Name::from_str("com").unwrap().is_root());
+    }
+
+    #[test]
+    fn test_parse_with_origin() {
+        let origin = Name::from_str("example.com.").unwrap();
+
+        let relative = Name::parse("www", Some(&origin)).unwrap();
+        assert!(relative.is_fqdn());
+        assert_eq!(relative, Name::from_str("www.example.com.").unwrap());
+
+        // fqdns ignore the origin
+        let fqdn = Name::parse("www.example.net.", Some(&origin)).unwrap();
+        assert_eq!(fqdn, Name::from_str("www.example.net.").unwrap());
+
+        // the origin may not push the name past 255 octets, this leaves room for a 50 byte label
+        let long_label = "a".repeat(63);
+        let local = format!("{0}.{0}.{0}.{1}", long_label, "b".repeat(50));
+        let name = Name::parse(&local, Some(&origin)).unwrap();
+        assert_eq!(name.len(), 255);
+        let local = format!("{0}.{0}.{0}.{1}", long_label, "b".repeat(51));
+        assert!(Name::parse(&local, Some(&origin)).is_err());
+        assert!(Name::parse(&format!("{}.", local), Some(&origin)).is_ok());
     }
 
     #[test]
@@ -1657,6 +2130,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_trailing_garbage() {
+        let origin = Name::from_ascii("example.").unwrap();
+
+        for input in &[
+            "example.com garbage",
+            "example.com. garbage",
+            "example.com ",
+            " example.com",
+            "example.com\t",
+            "example.com\n",
+            "www\x00.example.com",
+        ] {
+            assert!(Name::parse(input, None).is_err(), "{:?}", input);
+            assert!(Name::parse(input, Some(&origin)).is_err(), "{:?}", input);
+            assert!(Name::from_ascii(input).is_err(), "{:?}", input);
+            assert!(Name::from_str(input).is_err(), "{:?}", input);
+        }
+
+        // spaces may only be in a label when escaped
+        let name = Name::parse("a\\ b.example.com.", None).unwrap();
+        assert_eq!(name[0].as_bytes(), b"a b");
+
+        let error = Name::parse("example.com garbage", None).unwrap_err();
+        assert!(error.to_string().contains("' '"), "{}", error);
+    }
+
     #[test]
     fn test_from_ascii() {
         let bytes_name = Name::from_labels(vec![b"WWW" as &[u8], b"example", b"COM"]).unwrap();
@@ -1667,6 +2167,100 @@ mod tests {
         assert!(!lower_name.eq_case(&ascii_name));
     }
 
+    #[test]
+    fn test_escapes() {
+        // an escaped dot is part of the label
+        let name = Name::from_str("a\\.b.example.com.").unwrap();
+        assert_eq!(name.num_labels(), 3);
+        assert_eq!(name[0].as_bytes(), b"a.b");
+        assert_eq!(name.to_string(), "a\\.b.example.com.");
+
+        // decimal escapes
+        let name = Name::from_ascii("a\\092b.example.com.").unwrap();
+        assert_eq!(name[0].as_bytes(), b"a\\b");
+        assert_eq!(name.to_ascii(), "a\\\\b.example.com.");
+        assert_eq!(Name::from_ascii(name.to_ascii()).unwrap(), name);
+
+        let name = Name::from_ascii("\\000\\255.example.com.").unwrap();
+        assert_eq!(name[0].as_bytes(), &[0, 255]);
+        assert_eq!(name.to_ascii(), "\\000\\255.example.com.");
+
+        // out of range, or incomplete, escapes are rejected
+        assert!(Name::from_ascii("a\\256.example.com.").is_err());
+        assert!(Name::from_ascii("a\\09x.example.com.").is_err());
+        assert!(Name::from_ascii("a\\09").is_err());
+        assert!(Name::from_ascii("a\\").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        // labels that can only be written with escapes
+        for label in &[&b"a.b"[..], b"a\\b", b"a b", b"\x07", b".", b"xn--a.b"] {
+            let name = Name::from_labels(vec![*label, b"example", b"com"]).unwrap();
+            let displayed = name.to_string();
+            assert!(
+                displayed.ends_with(".example.com."),
+                "label not escaped: {}",
+                displayed
+            );
+
+            let parsed = Name::from_str(&displayed).expect("failed to parse displayed name");
+            assert!(parsed.eq_case(&name), "{} != {:?}", displayed, name);
+        }
+
+        let name = Name::from_labels(vec![&b"a.b"[..], b"example", b"com"]).unwrap();
+        assert_eq!(name.num_labels(), 3);
+        assert_eq!(name.to_string(), "a\\.b.example.com.");
+    }
+
+    #[test]
+    fn test_matches_wildcard() {
+        let wildcard = Name::from_str("*.example.com.").unwrap();
+
+        // one or more labels in place of the `*`
+        for query in &["www.example.com.", "WWW.Example.COM.", "a.b.example.com."] {
+            assert!(
+                wildcard.matches_wildcard(&Name::from_str(query).unwrap()),
+                "{} should match",
+                query
+            );
+        }
+
+        for query in &[
+            // the name the wildcard is at, and its parent
+            "example.com.",
+            "com.",
+            ".",
+            "www.example.net.",
+            "www.sub.example.org.",
+            // the wildcard itself, in any case, is not synthesized from the wildcard
+            "*.example.com.",
+            "*.EXAMPLE.com.",
+        ] {
+            assert!(
+                !wildcard.matches_wildcard(&Name::from_str(query).unwrap()),
+                "{} should not match",
+                query
+            );
+        }
+
+        // only a `*` as the left most label is a wildcard
+        let not_wildcard = Name::from_str("www.*.example.com.").unwrap();
+        assert!(!not_wildcard.matches_wildcard(&Name::from_str("www.a.example.com.").unwrap()));
+        let not_wildcard = Name::from_str("www.example.com.").unwrap();
+        assert!(!not_wildcard.matches_wildcard(&not_wildcard));
+
+        // a `*` deeper in the wildcard's name is compared literally
+        let wildcard = Name::from_str("*.*.example.com.").unwrap();
+        assert!(wildcard.matches_wildcard(&Name::from_str("www.*.example.com.").unwrap()));
+        assert!(!wildcard.matches_wildcard(&Name::from_str("www.a.example.com.").unwrap()));
+
+        // the root wildcard matches every other name
+        let wildcard = Name::from_str("*.").unwrap();
+        assert!(wildcard.matches_wildcard(&Name::from_str("com.").unwrap()));
+        assert!(!wildcard.matches_wildcard(&Name::root()));
+    }
+
     #[test]
     fn test_from_utf8() {
         let bytes_name = Name::from_labels(vec![b"WWW" as &[u8], b"example", b"COM"]).unwrap();
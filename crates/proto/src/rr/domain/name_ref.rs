@@ -0,0 +1,313 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! domain names read without copying their labels out of the message
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error::*;
+use crate::rr::domain::Name;
+use crate::serialize::binary::*;
+
+/// A domain name borrowed from the buffer it was read from, in its uncompressed wire form
+///
+/// Reading a `Name` allocates every label, for names read on a hot path where only comparison or
+///  inspection is needed, use `NameRef::read` and only convert with `to_name` when the name is kept.
+#[derive(Clone, Copy)]
+pub struct BorrowedName<'r> {
+    // the labels, each preceded by its length, and the terminating root label
+    wire: &'r [u8],
+}
+
+impl<'r> BorrowedName<'r> {
+    /// An iterator over the labels, from the left most label to the right most
+    pub fn iter(&self) -> BorrowedLabelIter<'r> {
+        BorrowedLabelIter(self.wire)
+    }
+
+    /// Returns the number of labels in the name, the root is not counted
+    pub fn num_labels(&self) -> u8 {
+        self.iter().count() as u8
+    }
+
+    /// Returns true if this is the root name, `.`
+    pub fn is_root(&self) -> bool {
+        self.wire == [0]
+    }
+
+    /// The uncompressed wire form of the name, exactly as it was in the buffer
+    pub fn as_bytes(&self) -> &'r [u8] {
+        self.wire
+    }
+
+    /// Copies the labels into an owned `Name`
+    pub fn to_name(&self) -> Name {
+        Name::from_labels(self.iter()).expect("labels were validated when read")
+    }
+}
+
+impl<'r> PartialEq<Name> for BorrowedName<'r> {
+    /// Case insensitive, like `Name`
+    fn eq(&self, other: &Name) -> bool {
+        self.iter().count() == other.iter().len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(l, r)| l.eq_ignore_ascii_case(r))
+    }
+}
+
+impl<'r> PartialEq for BorrowedName<'r> {
+    /// Case insensitive, like `Name`
+    fn eq(&self, other: &Self) -> bool {
+        self.wire.eq_ignore_ascii_case(other.wire)
+    }
+}
+
+impl<'r> From<BorrowedName<'r>> for Name {
+    fn from(name: BorrowedName<'r>) -> Self {
+        name.to_name()
+    }
+}
+
+impl<'r> fmt::Debug for BorrowedName<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BorrowedName({})", self)
+    }
+}
+
+impl<'r> fmt::Display for BorrowedName<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_name(), f)
+    }
+}
+
+/// An iterator over the labels of a `BorrowedName`
+pub struct BorrowedLabelIter<'r>(&'r [u8]);
+
+impl<'r> Iterator for BorrowedLabelIter<'r> {
+    type Item = &'r [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the wire form was validated when read, every label is complete and it ends with the root
+        let (&len, rest) = self.0.split_first()?;
+        if len == 0 {
+            return None;
+        }
+
+        let (label, rest) = rest.split_at(len as usize);
+        self.0 = rest;
+        Some(label)
+    }
+}
+
+/// A name read from a message, borrowing the labels when the name isn't compressed
+#[derive(Clone, Debug, PartialEq)]
+pub enum NameRef<'r> {
+    /// The name was entirely in place, its labels are borrowed from the buffer
+    Borrowed(BorrowedName<'r>),
+    /// The name ended in a compression pointer, so its labels had to be copied together
+    Owned(Name),
+}
+
+impl<'r> NameRef<'r> {
+    /// Copies the name into an owned `Name`, if it isn't one already
+    pub fn to_name(&self) -> Name {
+        match self {
+            NameRef::Borrowed(name) => name.to_name(),
+            NameRef::Owned(name) => name.clone(),
+        }
+    }
+
+    /// Converts into an owned `Name`, only copying if the labels are borrowed
+    pub fn into_name(self) -> Name {
+        match self {
+            NameRef::Borrowed(name) => name.to_name(),
+            NameRef::Owned(name) => name,
+        }
+    }
+
+    /// Returns the number of labels in the name, the root is not counted
+    pub fn num_labels(&self) -> u8 {
+        match self {
+            NameRef::Borrowed(name) => name.num_labels(),
+            NameRef::Owned(name) => name.num_labels(),
+        }
+    }
+}
+
+impl<'r> PartialEq<Name> for NameRef<'r> {
+    fn eq(&self, other: &Name) -> bool {
+        match self {
+            NameRef::Borrowed(name) => name == other,
+            NameRef::Owned(name) => name == other,
+        }
+    }
+}
+
+impl<'r> fmt::Display for NameRef<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameRef::Borrowed(name) => fmt::Display::fmt(name, f),
+            NameRef::Owned(name) => fmt::Display::fmt(name, f),
+        }
+    }
+}
+
+impl<'r> BinDecodable<'r> for NameRef<'r> {
+    /// Reads a name with the same validation as `Name::read`
+    ///
+    /// Labels are borrowed from the decoder's buffer until a compression pointer is found, in
+    ///  which case the name is read again from the start as an owned `Name`.
+    fn read(decoder: &mut BinDecoder<'r>) -> ProtoResult<Self> {
+        let start = decoder.index();
+
+        loop {
+            // enforce max length of name, the same as Name::read
+            let cur_len = decoder.index() - start;
+            if cur_len > 255 {
+                return Err(ProtoErrorKind::DomainNameTooLong(cur_len).into());
+            }
+
+            match decoder
+                .peek()
+                .map(Restrict::unverified /*verified in this usage*/)
+            {
+                Some(0) | None => {
+                    decoder.pop()?;
+                    let wire = decoder.slice_from(start)?;
+                    return Ok(NameRef::Borrowed(BorrowedName { wire }));
+                }
+                Some(byte) if byte & 0b1100_0000 == 0b1100_0000 => {
+                    // the name continues elsewhere, start over and collect the labels
+                    let start = u16::try_from(start)
+                        .map_err(|_| ProtoError::from("name is beyond the maximum message size"))?;
+                    let mut rewound = decoder.clone(start);
+                    let name = Name::read(&mut rewound)?;
+
+                    // move past the pointer, which ends the name
+                    decoder.read_u16()?;
+                    return Ok(NameRef::Owned(name));
+                }
+                Some(byte) if byte & 0b1100_0000 == 0b0000_0000 => {
+                    decoder
+                        .read_character_data_max(Some(63))?
+                        .verify_unwrap(|l| l.len() <= 63)
+                        .map_err(|_| ProtoError::from("label exceeds maximum length of 63"))?;
+                }
+                Some(byte) => return Err(ProtoErrorKind::UnrecognizedLabelCode(byte).into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn read_both(bytes: &[u8], at: u16) -> (NameRef<'_>, Name) {
+        let mut decoder = BinDecoder::new(bytes).clone(at);
+        let name_ref = NameRef::read(&mut decoder).expect("failed to read NameRef");
+        let ref_end = decoder.index();
+
+        let mut decoder = BinDecoder::new(bytes).clone(at);
+        let name = Name::read(&mut decoder).expect("failed to read Name");
+        assert_eq!(
+            ref_end,
+            decoder.index(),
+            "both reads consume the same bytes"
+        );
+
+        (name_ref, name)
+    }
+
+    #[test]
+    fn test_borrowed_equals_owned() {
+        let bytes = b"\x03WWW\x07example\x03com\x00";
+        let (name_ref, name) = read_both(bytes, 0);
+
+        match name_ref {
+            NameRef::Borrowed(borrowed) => {
+                assert_eq!(borrowed.as_bytes(), &bytes[..]);
+                assert_eq!(borrowed.num_labels(), 3);
+                assert_eq!(
+                    borrowed.iter().collect::<Vec<_>>(),
+                    vec![&b"WWW"[..], b"example", b"com"]
+                );
+                assert_eq!(borrowed, name);
+                assert!(borrowed.to_name().eq_case(&name));
+                assert_eq!(borrowed.to_string(), "WWW.example.com.");
+            }
+            NameRef::Owned(_) => panic!("an uncompressed name should be borrowed"),
+        }
+
+        assert_eq!(name_ref, Name::from_str("www.example.com.").unwrap());
+        assert_ne!(name_ref, Name::from_str("www.example.net.").unwrap());
+        assert_ne!(name_ref, Name::from_str("example.com.").unwrap());
+    }
+
+    #[test]
+    fn test_root() {
+        let (name_ref, name) = read_both(b"\x00", 0);
+        assert!(name.is_root());
+        match name_ref {
+            NameRef::Borrowed(borrowed) => {
+                assert!(borrowed.is_root());
+                assert_eq!(borrowed.num_labels(), 0);
+                assert_eq!(borrowed, name);
+            }
+            NameRef::Owned(_) => panic!("the root should be borrowed"),
+        }
+    }
+
+    #[test]
+    fn test_compressed_is_owned() {
+        // www.example.com. then mail.<pointer to example.com.>
+        let bytes = b"\x03www\x07example\x03com\x00\x04mail\xC0\x04";
+        let (name_ref, name) = read_both(bytes, 17);
+
+        match &name_ref {
+            NameRef::Owned(owned) => assert!(owned.eq_case(&name)),
+            NameRef::Borrowed(_) => panic!("a compressed name can't be borrowed"),
+        }
+        assert_eq!(
+            name_ref.into_name(),
+            Name::from_str("mail.example.com.").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_errors() {
+        for bytes in &[
+            // truncated label
+            &b"\x03ww"[..],
+            // missing root
+            &b"\x03www"[..],
+            // reserved label types
+            &b"\x40"[..],
+            &b"\x80"[..],
+            // pointer to itself
+            &b"\xC0\x00"[..],
+        ] {
+            assert!(NameRef::read(&mut BinDecoder::new(bytes)).is_err());
+            assert!(Name::read(&mut BinDecoder::new(bytes)).is_err());
+        }
+
+        // too long, 5 labels of 63
+        let mut bytes = Vec::new();
+        for _ in 0..5 {
+            bytes.push(63);
+            bytes.extend_from_slice(&[b'a'; 63]);
+        }
+        bytes.push(0);
+        assert!(NameRef::read(&mut BinDecoder::new(&bytes)).is_err());
+        assert!(Name::read(&mut BinDecoder::new(&bytes)).is_err());
+    }
+}
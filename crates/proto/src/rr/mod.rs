@@ -29,9 +29,9 @@ mod rr_set;
 
 pub use self::dns_class::DNSClass;
 pub use self::domain::{IntoName, Name, TryParseIp};
-pub use self::record_data::RData;
+pub use self::record_data::{RData, RDataType};
 pub use self::record_type::RecordType;
-pub use self::resource::Record;
+pub use self::resource::{records_of_type, Record};
 #[allow(deprecated)]
 pub use self::rr_set::IntoRecordSet;
 pub use self::rr_set::RecordSet;
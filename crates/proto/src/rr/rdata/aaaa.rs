@@ -121,6 +121,12 @@ mod tests {
                 Ipv6Addr::from_str("FF00::192.168.64.32").unwrap(),
                 vec![255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 192, 168, 64, 32],
             ),
+            (
+                Ipv6Addr::from_str("2001:0db8:0000:0000:0000:ff00:0042:8329").unwrap(),
+                vec![
+                    0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0xff, 0, 0, 0x42, 0x83, 0x29,
+                ],
+            ),
         ]
     }
 
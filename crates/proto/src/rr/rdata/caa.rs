@@ -834,17 +834,19 @@ impl fmt::Display for Value {
         f.write_str("\"")?;
 
         match self {
-            Value::Issuer(_, values) => {
-                if let Some(value) = values.first() {
-                    write!(f, "{}", value)?;
+            Value::Issuer(name, values) => {
+                match name {
+                    Some(name) => write!(f, "{}", name)?,
+                    None if values.is_empty() => f.write_str(";")?,
+                    None => (),
                 }
 
-                for value in &values[1..] {
+                for value in values {
                     write!(f, "; {}", value)?;
                 }
             }
             Value::Url(url) => write!(f, "{}", url)?,
-            Value::Unknown(v) => write!(f, "{:?}", v)?,
+            Value::Unknown(v) => write!(f, "{}", String::from_utf8_lossy(v))?,
         }
 
         f.write_str("\"")
@@ -862,15 +864,15 @@ impl fmt::Display for KeyValue {
     }
 }
 
-// FIXME: this needs to be verified to be correct, add tests...
+/// Formats the CAA record in the presentation format, `<flags> <tag> "<value>"`
 impl fmt::Display for CAA {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        let critical = if self.issuer_critical { "1" } else { "0" };
+        let flags = if self.issuer_critical { 0b1000_0000 } else { 0 };
 
         write!(
             f,
-            "{critical} {tag} {value}",
-            critical = critical,
+            "{flags} {tag} {value}",
+            flags = flags,
             tag = self.tag,
             value = self.value
         )
@@ -1064,6 +1066,36 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            CAA::new_issue(
+                false,
+                Some(Name::parse("letsencrypt.org", None).unwrap()),
+                vec![]
+            )
+            .to_string(),
+            "0 issue \"letsencrypt.org\""
+        );
+        assert_eq!(
+            CAA::new_issue(
+                true,
+                Some(Name::parse("ca.example.net", None).unwrap()),
+                vec![KeyValue::new("account", "230123")]
+            )
+            .to_string(),
+            "128 issue \"ca.example.net; account=230123\""
+        );
+        assert_eq!(
+            CAA::new_issuewild(false, None, vec![]).to_string(),
+            "0 issuewild \";\""
+        );
+        assert_eq!(
+            CAA::new_iodef(false, Url::parse("mailto:security@example.com").unwrap()).to_string(),
+            "0 iodef \"mailto:security@example.com\""
+        );
+    }
+
     fn test_encode(rdata: CAA, encoded: &[u8]) {
         let mut bytes = Vec::new();
         let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
@@ -0,0 +1,117 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! host information records for describing the CPU and OS of a host
+use std::fmt;
+
+use super::txt::fmt_character_string;
+use crate::error::*;
+use crate::serialize::binary::*;
+
+/// [RFC 1035, DOMAIN NAMES - IMPLEMENTATION AND SPECIFICATION, November 1987](https://tools.ietf.org/html/rfc1035#section-3.3.2)
+///
+/// ```text
+/// 3.3.2. HINFO RDATA format
+///
+///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///     /                      CPU                      /
+///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///     /                       OS                      /
+///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///
+/// where:
+///
+/// CPU             A <character-string> which specifies the CPU type.
+///
+/// OS              A <character-string> which specifies the operating
+///                 system type.
+///
+/// Standard values for CPU and OS can be found in [RFC-1010].
+///
+/// HINFO records are used to acquire general information about a host.  The
+/// main use is for protocols such as FTP that can use special procedures
+/// when talking between machines or operating systems of the same type.
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct HINFO {
+    cpu: Box<[u8]>,
+    os: Box<[u8]>,
+}
+
+impl HINFO {
+    /// Creates a new HINFO record data.
+    ///
+    /// # Arguments
+    ///
+    /// * `cpu` - the CPU type, at most 255 bytes
+    /// * `os` - the operating system type, at most 255 bytes
+    pub fn new(cpu: Box<[u8]>, os: Box<[u8]>) -> Self {
+        HINFO { cpu, os }
+    }
+
+    /// The CPU type of the host
+    pub fn cpu(&self) -> &[u8] {
+        &self.cpu
+    }
+
+    /// The operating system type of the host
+    pub fn os(&self) -> &[u8] {
+        &self.os
+    }
+}
+
+/// Read the RData from the given Decoder
+pub fn read(decoder: &mut BinDecoder<'_>) -> ProtoResult<HINFO> {
+    Ok(HINFO::new(
+        decoder.read_character_data()?.unverified(/*any chardata*/).to_vec().into_boxed_slice(),
+        decoder.read_character_data()?.unverified(/*any chardata*/).to_vec().into_boxed_slice(),
+    ))
+}
+
+/// Write the RData from the given Decoder
+pub fn emit(encoder: &mut BinEncoder<'_>, hinfo: &HINFO) -> ProtoResult<()> {
+    encoder.emit_character_data(&hinfo.cpu)?;
+    encoder.emit_character_data(&hinfo.os)?;
+
+    Ok(())
+}
+
+/// Both fields are written as quoted `<character-string>`s, e.g. `"PDP-11/70" "UNIX"`
+impl fmt::Display for HINFO {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_character_string(f, &self.cpu)?;
+        f.write_str(" ")?;
+        fmt_character_string(f, &self.os)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::dbg_macro, clippy::print_stdout)]
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let rdata = HINFO::new(
+            b"Intel Core i7".to_vec().into_boxed_slice(),
+            b"Debian GNU/Linux".to_vec().into_boxed_slice(),
+        );
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, &rdata).is_ok());
+        let bytes = encoder.into_bytes();
+
+        println!("bytes: {:?}", bytes);
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let read_rdata = read(&mut decoder).expect("Decoding error");
+        assert_eq!(rdata, read_rdata);
+        assert_eq!(rdata.to_string(), r#""Intel Core i7" "Debian GNU/Linux""#);
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! location records for the geographic position of a host or network
+use std::fmt;
+
+use crate::error::*;
+use crate::serialize::binary::*;
+
+/// The encoded latitude of the equator, and longitude of the prime meridian
+pub const EQUATOR: u32 = 1 << 31;
+
+/// The encoded altitude of the WGS 84 reference spheroid, the base is 100,000m below it
+pub const REFERENCE_ALTITUDE: u32 = 10_000_000;
+
+/// The size used when none is given, 1m
+pub const DEFAULT_SIZE: u8 = 0x12;
+
+/// The horizontal precision used when none is given, 10,000m
+pub const DEFAULT_HORIZ_PRE: u8 = 0x16;
+
+/// The vertical precision used when none is given, 10m
+pub const DEFAULT_VERT_PRE: u8 = 0x13;
+
+// thousandths of a second of arc in a degree
+const ARC_DEGREE: i64 = 3_600_000;
+
+/// [RFC 1876, A Means for Expressing Location Information in the DNS, January 1996](https://tools.ietf.org/html/rfc1876#section-2)
+///
+/// ```text
+/// 2. RDATA Format
+///
+///        MSB                                           LSB
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///       0|        VERSION        |         SIZE          |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///       2|       HORIZ PRE       |       VERT PRE        |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///       4|                   LATITUDE                    |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///       6|                   LATITUDE                    |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///       8|                   LONGITUDE                   |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///      10|                   LONGITUDE                   |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///      12|                   ALTITUDE                    |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///      14|                   ALTITUDE                    |
+///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+///                                                    (octet)
+///
+/// where:
+///
+/// VERSION      Version number of the representation.  This must be zero.
+///              Implementations are required to check this field and make
+///              no assumptions about the format of unrecognized versions.
+///
+/// SIZE         The diameter of a sphere enclosing the described entity, in
+///              centimeters, expressed as a pair of four-bit unsigned
+///              integers, each ranging from zero to nine, with the most
+///              significant four bits representing the base and the second
+///              number representing the power of ten by which to multiply
+///              the base.  This allows sizes from 0e0 (<1cm) to 9e9
+///              (90,000km) to be expressed.
+///
+/// HORIZ PRE    The horizontal precision of the data, in centimeters,
+///              expressed using the same representation as SIZE.
+///
+/// VERT PRE     The vertical precision of the data, in centimeters,
+///              expressed using the sane representation as for SIZE.
+///
+/// LATITUDE     The latitude of the center of the sphere described by the
+///              SIZE field, expressed as a 32-bit integer, most significant
+///              octet first (network standard byte order), in thousandths
+///              of a second of arc.  2^31 represents the equator; numbers
+///              above that are north latitude.
+///
+/// LONGITUDE    The longitude of the center of the sphere described by the
+///              SIZE field, expressed as a 32-bit integer, most significant
+///              octet first (network standard byte order), in thousandths
+///              of a second of arc, rounded away from the prime meridian.
+///              2^31 represents the prime meridian; numbers above that are
+///              east longitude.
+///
+/// ALTITUDE     The altitude of the center of the sphere described by the
+///              SIZE field, expressed as a 32-bit integer, most significant
+///              octet first (network standard byte order), in centimeters,
+///              from a base of 100,000m below the [WGS 84] reference
+///              spheroid used by GPS (semimajor axis a=6378137.0,
+///              reciprocal flattening rf=298.257223563).
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct LOC {
+    size: u8,
+    horiz_pre: u8,
+    vert_pre: u8,
+    latitude: u32,
+    longitude: u32,
+    altitude: u32,
+}
+
+impl LOC {
+    /// Creates a new LOC record data from the encoded fields, the version is always 0
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - diameter of the located entity, see `precision_to_centimeters`
+    /// * `horiz_pre` - horizontal precision, in the same form as `size`
+    /// * `vert_pre` - vertical precision, in the same form as `size`
+    /// * `latitude` - thousandths of a second of arc, `EQUATOR` is the equator, north is above
+    /// * `longitude` - thousandths of a second of arc, `EQUATOR` is the prime meridian, east is above
+    /// * `altitude` - centimeters, `REFERENCE_ALTITUDE` is the WGS 84 reference spheroid
+    pub fn new(
+        size: u8,
+        horiz_pre: u8,
+        vert_pre: u8,
+        latitude: u32,
+        longitude: u32,
+        altitude: u32,
+    ) -> Self {
+        LOC {
+            size,
+            horiz_pre,
+            vert_pre,
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+
+    /// Creates a new LOC record data from decimal degrees and an altitude in meters, using the
+    ///  default size and precisions
+    ///
+    /// # Arguments
+    ///
+    /// * `latitude` - degrees, from -90 (south) to 90 (north)
+    /// * `longitude` - degrees, from -180 (west) to 180 (east)
+    /// * `altitude` - meters above the WGS 84 reference spheroid, from -100,000m
+    pub fn from_degrees(latitude: f64, longitude: f64, altitude: f64) -> ProtoResult<Self> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(format!("LOC latitude out of range: {}", latitude).into());
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(format!("LOC longitude out of range: {}", longitude).into());
+        }
+
+        let altitude_cm = (altitude * 100.0).round();
+        let encoded_altitude = altitude_cm + f64::from(REFERENCE_ALTITUDE);
+        if !(0.0..=f64::from(u32::max_value())).contains(&encoded_altitude) {
+            return Err(format!("LOC altitude out of range: {}", altitude).into());
+        }
+
+        Ok(LOC::new(
+            DEFAULT_SIZE,
+            DEFAULT_HORIZ_PRE,
+            DEFAULT_VERT_PRE,
+            encode_degrees(latitude),
+            encode_degrees(longitude),
+            encoded_altitude as u32,
+        ))
+    }
+
+    /// The diameter of the located entity, see `precision_to_centimeters`
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+
+    /// The horizontal precision, see `precision_to_centimeters`
+    pub fn horiz_pre(&self) -> u8 {
+        self.horiz_pre
+    }
+
+    /// The vertical precision, see `precision_to_centimeters`
+    pub fn vert_pre(&self) -> u8 {
+        self.vert_pre
+    }
+
+    /// The encoded latitude, in thousandths of a second of arc relative to `EQUATOR`
+    pub fn latitude(&self) -> u32 {
+        self.latitude
+    }
+
+    /// The encoded longitude, in thousandths of a second of arc relative to `EQUATOR`
+    pub fn longitude(&self) -> u32 {
+        self.longitude
+    }
+
+    /// The encoded altitude, in centimeters from 100,000m below the reference spheroid
+    pub fn altitude(&self) -> u32 {
+        self.altitude
+    }
+
+    /// The latitude in decimal degrees, south is negative
+    pub fn latitude_degrees(&self) -> f64 {
+        decode_degrees(self.latitude)
+    }
+
+    /// The longitude in decimal degrees, west is negative
+    pub fn longitude_degrees(&self) -> f64 {
+        decode_degrees(self.longitude)
+    }
+
+    /// The altitude in meters above the WGS 84 reference spheroid
+    pub fn altitude_meters(&self) -> f64 {
+        (i64::from(self.altitude) - i64::from(REFERENCE_ALTITUDE)) as f64 / 100.0
+    }
+}
+
+fn encode_degrees(degrees: f64) -> u32 {
+    (i64::from(EQUATOR) + (degrees * ARC_DEGREE as f64).round() as i64) as u32
+}
+
+fn decode_degrees(encoded: u32) -> f64 {
+    (i64::from(encoded) - i64::from(EQUATOR)) as f64 / ARC_DEGREE as f64
+}
+
+/// Decodes a size or precision octet, a mantissa in the high four bits and a power of ten in
+///  the low four bits, into centimeters
+pub fn precision_to_centimeters(precision: u8) -> u64 {
+    u64::from(precision >> 4) * 10_u64.pow(u32::from(precision & 0x0F))
+}
+
+/// Encodes centimeters as a size or precision octet, only the most significant digit is kept
+pub fn precision_from_centimeters(centimeters: u64) -> ProtoResult<u8> {
+    let mut mantissa = centimeters;
+    let mut exponent = 0;
+    while mantissa >= 10 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+
+    if exponent > 9 {
+        return Err(format!("LOC size or precision too large: {}cm", centimeters).into());
+    }
+
+    Ok((mantissa as u8) << 4 | exponent)
+}
+
+fn read_precision(decoder: &mut BinDecoder<'_>) -> ProtoResult<u8> {
+    decoder
+        .read_u8()?
+        .verify_unwrap(|precision| *precision >> 4 <= 9 && *precision & 0x0F <= 9)
+        .map_err(|precision| {
+            ProtoError::from(format!("invalid LOC size or precision: {:#04x}", precision))
+        })
+}
+
+/// Read the RData from the given Decoder
+pub fn read(decoder: &mut BinDecoder<'_>) -> ProtoResult<LOC> {
+    decoder
+        .read_u8()?
+        .verify_unwrap(|version| *version == 0)
+        .map_err(|version| ProtoError::from(format!("unsupported LOC version: {}", version)))?;
+
+    let size = read_precision(decoder)?;
+    let horiz_pre = read_precision(decoder)?;
+    let vert_pre = read_precision(decoder)?;
+    let latitude = decoder.read_u32()?.unverified(/*any u32 is a valid latitude*/);
+    let longitude = decoder.read_u32()?.unverified(/*any u32 is a valid longitude*/);
+    let altitude = decoder.read_u32()?.unverified(/*any u32 is a valid altitude*/);
+
+    Ok(LOC::new(
+        size, horiz_pre, vert_pre, latitude, longitude, altitude,
+    ))
+}
+
+/// Write the RData from the given Decoder
+pub fn emit(encoder: &mut BinEncoder<'_>, loc: &LOC) -> ProtoResult<()> {
+    encoder.emit_u8(0)?; // version
+    encoder.emit_u8(loc.size)?;
+    encoder.emit_u8(loc.horiz_pre)?;
+    encoder.emit_u8(loc.vert_pre)?;
+    encoder.emit_u32(loc.latitude)?;
+    encoder.emit_u32(loc.longitude)?;
+    encoder.emit_u32(loc.altitude)?;
+
+    Ok(())
+}
+
+fn fmt_coordinate(
+    f: &mut fmt::Formatter<'_>,
+    encoded: u32,
+    positive: char,
+    negative: char,
+) -> Result<(), fmt::Error> {
+    let thousandths = i64::from(encoded) - i64::from(EQUATOR);
+    let hemisphere = if thousandths < 0 { negative } else { positive };
+    let thousandths = thousandths.abs();
+
+    write!(
+        f,
+        "{degrees} {minutes} {seconds}.{fraction:03} {hemisphere}",
+        degrees = thousandths / ARC_DEGREE,
+        minutes = thousandths / 60_000 % 60,
+        seconds = thousandths / 1000 % 60,
+        fraction = thousandths % 1000,
+        hemisphere = hemisphere,
+    )
+}
+
+fn fmt_meters(f: &mut fmt::Formatter<'_>, centimeters: i64) -> Result<(), fmt::Error> {
+    if centimeters < 0 {
+        f.write_str("-")?;
+    }
+    let centimeters = centimeters.abs();
+
+    write!(f, "{}.{:02}m", centimeters / 100, centimeters % 100)
+}
+
+fn fmt_precision(f: &mut fmt::Formatter<'_>, precision: u8) -> Result<(), fmt::Error> {
+    let centimeters = precision_to_centimeters(precision);
+    if centimeters % 100 == 0 {
+        write!(f, "{}m", centimeters / 100)
+    } else {
+        write!(f, "{}.{:02}m", centimeters / 100, centimeters % 100)
+    }
+}
+
+/// [RFC 1876, A Means for Expressing Location Information in the DNS, January 1996](https://tools.ietf.org/html/rfc1876#section-3)
+///
+/// ```text
+/// 3. Master File Format
+///
+///    The LOC record is expressed in a master file in the following format:
+///
+///    <owner> <TTL> <class> LOC ( d1 [m1 [s1]] {"N"|"S"} d2 [m2 [s2]]
+///                                {"E"|"W"} alt["m"] [siz["m"] [hp["m"]
+///                                [vp["m"]]]] )
+///
+///    (The parentheses are used for multi-line data as specified in [RFC
+///    1035] section 5.1.)
+///
+///    where:
+///
+///        d1:     [0 .. 90]            (degrees latitude)
+///        d2:     [0 .. 180]           (degrees longitude)
+///        m1, m2: [0 .. 59]            (minutes latitude/longitude)
+///        s1, s2: [0 .. 59.999]        (seconds latitude/longitude)
+///        alt:    [-100000.00 .. 42849672.95] BY .01 (altitude in meters)
+///        siz, hp, vp: [0 .. 90000000.00] (size/precision in meters)
+/// ```
+///
+/// All of the fields are always written, e.g. `42 21 54.000 N 71 6 18.000 W -24.00m 30m 10000m 10m`
+impl fmt::Display for LOC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt_coordinate(f, self.latitude, 'N', 'S')?;
+        f.write_str(" ")?;
+        fmt_coordinate(f, self.longitude, 'E', 'W')?;
+        f.write_str(" ")?;
+        fmt_meters(f, i64::from(self.altitude) - i64::from(REFERENCE_ALTITUDE))?;
+        f.write_str(" ")?;
+        fmt_precision(f, self.size)?;
+        f.write_str(" ")?;
+        fmt_precision(f, self.horiz_pre)?;
+        f.write_str(" ")?;
+        fmt_precision(f, self.vert_pre)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::dbg_macro, clippy::print_stdout)]
+
+    use super::*;
+
+    // cambridge-net.kei.com.        LOC   42 21 54 N 71 06 18 W -24m 30m
+    fn cambridge_net() -> LOC {
+        LOC::new(
+            0x33,
+            DEFAULT_HORIZ_PRE,
+            DEFAULT_VERT_PRE,
+            EQUATOR + 152_514_000,
+            EQUATOR - 255_978_000,
+            REFERENCE_ALTITUDE - 2400,
+        )
+    }
+
+    #[test]
+    fn test() {
+        let rdata = cambridge_net();
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, &rdata).is_ok());
+        let bytes = encoder.into_bytes();
+
+        println!("bytes: {:?}", bytes);
+        assert_eq!(
+            bytes,
+            &[
+                0, 0x33, 0x16, 0x13, 0x89, 0x17, 0x2D, 0xD0, 0x70, 0xBE, 0x15, 0xF0, 0x00, 0x98,
+                0x8D, 0x20
+            ]
+        );
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let read_rdata = read(&mut decoder).expect("Decoding error");
+        assert_eq!(rdata, read_rdata);
+        assert_eq!(
+            rdata.to_string(),
+            "42 21 54.000 N 71 6 18.000 W -24.00m 30m 10000m 10m"
+        );
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let mut bytes = vec![0, 0x33, 0x16, 0x13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        // unknown versions aren't guessed at
+        bytes[0] = 1;
+        assert!(read(&mut BinDecoder::new(&bytes)).is_err());
+
+        // the mantissa and exponent must be decimal digits
+        bytes[0] = 0;
+        bytes[1] = 0x3A;
+        assert!(read(&mut BinDecoder::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn test_degrees() {
+        let rdata = cambridge_net();
+        assert!((rdata.latitude_degrees() - 42.365).abs() < 1e-9);
+        assert!((rdata.longitude_degrees() + 71.105).abs() < 1e-9);
+        assert!((rdata.altitude_meters() + 24.0).abs() < 1e-9);
+
+        let from_degrees = LOC::from_degrees(42.365, -71.105, -24.0).unwrap();
+        assert_eq!(from_degrees.latitude(), rdata.latitude());
+        assert_eq!(from_degrees.longitude(), rdata.longitude());
+        assert_eq!(from_degrees.altitude(), rdata.altitude());
+        assert_eq!(from_degrees.size(), DEFAULT_SIZE);
+
+        assert_eq!(
+            LOC::from_degrees(-90.0, 180.0, 0.0).unwrap().to_string(),
+            "90 0 0.000 S 180 0 0.000 E 0.00m 1m 10000m 10m"
+        );
+        assert!(LOC::from_degrees(90.5, 0.0, 0.0).is_err());
+        assert!(LOC::from_degrees(0.0, -180.5, 0.0).is_err());
+        assert!(LOC::from_degrees(0.0, 0.0, -100_000.01).is_err());
+        assert!(LOC::from_degrees(std::f64::NAN, 0.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        assert_eq!(precision_to_centimeters(0x12), 100);
+        assert_eq!(precision_to_centimeters(0x16), 1_000_000);
+        assert_eq!(precision_to_centimeters(0x99), 9_000_000_000);
+        assert_eq!(precision_to_centimeters(0x00), 0);
+
+        assert_eq!(precision_from_centimeters(100).unwrap(), 0x12);
+        assert_eq!(precision_from_centimeters(3000).unwrap(), 0x33);
+        assert_eq!(precision_from_centimeters(50).unwrap(), 0x51);
+        assert_eq!(precision_from_centimeters(0).unwrap(), 0x00);
+        // only the most significant digit is kept
+        assert_eq!(precision_from_centimeters(2500).unwrap(), 0x23);
+        assert!(precision_from_centimeters(10_000_000_000).is_err());
+    }
+}
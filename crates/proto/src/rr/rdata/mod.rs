@@ -22,6 +22,8 @@
 pub mod a;
 pub mod aaaa;
 pub mod caa;
+pub mod hinfo;
+pub mod loc;
 pub mod mx;
 pub mod name;
 pub mod naptr;
@@ -33,8 +35,11 @@ pub mod srv;
 pub mod sshfp;
 pub mod tlsa;
 pub mod txt;
+pub mod uri;
 
 pub use self::caa::CAA;
+pub use self::hinfo::HINFO;
+pub use self::loc::LOC;
 pub use self::mx::MX;
 pub use self::naptr::NAPTR;
 pub use self::null::NULL;
@@ -45,3 +50,4 @@ pub use self::srv::SRV;
 pub use self::sshfp::SSHFP;
 pub use self::tlsa::TLSA;
 pub use self::txt::TXT;
+pub use self::uri::URI;
@@ -39,7 +39,9 @@ use crate::serialize::binary::*;
 /// [RFC-974].
 ///
 /// ```
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+///
+/// `MX` orders by preference first, so a sorted list of records is in the order they should be tried.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
 pub struct MX {
     preference: u16,
     exchange: Name,
@@ -181,4 +183,36 @@ mod tests {
         let read_rdata = read(&mut decoder).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_display() {
+        use std::str::FromStr;
+
+        let rdata = MX::new(10, Name::from_str("mail.example.com.").unwrap());
+        assert_eq!(rdata.to_string(), "10 mail.example.com.");
+    }
+
+    #[test]
+    fn test_sort_by_preference() {
+        use std::str::FromStr;
+
+        let mut mxs = vec![
+            MX::new(30, Name::from_str("po3.example.com.").unwrap()),
+            MX::new(10, Name::from_str("po1.example.com.").unwrap()),
+            MX::new(20, Name::from_str("b.example.com.").unwrap()),
+            MX::new(20, Name::from_str("a.example.com.").unwrap()),
+        ];
+        mxs.sort();
+
+        let sorted: Vec<String> = mxs.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "10 po1.example.com.",
+                "20 a.example.com.",
+                "20 b.example.com.",
+                "30 po3.example.com."
+            ]
+        );
+    }
 }
@@ -89,3 +89,19 @@ pub fn test() {
     let read_rdata = read(&mut decoder).expect("Decoding error");
     assert_eq!(rdata, read_rdata);
 }
+
+#[test]
+pub fn test_read_compressed() {
+    // example.com. at offset 0, followed by the rdata: www + pointer to offset 0
+    let bytes: &[u8] = &[
+        7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0, 3, b'w', b'w', b'w',
+        0xC0, 0x00,
+    ];
+
+    let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+    decoder.read_slice(13).expect("skip origin");
+    let read_rdata = read(&mut decoder).expect("Decoding error");
+
+    assert_eq!(read_rdata, Name::from_ascii("www.example.com.").unwrap());
+    assert!(decoder.is_empty());
+}
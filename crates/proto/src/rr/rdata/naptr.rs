@@ -9,6 +9,7 @@
 
 use std::fmt;
 
+use super::txt::fmt_character_string;
 use crate::error::*;
 use crate::rr::domain::Name;
 use crate::serialize::binary::*;
@@ -228,7 +229,13 @@ pub fn emit(encoder: &mut BinEncoder<'_>, naptr: &NAPTR) -> ProtoResult<()> {
     encoder.emit_character_data(&naptr.services)?;
     encoder.emit_character_data(&naptr.regexp)?;
 
-    encoder.with_canonical_names(|encoder| naptr.replacement.emit(encoder))?;
+    // the replacement is never compressed, but is only lowercased for canonical form, RFC 4034 section 6.2
+    let is_canonical_names = encoder.is_canonical_names();
+    encoder.with_canonical_names(|encoder| {
+        naptr
+            .replacement
+            .emit_with_lowercase(encoder, is_canonical_names)
+    })?;
     Ok(())
 }
 
@@ -254,14 +261,16 @@ impl fmt::Display for NAPTR {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         write!(
             f,
-            "{order} {pref} \"{flags}\" \"{service}\" \"{regexp}\" {replace}",
+            "{order} {pref} ",
             order = self.order,
-            pref = self.preference,
-            flags = &String::from_utf8_lossy(&self.flags),
-            service = &String::from_utf8_lossy(&self.services),
-            regexp = &String::from_utf8_lossy(&self.regexp),
-            replace = self.replacement
-        )
+            pref = self.preference
+        )?;
+        fmt_character_string(f, &self.flags)?;
+        f.write_str(" ")?;
+        fmt_character_string(f, &self.services)?;
+        f.write_str(" ")?;
+        fmt_character_string(f, &self.regexp)?;
+        write!(f, " {replace}", replace = self.replacement)
     }
 }
 
@@ -295,6 +304,54 @@ mod tests {
         assert_eq!(rdata, read_rdata);
     }
 
+    #[test]
+    fn test_emit_canonical_replacement() {
+        let rdata = NAPTR::new(
+            8,
+            16,
+            b"S".to_vec().into_boxed_slice(),
+            b"SIP+D2U".to_vec().into_boxed_slice(),
+            b"".to_vec().into_boxed_slice(),
+            Name::from_ascii("_SIP._udp.Example.COM.").unwrap(),
+        );
+
+        // never compressed, case preserved
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        Name::from_ascii("Example.COM.")
+            .unwrap()
+            .emit(&mut encoder)
+            .unwrap();
+        emit(&mut encoder, &rdata).unwrap();
+        assert!(bytes.ends_with(b"\x04_SIP\x04_udp\x07Example\x03COM\x00"));
+
+        // canonical form is also lowercased
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        encoder.set_canonical_names(true);
+        emit(&mut encoder, &rdata).unwrap();
+        assert!(bytes.ends_with(b"\x04_sip\x04_udp\x07example\x03com\x00"));
+    }
+
+    #[test]
+    fn test_display() {
+        let rdata = NAPTR::new(
+            100,
+            10,
+            b"U".to_vec().into_boxed_slice(),
+            b"E2U+sip".to_vec().into_boxed_slice(),
+            b"!^\\+1(.*)$!sip:\\1@example.com!"
+                .to_vec()
+                .into_boxed_slice(),
+            Name::root(),
+        );
+
+        assert_eq!(
+            rdata.to_string(),
+            r#"100 10 "U" "E2U+sip" "!^\\+1(.*)$!sip:\\1@example.com!" ."#
+        );
+    }
+
     #[test]
     pub fn test_bad_data() {
         use std::str::FromStr;
@@ -19,6 +19,7 @@ use std::fmt;
 
 use crate::error::*;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_base64;
 
 /// [RFC 1035, DOMAIN NAMES - IMPLEMENTATION AND SPECIFICATION, November 1987](https://tools.ietf.org/html/rfc1035)
 ///
@@ -86,7 +87,7 @@ pub fn emit(encoder: &mut BinEncoder<'_>, nil: &NULL) -> ProtoResult<()> {
 impl fmt::Display for NULL {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         if let Some(thing) = &self.anything {
-            f.write_str(&data_encoding::BASE64.encode(thing))?;
+            f.write_str(&to_base64(thing))?;
         }
 
         Ok(())
@@ -10,6 +10,7 @@ use std::fmt;
 
 use crate::error::*;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_base64;
 
 /// [RFC 7929](https://tools.ietf.org/html/rfc7929#section-2.1)
 ///
@@ -68,7 +69,7 @@ pub fn emit(encoder: &mut BinEncoder<'_>, openpgpkey: &OPENPGPKEY) -> ProtoResul
 /// ```
 impl fmt::Display for OPENPGPKEY {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(&data_encoding::BASE64.encode(&self.public_key))
+        f.write_str(&to_base64(&self.public_key))
     }
 }
 
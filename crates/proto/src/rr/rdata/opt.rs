@@ -17,6 +17,7 @@
 //! option record for passing protocol options between the client and server
 
 use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 use log::warn;
 
@@ -201,12 +202,12 @@ impl OPT {
 pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoResult<OPT> {
     let mut state: OptReadState = OptReadState::ReadCode;
     let mut options: HashMap<EdnsCode, EdnsOption> = HashMap::new();
-    let start_idx = decoder.index();
 
     // There is no unsafe direct use of the rdata length after this point
     let rdata_length =
         rdata_length.map(|u| u as usize).unverified(/*rdata length usage is bounded*/);
-    while rdata_length > decoder.index() - start_idx {
+    let mut decoder = decoder.read_bounded(rdata_length)?;
+    while !decoder.is_empty() {
         match state {
             OptReadState::ReadCode => {
                 state = OptReadState::Code {
@@ -403,6 +404,12 @@ pub enum EdnsOption {
     #[cfg(feature = "dnssec")]
     N3U(SupportedAlgorithms),
 
+    /// [RFC 7871, Client Subnet](https://tools.ietf.org/html/rfc7871)
+    Subnet(ClientSubnet),
+
+    /// [RFC 7873, DNS Cookies](https://tools.ietf.org/html/rfc7873)
+    Cookie(Cookie),
+
     /// Unknown, used to deal with unknown or unsupported codes
     Unknown(u16, Vec<u8>),
 }
@@ -415,6 +422,8 @@ impl EdnsOption {
             EdnsOption::DAU(ref algorithms)
             | EdnsOption::DHU(ref algorithms)
             | EdnsOption::N3U(ref algorithms) => algorithms.len(),
+            EdnsOption::Subnet(ref subnet) => subnet.len(),
+            EdnsOption::Cookie(ref cookie) => cookie.len(),
             EdnsOption::Unknown(_, ref data) => data.len() as u16, // TODO: should we verify?
         }
     }
@@ -426,6 +435,7 @@ impl EdnsOption {
             EdnsOption::DAU(ref algorithms)
             | EdnsOption::DHU(ref algorithms)
             | EdnsOption::N3U(ref algorithms) => algorithms.is_empty(),
+            EdnsOption::Subnet(..) | EdnsOption::Cookie(..) => false,
             EdnsOption::Unknown(_, ref data) => data.is_empty(),
         }
     }
@@ -438,16 +448,31 @@ impl BinEncodable for EdnsOption {
             EdnsOption::DAU(ref algorithms)
             | EdnsOption::DHU(ref algorithms)
             | EdnsOption::N3U(ref algorithms) => algorithms.emit(encoder),
+            EdnsOption::Subnet(ref subnet) => subnet.emit(encoder),
+            EdnsOption::Cookie(ref cookie) => cookie.emit(encoder),
             EdnsOption::Unknown(_, ref data) => encoder.emit_vec(data), // gah, clone needed or make a crazy api.
         }
     }
 }
 
-/// only the supported extensions are listed right now.
+/// only the supported extensions are listed right now, malformed options are left as `Unknown`.
 impl<'a> From<(EdnsCode, &'a [u8])> for EdnsOption {
-    #[allow(clippy::match_single_binding)]
     fn from(value: (EdnsCode, &'a [u8])) -> EdnsOption {
         match value.0 {
+            EdnsCode::Subnet => match ClientSubnet::from_bytes(value.1) {
+                Ok(subnet) => EdnsOption::Subnet(subnet),
+                Err(e) => {
+                    warn!("ignoring malformed EDNS client subnet: {}", e);
+                    EdnsOption::Unknown(value.0.into(), value.1.to_vec())
+                }
+            },
+            EdnsCode::Cookie => match Cookie::from_bytes(value.1) {
+                Ok(cookie) => EdnsOption::Cookie(cookie),
+                Err(e) => {
+                    warn!("ignoring malformed EDNS cookie: {}", e);
+                    EdnsOption::Unknown(value.0.into(), value.1.to_vec())
+                }
+            },
             #[cfg(feature = "dnssec")]
             EdnsCode::DAU => EdnsOption::DAU(value.1.into()),
             #[cfg(feature = "dnssec")]
@@ -466,6 +491,8 @@ impl<'a> From<&'a EdnsOption> for Vec<u8> {
             EdnsOption::DAU(ref algorithms)
             | EdnsOption::DHU(ref algorithms)
             | EdnsOption::N3U(ref algorithms) => algorithms.into(),
+            EdnsOption::Subnet(ref subnet) => subnet.into(),
+            EdnsOption::Cookie(ref cookie) => cookie.into(),
             EdnsOption::Unknown(_, ref data) => data.clone(), // gah, clone needed or make a crazy api.
         }
     }
@@ -480,16 +507,280 @@ impl<'a> From<&'a EdnsOption> for EdnsCode {
             EdnsOption::DHU(..) => EdnsCode::DHU,
             #[cfg(feature = "dnssec")]
             EdnsOption::N3U(..) => EdnsCode::N3U,
+            EdnsOption::Subnet(..) => EdnsCode::Subnet,
+            EdnsOption::Cookie(..) => EdnsCode::Cookie,
             EdnsOption::Unknown(code, _) => code.into(),
         }
     }
 }
 
+/// [RFC 7871, Client Subnet in DNS Queries](https://tools.ietf.org/html/rfc7871#section-6)
+///
+/// ```text
+/// 6.  Option Format
+///
+///                 +0 (MSB)                            +1 (LSB)
+///       +---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+
+///    4: |                            FAMILY                             |
+///       +---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+
+///    6: |     SOURCE PREFIX-LENGTH      |     SCOPE PREFIX-LENGTH       |
+///       +---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+
+///    8: |                           ADDRESS...                          /
+///       +---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+---+
+///
+///    o  ADDRESS, variable number of octets, contains either an IPv4 or
+///       IPv6 address, depending on FAMILY, which MUST be truncated to the
+///       number of bits indicated by the SOURCE PREFIX-LENGTH field,
+///       padding with 0 bits to pad to the end of the last octet needed.
+/// ```
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct ClientSubnet {
+    address: IpAddr,
+    source_prefix: u8,
+    scope_prefix: u8,
+}
+
+impl ClientSubnet {
+    /// Creates a new ClientSubnet, the address is truncated to the source prefix length
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - the client address, its type determines the family
+    /// * `source_prefix` - the number of leading bits of the address to send
+    /// * `scope_prefix` - the number of leading bits covered by the answer, `0` in queries
+    pub fn new(address: IpAddr, source_prefix: u8, scope_prefix: u8) -> ProtoResult<Self> {
+        let max_prefix = match address {
+            IpAddr::V4(..) => 32,
+            IpAddr::V6(..) => 128,
+        };
+        if source_prefix > max_prefix || scope_prefix > max_prefix {
+            return Err(format!(
+                "bad client subnet prefix for {}: {}/{}",
+                address, source_prefix, scope_prefix
+            )
+            .into());
+        }
+
+        let address = match address {
+            IpAddr::V4(v4) => {
+                let mask = u32::max_value()
+                    .checked_shl(32 - u32::from(source_prefix))
+                    .unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask))
+            }
+            IpAddr::V6(v6) => {
+                let mask = u128::max_value()
+                    .checked_shl(128 - u32::from(source_prefix))
+                    .unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask))
+            }
+        };
+
+        Ok(ClientSubnet {
+            address,
+            source_prefix,
+            scope_prefix,
+        })
+    }
+
+    /// Reads the ClientSubnet from the option data
+    pub fn from_bytes(data: &[u8]) -> ProtoResult<Self> {
+        if data.len() < 4 {
+            return Err(format!("bad client subnet length: {}", data.len()).into());
+        }
+
+        let family = u16::from_be_bytes([data[0], data[1]]);
+        let source_prefix = data[2];
+        let scope_prefix = data[3];
+        let address = &data[4..];
+
+        if address.len() != (usize::from(source_prefix) + 7) / 8 {
+            return Err(format!(
+                "client subnet address length {} does not match prefix: {}",
+                address.len(),
+                source_prefix
+            )
+            .into());
+        }
+
+        let ip = match family {
+            1 if address.len() <= 4 => {
+                let mut octets = [0_u8; 4];
+                octets[..address.len()].copy_from_slice(address);
+                IpAddr::from(octets)
+            }
+            2 if address.len() <= 16 => {
+                let mut octets = [0_u8; 16];
+                octets[..address.len()].copy_from_slice(address);
+                IpAddr::from(octets)
+            }
+            _ => {
+                return Err(format!(
+                    "bad client subnet family {} for address length: {}",
+                    family,
+                    address.len()
+                )
+                .into())
+            }
+        };
+
+        let subnet = Self::new(ip, source_prefix, scope_prefix)?;
+        if subnet.address != ip {
+            return Err(format!("client subnet address bits set beyond prefix: {}", ip).into());
+        }
+
+        Ok(subnet)
+    }
+
+    /// The client address, truncated to the source prefix length
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// The number of leading bits of the address supplied by the client
+    pub fn source_prefix(&self) -> u8 {
+        self.source_prefix
+    }
+
+    /// The number of leading bits of the address the response covers, `0` in queries
+    pub fn scope_prefix(&self) -> u8 {
+        self.scope_prefix
+    }
+
+    /// Returns the address octets that are sent on the wire
+    fn address_bytes(&self) -> Vec<u8> {
+        let len = (usize::from(self.source_prefix) + 7) / 8;
+        match self.address {
+            IpAddr::V4(v4) => v4.octets()[..len].to_vec(),
+            IpAddr::V6(v6) => v6.octets()[..len].to_vec(),
+        }
+    }
+
+    /// Returns the length in bytes of the option data
+    fn len(&self) -> u16 {
+        4 + (u16::from(self.source_prefix) + 7) / 8
+    }
+}
+
+impl BinEncodable for ClientSubnet {
+    fn emit(&self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
+        let family = match self.address {
+            IpAddr::V4(..) => 1,
+            IpAddr::V6(..) => 2,
+        };
+
+        encoder.emit_u16(family)?;
+        encoder.emit(self.source_prefix)?;
+        encoder.emit(self.scope_prefix)?;
+        encoder.emit_vec(&self.address_bytes())
+    }
+}
+
+impl<'a> From<&'a ClientSubnet> for Vec<u8> {
+    fn from(value: &'a ClientSubnet) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(value.len() as usize);
+        let mut encoder = BinEncoder::new(&mut bytes);
+        value
+            .emit(&mut encoder)
+            .expect("emitting to a Vec can not fail");
+
+        bytes
+    }
+}
+
+/// [RFC 7873, DNS Cookies](https://tools.ietf.org/html/rfc7873#section-4)
+///
+/// ```text
+/// 4.  DNS Cookie Option
+///
+///    The DNS Cookie Option is an OPT RR [RFC6891] option that can be
+///    included in the RDATA portion of an OPT RR in DNS requests and
+///    responses.  The option length varies, depending on the
+///    circumstances in which it is being used.  There are two cases, as
+///    described below.  Both use the same OPTION-CODE; they are
+///    distinguished by their length.
+///
+///    1.  When a client has no server cookie, the COOKIE option contains
+///        only the 8-byte Client Cookie.
+///
+///    2.  Otherwise, the COOKIE option is followed by a Server Cookie
+///        of 8 to 32 bytes.
+/// ```
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Hash)]
+pub struct Cookie {
+    client: [u8; 8],
+    server: Option<Vec<u8>>,
+}
+
+impl Cookie {
+    /// Creates a new Cookie, the server cookie, if present, must be between 8 and 32 bytes
+    pub fn new(client: [u8; 8], server: Option<Vec<u8>>) -> ProtoResult<Self> {
+        if let Some(ref server) = server {
+            if !(8..=32).contains(&server.len()) {
+                return Err(format!("bad server cookie length: {}", server.len()).into());
+            }
+        }
+
+        Ok(Cookie { client, server })
+    }
+
+    /// Reads the Cookie from the option data
+    pub fn from_bytes(data: &[u8]) -> ProtoResult<Self> {
+        if data.len() < 8 {
+            return Err(format!("bad client cookie length: {}", data.len()).into());
+        }
+
+        let mut client = [0_u8; 8];
+        client.copy_from_slice(&data[..8]);
+        let server = Some(&data[8..])
+            .filter(|server| !server.is_empty())
+            .map(<[u8]>::to_vec);
+
+        Self::new(client, server)
+    }
+
+    /// The client cookie
+    pub fn client(&self) -> &[u8; 8] {
+        &self.client
+    }
+
+    /// The server cookie, if the server has issued one
+    pub fn server(&self) -> Option<&[u8]> {
+        self.server.as_deref()
+    }
+
+    /// Returns the length in bytes of the option data
+    fn len(&self) -> u16 {
+        (self.client.len() + self.server.as_ref().map_or(0, Vec::len)) as u16
+    }
+}
+
+impl BinEncodable for Cookie {
+    fn emit(&self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
+        encoder.emit_vec(&self.client)?;
+        if let Some(ref server) = self.server {
+            encoder.emit_vec(server)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a Cookie> for Vec<u8> {
+    fn from(value: &'a Cookie) -> Vec<u8> {
+        let mut bytes = value.client.to_vec();
+        if let Some(ref server) = value.server {
+            bytes.extend_from_slice(server);
+        }
+
+        bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::dbg_macro, clippy::print_stdout)]
 
-    #[cfg(feature = "dnssec")]
     use super::*;
 
     #[test]
@@ -510,6 +801,113 @@ mod tests {
         let read_rdata = read(&mut decoder, restrict).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_cookie() {
+        let client = [0x0b, 0x64, 0xb4, 0xdc, 0xd7, 0xb0, 0xcc, 0x8f];
+        let server = vec![
+            0x01, 0x00, 0x00, 0x00, 0x5f, 0xb3, 0xe1, 0x2c, 1, 2, 3, 4, 5, 6, 7, 8,
+        ];
+        let cookie = Cookie::new(client, Some(server.clone())).unwrap();
+        assert_eq!(cookie.client(), &client);
+        assert_eq!(cookie.server(), Some(&server[..]));
+        assert_eq!(cookie.len(), 24);
+
+        let mut rdata = OPT::default();
+        rdata.insert(EdnsOption::Cookie(cookie.clone()));
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        emit(&mut encoder, &rdata).expect("failed to emit OPT");
+        assert_eq!(&bytes[..4], &[0x00, 0x0a, 0x00, 24]);
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(&bytes);
+        let read_rdata =
+            read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("failed to read OPT");
+        assert_eq!(read_rdata, rdata);
+        assert_eq!(
+            read_rdata.get(EdnsCode::Cookie),
+            Some(&EdnsOption::Cookie(cookie))
+        );
+    }
+
+    fn test_client_subnet(subnet: ClientSubnet, option_data: &[u8]) {
+        let mut rdata = OPT::default();
+        rdata.insert(EdnsOption::Subnet(subnet));
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        emit(&mut encoder, &rdata).expect("failed to emit OPT");
+        assert_eq!(&bytes[..2], &[0x00, 0x08]);
+        assert_eq!(&bytes[2..4], &(option_data.len() as u16).to_be_bytes());
+        assert_eq!(&bytes[4..], option_data);
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(&bytes);
+        let read_rdata =
+            read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("failed to read OPT");
+        assert_eq!(read_rdata, rdata);
+    }
+
+    #[test]
+    fn test_client_subnet_v4() {
+        let subnet = ClientSubnet::new("192.0.2.55".parse().unwrap(), 24, 0).unwrap();
+        assert_eq!(subnet.address(), "192.0.2.0".parse::<IpAddr>().unwrap());
+        assert_eq!(subnet.source_prefix(), 24);
+        assert_eq!(subnet.scope_prefix(), 0);
+
+        test_client_subnet(subnet, &[0, 1, 24, 0, 192, 0, 2]);
+    }
+
+    #[test]
+    fn test_client_subnet_v6() {
+        // scope prefix as set by a server in a response
+        let subnet = ClientSubnet::new("2001:db8:1234:5678::1".parse().unwrap(), 56, 48).unwrap();
+        assert_eq!(
+            subnet.address(),
+            "2001:db8:1234:5600::".parse::<IpAddr>().unwrap()
+        );
+
+        test_client_subnet(
+            subnet,
+            &[0, 2, 56, 48, 0x20, 0x01, 0x0d, 0xb8, 0x12, 0x34, 0x56],
+        );
+    }
+
+    #[test]
+    fn test_client_subnet_family_only() {
+        let subnet = ClientSubnet::new("::1".parse().unwrap(), 0, 0).unwrap();
+        assert_eq!(subnet.address(), "::".parse::<IpAddr>().unwrap());
+
+        test_client_subnet(subnet, &[0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_bad_client_subnet() {
+        assert!(ClientSubnet::new("192.0.2.1".parse().unwrap(), 33, 0).is_err());
+        assert!(ClientSubnet::new("::1".parse().unwrap(), 56, 129).is_err());
+
+        // address longer than the prefix
+        assert!(ClientSubnet::from_bytes(&[0, 1, 16, 0, 192, 0, 2]).is_err());
+        // bits set beyond the prefix
+        assert!(ClientSubnet::from_bytes(&[0, 1, 20, 0, 192, 0, 255]).is_err());
+        // unknown family
+        assert!(ClientSubnet::from_bytes(&[0, 3, 0, 0]).is_err());
+        // IPv4 prefix too long
+        assert!(ClientSubnet::from_bytes(&[0, 1, 40, 0, 1, 2, 3, 4, 5]).is_err());
+    }
+
+    #[test]
+    fn test_bad_cookie() {
+        assert!(Cookie::new([0; 8], Some(vec![0; 7])).is_err());
+        assert!(Cookie::new([0; 8], Some(vec![0; 33])).is_err());
+        assert!(Cookie::from_bytes(&[0; 7]).is_err());
+
+        // malformed cookies are preserved as unknown options
+        assert_eq!(
+            EdnsOption::from((EdnsCode::Cookie, &[0_u8; 10] as &[u8])),
+            EdnsOption::Unknown(10, vec![0; 10])
+        );
+    }
 }
 
 #[test]
@@ -528,10 +926,15 @@ pub fn test_read_empty_option_at_end_of_opt() {
 
     let opt = read_rdata.unwrap();
     let mut options = HashMap::default();
-    options.insert(EdnsCode::Subnet, EdnsOption::Unknown(8, vec![0, 1, 0, 0]));
+    options.insert(
+        EdnsCode::Subnet,
+        EdnsOption::Subnet(ClientSubnet::new(Ipv4Addr::UNSPECIFIED.into(), 0, 0).unwrap()),
+    );
     options.insert(
         EdnsCode::Cookie,
-        EdnsOption::Unknown(10, vec![0x0b, 0x64, 0xb4, 0xdc, 0xd7, 0xb0, 0xcc, 0x8f]),
+        EdnsOption::Cookie(
+            Cookie::new([0x0b, 0x64, 0xb4, 0xdc, 0xd7, 0xb0, 0xcc, 0x8f], None).unwrap(),
+        ),
     );
     options.insert(EdnsCode::Keepalive, EdnsOption::Unknown(11, vec![]));
     let options = OPT::new(options);
@@ -16,6 +16,7 @@
 
 //! start of authority record defining ownership and defaults for the zone
 
+use std::cmp::Ordering;
 use std::fmt;
 
 use crate::error::*;
@@ -114,8 +115,11 @@ impl SOA {
     }
 
     /// Increments the serial number by one
+    ///
+    /// Serial numbers use [RFC 1982](https://tools.ietf.org/html/rfc1982) arithmetic, so this wraps
+    /// from `u32::MAX` back around to `0`, which secondaries still see as a newer serial.
     pub fn increment_serial(&mut self) {
-        self.serial += 1; // TODO: what to do on overflow?
+        self.serial = self.serial.wrapping_add(1);
     }
 
     /// ```text
@@ -211,6 +215,49 @@ impl SOA {
     }
 }
 
+/// Compares two serial numbers using [RFC 1982](https://tools.ietf.org/html/rfc1982) sequence space arithmetic
+///
+/// ```text
+/// 3.2. Comparison
+///
+///    Formally,
+///
+///    i1 is said to be less than i2 if, and only if,
+///
+///         (i1 < i2 and i2 - i1 < 2^(SERIAL_BITS - 1)) or
+///         (i1 > i2 and i1 - i2 > 2^(SERIAL_BITS - 1))
+///
+///    ...
+///
+///    Note that there are some pairs of values s1 and s2 for which s1 is
+///    not equal to s2, but for which s1 is neither greater than, nor less
+///    than, s2.  An attempt to use these ordering operators on such pairs
+///    of values produces an undefined result.
+/// ```
+///
+/// # Return value
+///
+/// The ordering of `a` relative to `b`, or `None` when they are exactly 2^31 apart and the
+/// comparison is undefined.
+pub fn serial_compare(a: u32, b: u32) -> Option<Ordering> {
+    const HALF: u32 = 1 << 31;
+
+    match a.wrapping_sub(b) {
+        0 => Some(Ordering::Equal),
+        HALF => None,
+        distance if distance < HALF => Some(Ordering::Greater),
+        _ => Some(Ordering::Less),
+    }
+}
+
+/// Returns true if serial `a` is newer than serial `b`, see `serial_compare`
+///
+/// Serials where the comparison is undefined are not greater, so a zone is never considered
+/// fresher on an ambiguous serial.
+pub fn serial_greater_than(a: u32, b: u32) -> bool {
+    serial_compare(a, b) == Some(Ordering::Greater)
+}
+
 /// Read the RData from the given Decoder
 pub fn read(decoder: &mut BinDecoder<'_>) -> ProtoResult<SOA> {
     Ok(SOA {
@@ -355,4 +402,50 @@ mod tests {
         let read_rdata = read(&mut decoder).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_increment_serial() {
+        let mut rdata = SOA::new(Name::root(), Name::root(), 1, 2, 3, 4, 5);
+        rdata.increment_serial();
+        assert_eq!(rdata.serial(), 2);
+
+        let mut rdata = SOA::new(Name::root(), Name::root(), u32::max_value(), 2, 3, 4, 5);
+        rdata.increment_serial();
+        assert_eq!(rdata.serial(), 0);
+    }
+
+    #[test]
+    fn test_serial_compare() {
+        assert_eq!(serial_compare(1, 1), Some(Ordering::Equal));
+        assert_eq!(serial_compare(2, 1), Some(Ordering::Greater));
+        assert_eq!(serial_compare(1, 2), Some(Ordering::Less));
+
+        // wraparound, u32::MAX + 1 == 0
+        assert_eq!(serial_compare(0, u32::max_value()), Some(Ordering::Greater));
+        assert_eq!(serial_compare(u32::max_value(), 0), Some(Ordering::Less));
+        assert_eq!(
+            serial_compare(5, u32::max_value() - 5),
+            Some(Ordering::Greater)
+        );
+
+        // the largest distance still defined, 2^31 - 1
+        assert_eq!(serial_compare((1 << 31) - 1, 0), Some(Ordering::Greater));
+        assert_eq!(serial_compare(0, (1 << 31) - 1), Some(Ordering::Less));
+        assert_eq!(serial_compare(1 << 31, 0), None);
+        assert_eq!(serial_compare(0, 1 << 31), None);
+        assert_eq!(serial_compare(u32::max_value(), (1 << 31) - 1), None);
+        assert_eq!(serial_compare((1 << 31) + 1, 0), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn test_serial_greater_than() {
+        assert!(serial_greater_than(2, 1));
+        assert!(serial_greater_than(0, u32::max_value()));
+        assert!(!serial_greater_than(1, 1));
+        assert!(!serial_greater_than(u32::max_value(), 0));
+
+        // ambiguous in both directions
+        assert!(!serial_greater_than(1 << 31, 0));
+        assert!(!serial_greater_than(0, 1 << 31));
+    }
 }
@@ -15,8 +15,14 @@
  */
 
 //! service records for identify port mapping for specific services on a host
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
 
+use rand::Rng;
+#[cfg(feature = "serde-config")]
+use serde::{Deserialize, Serialize};
+
 use crate::error::*;
 use crate::rr::domain::Name;
 use crate::serialize::binary::*;
@@ -81,6 +87,7 @@ use crate::serialize::binary::*;
 /// Class.
 ///
 /// ```
+#[cfg_attr(feature = "serde-config", derive(Deserialize, Serialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct SRV {
     priority: u16,
@@ -191,6 +198,110 @@ impl SRV {
     pub fn target(&self) -> &Name {
         &self.target
     }
+
+    /// Returns a copy of this record with the port replaced, all other fields are unchanged
+    pub fn with_port(&self, port: u16) -> Self {
+        SRV {
+            port,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this record with the target replaced, all other fields are unchanged
+    ///
+    /// # Errors
+    ///
+    /// The target must be fully qualified, e.g. `www.example.com.` or `.`, as it is in the wire
+    ///  format. A relative name has no origin here to be appended to.
+    pub fn with_target(&self, target: Name) -> ProtoResult<Self> {
+        if !target.is_fqdn() {
+            return Err(format!("SRV target must be fully qualified: {}", target).into());
+        }
+
+        Ok(SRV {
+            priority: self.priority,
+            weight: self.weight,
+            port: self.port,
+            target,
+        })
+    }
+}
+
+impl PartialOrd<SRV> for SRV {
+    fn partial_cmp(&self, other: &SRV) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders by ascending priority, then descending weight, then by target and port
+///
+/// This is only a coarse, deterministic sort that puts the preferred records first, it is not the
+///  weighted random selection of RFC 2782, see `selection_order` for that.
+impl Ord for SRV {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.weight.cmp(&self.weight))
+            .then_with(|| self.target.cmp(&other.target))
+            .then_with(|| self.port.cmp(&other.port))
+    }
+}
+
+/// Orders SRV records in which their targets should be contacted, as described in RFC 2782
+///
+/// See `SRV::weight` for the algorithm. Records with a lower priority always come first, within a
+///  priority records are chosen at random, in proportion to their weight.
+pub fn selection_order<'a, I: IntoIterator<Item = &'a SRV>>(srvs: I) -> Vec<&'a SRV> {
+    selection_order_with_rng(srvs, &mut rand::thread_rng())
+}
+
+/// Same as `selection_order`, but the weighted selection draws from the given random number generator
+///
+/// Before selecting, the records of each priority are arranged with weight 0 records first, as the
+///  RFC requires, and otherwise by target name. So the result only depends on the set of records
+///  and the random number generator, never on the order the records were given in. With a seeded
+///  generator this is reproducible, which is intended for tests and for stable caching.
+pub fn selection_order_with_rng<'a, I, R>(srvs: I, rng: &mut R) -> Vec<&'a SRV>
+where
+    I: IntoIterator<Item = &'a SRV>,
+    R: Rng + ?Sized,
+{
+    let mut unordered: Vec<&SRV> = srvs.into_iter().collect();
+    unordered.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then_with(|| (a.weight != 0).cmp(&(b.weight != 0)))
+            .then_with(|| a.target.cmp(&b.target))
+            .then_with(|| a.weight.cmp(&b.weight))
+            .then_with(|| a.port.cmp(&b.port))
+    });
+
+    let mut ordered = Vec::with_capacity(unordered.len());
+    while let Some(priority) = unordered.first().map(|srv| srv.priority) {
+        let same_priority = unordered
+            .iter()
+            .position(|srv| srv.priority != priority)
+            .unwrap_or(unordered.len());
+        let mut group: Vec<&SRV> = unordered.drain(..same_priority).collect();
+
+        while !group.is_empty() {
+            let sum: u32 = group.iter().map(|srv| u32::from(srv.weight)).sum();
+            let selected = rng.gen_range(0, sum + 1);
+
+            let mut running_sum = 0;
+            let next = group
+                .iter()
+                .position(|srv| {
+                    running_sum += u32::from(srv.weight);
+                    running_sum >= selected
+                })
+                .expect("the running sum reaches the sum of the weights");
+
+            ordered.push(group.remove(next));
+        }
+    }
+
+    ordered
 }
 
 /// Read the RData from the given Decoder
@@ -204,6 +315,22 @@ pub fn read(decoder: &mut BinDecoder<'_>) -> ProtoResult<SRV> {
     ))
 }
 
+/// Reads the RData from a buffer holding exactly the RDATA of one SRV record, nothing may follow
+///  the target
+impl<'a> TryFrom<&'a [u8]> for SRV {
+    type Error = ProtoError;
+
+    fn try_from(bytes: &'a [u8]) -> ProtoResult<Self> {
+        let mut decoder = BinDecoder::new(bytes);
+        let srv = read(&mut decoder)?;
+
+        if !decoder.is_empty() {
+            return Err(format!("{} bytes remain after the SRV target", decoder.len()).into());
+        }
+        Ok(srv)
+    }
+}
+
 /// [RFC 4034](https://tools.ietf.org/html/rfc4034#section-6), DNSSEC Resource Records, March 2005
 ///
 /// This is accurate for all currently known name records.
@@ -228,8 +355,12 @@ pub fn emit(encoder: &mut BinEncoder<'_>, srv: &SRV) -> ProtoResult<()> {
     encoder.emit_u16(srv.priority())?;
     encoder.emit_u16(srv.weight())?;
     encoder.emit_u16(srv.port())?;
-    srv.target()
-        .emit_with_lowercase(encoder, is_canonical_names)?;
+
+    // the target is never compressed, RFC 2782, but is only lowercased for canonical form
+    encoder.with_canonical_names(|encoder| {
+        srv.target()
+            .emit_with_lowercase(encoder, is_canonical_names)
+    })?;
     Ok(())
 }
 
@@ -281,4 +412,212 @@ mod tests {
         let read_rdata = read(&mut decoder).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_with_port() {
+        use std::str::FromStr;
+
+        let srv = SRV::new(1, 2, 3, Name::from_str("_dns._tcp.example.com.").unwrap());
+        let moved = srv.with_port(8053);
+
+        assert_eq!(moved.priority(), srv.priority());
+        assert_eq!(moved.weight(), srv.weight());
+        assert_eq!(moved.port(), 8053);
+        assert!(moved.target().eq_case(srv.target()));
+        assert_eq!(srv.port(), 3);
+    }
+
+    #[test]
+    fn test_with_target() {
+        use std::str::FromStr;
+
+        let srv = SRV::new(1, 2, 3, Name::from_str("_dns._tcp.example.com.").unwrap());
+
+        let moved = srv
+            .with_target(Name::from_str("ns.example.net.").unwrap())
+            .unwrap();
+        assert_eq!(
+            moved,
+            SRV::new(1, 2, 3, Name::from_str("ns.example.net.").unwrap())
+        );
+
+        // the service is not available at this domain
+        assert!(srv.with_target(Name::root()).unwrap().target().is_root());
+
+        assert!(srv
+            .with_target(Name::from_str("ns.example").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_ord() {
+        use std::str::FromStr;
+
+        let srv = |priority, weight, target| {
+            SRV::new(priority, weight, 443, Name::from_str(target).unwrap())
+        };
+
+        let mut srvs = vec![
+            srv(20, 0, "d.example.com."),
+            srv(10, 5, "b.example.com."),
+            srv(10, 60, "c.example.com."),
+            srv(0, 1, "e.example.com."),
+            srv(10, 5, "a.example.com."),
+        ];
+        srvs.sort();
+
+        assert_eq!(
+            srvs,
+            vec![
+                srv(0, 1, "e.example.com."),
+                srv(10, 60, "c.example.com."),
+                srv(10, 5, "a.example.com."),
+                srv(10, 5, "b.example.com."),
+                srv(20, 0, "d.example.com."),
+            ]
+        );
+
+        // consistent with Eq, only equal records compare equal
+        assert_eq!(
+            srv(10, 5, "a.example.com.").cmp(&srv(10, 5, "A.Example.COM.")),
+            Ordering::Equal
+        );
+        assert_ne!(
+            srv(10, 5, "a.example.com.").cmp(&srv(10, 5, "a.example.com.").with_port(80)),
+            Ordering::Equal
+        );
+    }
+
+    #[cfg(feature = "serde-config")]
+    #[test]
+    fn test_serde() {
+        let rdata = SRV::new(1, 2, 3, Name::from_ascii("_dns._tcp.Example.com.").unwrap());
+
+        // the target is in its presentation form
+        let json = serde_json::to_string(&rdata).unwrap();
+        assert_eq!(
+            json,
+            r#"{"priority":1,"weight":2,"port":3,"target":"_dns._tcp.Example.com."}"#
+        );
+
+        let got: SRV = serde_json::from_str(&json).unwrap();
+        assert_eq!(got, rdata);
+        assert!(got.target().is_fqdn());
+    }
+
+    #[test]
+    fn test_try_from_bytes() {
+        let bytes: &[u8] = b"\x00\x01\x00\x02\x00\x35\x03_ns\x07example\x03com\x00";
+
+        let srv = SRV::try_from(bytes).unwrap();
+        assert_eq!(srv.priority(), 1);
+        assert_eq!(srv.weight(), 2);
+        assert_eq!(srv.port(), 53);
+        assert!(srv
+            .target()
+            .eq_case(&Name::from_ascii("_ns.example.com.").unwrap()));
+
+        // truncated, and with trailing data
+        assert!(SRV::try_from(&bytes[..bytes.len() - 1]).is_err());
+        assert!(SRV::try_from(&[bytes, b"\x00"].concat()[..]).is_err());
+    }
+
+    #[test]
+    fn test_emit_canonical_target() {
+        let zone = Name::from_ascii("Example.COM.").unwrap();
+        let rdata = SRV::new(1, 2, 3, Name::from_ascii("Mail.Example.COM.").unwrap());
+
+        // the target is never compressed, and keeps its case
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        zone.emit(&mut encoder).unwrap();
+        emit(&mut encoder, &rdata).unwrap();
+        assert_eq!(bytes.len(), 13 + 6 + 18);
+        assert_eq!(&bytes[19..], b"\x04Mail\x07Example\x03COM\x00");
+
+        // in canonical form the target is lowercased and not compressed
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        zone.emit(&mut encoder).unwrap();
+        encoder.set_canonical_names(true);
+        emit(&mut encoder, &rdata).unwrap();
+        assert_eq!(bytes.len(), 13 + 6 + 18);
+        assert_eq!(&bytes[19..], b"\x04mail\x07example\x03com\x00");
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(&bytes);
+        decoder.read_slice(13).unwrap();
+        assert_eq!(read(&mut decoder).unwrap(), rdata);
+    }
+
+    #[test]
+    fn test_selection_order_tiebreak() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let srv = |priority, weight, target| {
+            SRV::new(priority, weight, 443, Name::from_ascii(target).unwrap())
+        };
+
+        // with identical priorities and zero weights, the selection is decided by the target name
+        let records = vec![
+            srv(10, 0, "c.example.com."),
+            srv(10, 0, "a.example.com."),
+            srv(5, 0, "z.example.com."),
+            srv(10, 0, "b.example.com."),
+        ];
+        for seed in 0..8 {
+            let ordered = selection_order_with_rng(&records, &mut StdRng::seed_from_u64(seed));
+            let targets = ordered
+                .iter()
+                .map(|srv| srv.target().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                targets,
+                vec![
+                    "z.example.com.",
+                    "a.example.com.",
+                    "b.example.com.",
+                    "c.example.com."
+                ]
+            );
+        }
+
+        // identical weights are chosen at random, but not depending on the order they were given
+        let records = vec![
+            srv(10, 5, "a.example.com."),
+            srv(10, 5, "b.example.com."),
+            srv(10, 5, "c.example.com."),
+            srv(20, 5, "d.example.com."),
+        ];
+        let mut reversed = records.clone();
+        reversed.reverse();
+        for seed in 0..8 {
+            let ordered = selection_order_with_rng(&records, &mut StdRng::seed_from_u64(seed));
+            let again = selection_order_with_rng(&reversed, &mut StdRng::seed_from_u64(seed));
+            assert_eq!(ordered, again);
+            assert_eq!(ordered.len(), 4);
+            assert_eq!(ordered[3].target().to_string(), "d.example.com.");
+        }
+    }
+
+    #[test]
+    fn test_selection_order_weights() {
+        let heavy = SRV::new(1, 1000, 80, Name::from_ascii("heavy.example.com.").unwrap());
+        let light = SRV::new(1, 1, 80, Name::from_ascii("light.example.com.").unwrap());
+        let zero = SRV::new(1, 0, 80, Name::from_ascii("zero.example.com.").unwrap());
+        let records = vec![zero.clone(), light.clone(), heavy.clone()];
+
+        // seeded, so this is reproducible, the odds of heavy not being first are 2 in 1002 each time
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut heavy_first = 0;
+        for _ in 0..100 {
+            let ordered = selection_order_with_rng(&records, &mut rng);
+            assert_eq!(ordered.len(), 3);
+            if ordered[0] == &heavy {
+                heavy_first += 1;
+            }
+        }
+
+        assert!(heavy_first > 90, "heavy was first {} times", heavy_first);
+    }
 }
@@ -8,23 +8,12 @@
 //! SSHFP records for SSH public key fingerprints
 use std::fmt;
 
-use data_encoding::{Encoding, Specification};
-use lazy_static::lazy_static;
-
 use crate::error::*;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_hex;
 
-lazy_static! {
-    /// HEX formatting specific to TLSA and SSHFP encodings
-    pub static ref HEX: Encoding = {
-        let mut spec = Specification::new();
-        spec.symbols.push_str("0123456789abcdef");
-        spec.ignore.push_str(" \t\r\n");
-        spec.translate.from.push_str("ABCDEF");
-        spec.translate.to.push_str("abcdef");
-        spec.encoding().expect("error in sshfp HEX encoding")
-    };
-}
+/// HEX formatting used by TLSA and SSHFP encodings, see `serialize::txt::from_hex`
+pub use crate::serialize::txt::HEX;
 
 /// [RFC 4255](https://tools.ietf.org/html/rfc4255#section-3.1)
 ///
@@ -258,7 +247,7 @@ impl fmt::Display for SSHFP {
             "{algorithm} {ty} {fingerprint}",
             algorithm = u8::from(self.algorithm),
             ty = u8::from(self.fingerprint_type),
-            fingerprint = HEX.encode(&self.fingerprint),
+            fingerprint = to_hex(&self.fingerprint),
         )
     }
 }
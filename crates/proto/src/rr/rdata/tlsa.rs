@@ -8,10 +8,9 @@
 //! TLSA records for storing TLS certificate validation information
 use std::fmt;
 
-use super::sshfp;
-
 use crate::error::*;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_hex;
 
 /// [RFC 6698, DNS-Based Authentication for TLS](https://tools.ietf.org/html/rfc6698#section-2.1)
 ///
@@ -429,7 +428,7 @@ impl fmt::Display for TLSA {
             usage = u8::from(self.cert_usage),
             selector = u8::from(self.selector),
             matching = u8::from(self.matching),
-            cert = sshfp::HEX.encode(&self.cert_data),
+            cert = to_hex(&self.cert_data),
         )
     }
 }
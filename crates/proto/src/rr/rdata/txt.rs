@@ -94,13 +94,13 @@ impl TXT {
 
 /// Read the RData from the given Decoder
 pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoResult<TXT> {
-    let data_len = decoder.len();
     let mut strings = Vec::with_capacity(1);
 
     // no unsafe usage of rdata length after this point
     let rdata_length =
         rdata_length.map(|u| u as usize).unverified(/*used as a higher bound, safely*/);
-    while data_len - decoder.len() < rdata_length {
+    let mut decoder = decoder.read_bounded(rdata_length)?;
+    while !decoder.is_empty() {
         let string =
             decoder.read_character_data()?.unverified(/*any data should be validate in TXT usage*/);
         strings.push(string.to_vec().into_boxed_slice());
@@ -119,6 +119,22 @@ pub fn emit(encoder: &mut BinEncoder<'_>, txt: &TXT) -> ProtoResult<()> {
     Ok(())
 }
 
+/// Writes a `<character-string>` in its quoted presentation form
+///
+/// `"` and `\` are escaped with a backslash, and any byte outside of printable ASCII is written
+/// as a decimal `\DDD` escape, see [RFC 1035, section 5.1](https://tools.ietf.org/html/rfc1035#section-5.1)
+pub(crate) fn fmt_character_string(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    f.write_str("\"")?;
+    for &byte in data {
+        match byte {
+            b'"' | b'\\' => write!(f, "\\{}", byte as char)?,
+            b' '..=b'~' => write!(f, "{}", byte as char)?,
+            _ => write!(f, "\\{:03}", byte)?,
+        }
+    }
+    f.write_str("\"")
+}
+
 impl fmt::Display for TXT {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         for txt in self.txt_data.iter() {
@@ -169,4 +185,19 @@ mod tests {
         let read_rdata = read(&mut decoder, restrict).expect("Decoding error");
         assert_eq!(rdata, read_rdata);
     }
+
+    #[test]
+    fn test_read_stops_at_rdata_length() {
+        // two strings, followed by bytes that aren't part of the rdata
+        let bytes = b"\x03abc\x02de\x03xyz";
+        let mut decoder = BinDecoder::new(bytes);
+
+        let rdata = read(&mut decoder, Restrict::new(7)).expect("Decoding error");
+        assert_eq!(rdata, TXT::from_bytes(vec![&b"abc"[..], b"de"]));
+        assert_eq!(decoder.remaining(), 4);
+
+        // a string can't run past the end of the rdata
+        let mut decoder = BinDecoder::new(bytes);
+        assert!(read(&mut decoder, Restrict::new(6)).is_err());
+    }
 }
@@ -0,0 +1,170 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! uniform resource identifier records for mapping names to URIs
+use std::fmt;
+
+use super::txt::fmt_character_string;
+use crate::error::*;
+use crate::serialize::binary::*;
+
+/// [RFC 7553, The Uniform Resource Identifier (URI) DNS Resource Record, June 2015](https://tools.ietf.org/html/rfc7553#section-4.5)
+///
+/// ```text
+/// 4.5.  URI RDATA Wire Format
+///
+///    The RDATA for a URI RR consists of a 2-octet Priority field, a
+///    2-octet Weight field, and a variable-length Target field.
+///
+///    Priority and Weight are unsigned integers in network byte order.
+///
+///    The remaining data in the RDATA contains the Target field.  The
+///    Target field contains the URI as a sequence of octets (without the
+///    enclosing double-quote characters used in the presentation format).
+///
+///    The length of the Target field MUST be greater than zero.
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct URI {
+    priority: u16,
+    weight: u16,
+    target: String,
+}
+
+impl URI {
+    /// Creates a new URI record data.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - lower values are preferred, as with SRV
+    /// * `weight` - relative weight for entries of the same priority
+    /// * `target` - the URI, this is not validated beyond being non-empty on read and parse
+    pub fn new(priority: u16, weight: u16, target: String) -> Self {
+        URI {
+            priority,
+            weight,
+            target,
+        }
+    }
+
+    /// The priority of the target, lower values are preferred
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// The relative weight for entries with the same priority
+    pub fn weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// The target URI, exactly as it appears in the record
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+}
+
+/// Read the RData from the given Decoder
+pub fn read(decoder: &mut BinDecoder<'_>, rdata_length: Restrict<u16>) -> ProtoResult<URI> {
+    let priority = decoder.read_u16()?.unverified(/*any u16 is valid*/);
+    let weight = decoder.read_u16()?.unverified(/*any u16 is valid*/);
+
+    let target_len = rdata_length
+        .map(usize::from)
+        .checked_sub(4)
+        .map_err(|_| ProtoError::from("invalid rdata length in URI"))?
+        .verify_unwrap(|len| *len > 0)
+        .map_err(|_| ProtoError::from("URI target must not be empty"))?;
+    let target = decoder.read_vec(target_len)?.unverified(/*validated as UTF-8 below*/);
+    let target = String::from_utf8(target)?;
+
+    Ok(URI::new(priority, weight, target))
+}
+
+/// Write the RData from the given Decoder
+pub fn emit(encoder: &mut BinEncoder<'_>, uri: &URI) -> ProtoResult<()> {
+    encoder.emit_u16(uri.priority())?;
+    encoder.emit_u16(uri.weight())?;
+    encoder.emit_vec(uri.target().as_bytes())
+}
+
+/// [RFC 7553, The Uniform Resource Identifier (URI) DNS Resource Record, June 2015](https://tools.ietf.org/html/rfc7553#section-4.4)
+///
+/// ```text
+/// 4.4.  URI RR Presentation Format
+///
+///    The presentation format of the RDATA portion is as follows:
+///
+///    o  The Priority field is represented as an integer.
+///
+///    o  The Weight field is represented as an integer.
+///
+///    o  The Target field is represented as a <character-string>
+///       enclosed in double-quote characters
+///
+///    $ORIGIN example.com.
+///    _ftp._tcp IN URI 10 1 "ftp://ftp1.example.com/public"
+/// ```
+impl fmt::Display for URI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{priority} {weight} ",
+            priority = self.priority,
+            weight = self.weight
+        )?;
+        fmt_character_string(f, self.target.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::dbg_macro, clippy::print_stdout)]
+
+    use super::*;
+
+    #[test]
+    fn test() {
+        let rdata = URI::new(
+            10,
+            1,
+            "https://www.example.com/path?query=a;b&c=%20#frag".to_string(),
+        );
+
+        let mut bytes = Vec::new();
+        let mut encoder: BinEncoder<'_> = BinEncoder::new(&mut bytes);
+        assert!(emit(&mut encoder, &rdata).is_ok());
+        let bytes = encoder.into_bytes();
+
+        println!("bytes: {:?}", bytes);
+        assert_eq!(&bytes[..4], &[0, 10, 0, 1]);
+        assert_eq!(&bytes[4..], rdata.target().as_bytes());
+
+        let mut decoder: BinDecoder<'_> = BinDecoder::new(bytes);
+        let read_rdata =
+            read(&mut decoder, Restrict::new(bytes.len() as u16)).expect("Decoding error");
+        assert_eq!(rdata, read_rdata);
+    }
+
+    #[test]
+    fn test_read_bad_target() {
+        // empty target
+        let bytes = [0, 10, 0, 1];
+        let mut decoder = BinDecoder::new(&bytes);
+        assert!(read(&mut decoder, Restrict::new(bytes.len() as u16)).is_err());
+
+        // invalid UTF-8
+        let bytes = [0, 10, 0, 1, 0xff];
+        let mut decoder = BinDecoder::new(&bytes);
+        assert!(read(&mut decoder, Restrict::new(bytes.len() as u16)).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let rdata = URI::new(10, 1, "ftp://ftp1.example.com/public".to_string());
+        assert_eq!(rdata.to_string(), r#"10 1 "ftp://ftp1.example.com/public""#);
+    }
+}
@@ -27,10 +27,13 @@ use log::{trace, warn};
 
 use super::domain::Name;
 use super::rdata;
-use super::rdata::{CAA, MX, NAPTR, NULL, OPENPGPKEY, OPT, SOA, SRV, SSHFP, TLSA, TXT};
+use super::rdata::{
+    CAA, HINFO, LOC, MX, NAPTR, NULL, OPENPGPKEY, OPT, SOA, SRV, SSHFP, TLSA, TXT, URI,
+};
 use super::record_type::RecordType;
 use crate::error::*;
 use crate::serialize::binary::*;
+use crate::serialize::txt::to_hex;
 
 #[cfg(feature = "dnssec")]
 use super::dnssec::rdata::DNSSECRData;
@@ -169,6 +172,42 @@ pub enum RData {
     /// ```
     CNAME(Name),
 
+    /// ```text
+    /// 3.3.2. HINFO RDATA format
+    ///
+    ///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///     /                      CPU                      /
+    ///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///     /                       OS                      /
+    ///     +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    /// ```
+    HINFO(HINFO),
+
+    /// ```text
+    /// 2. RDATA Format
+    ///
+    ///        MSB                                           LSB
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///       0|        VERSION        |         SIZE          |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///       2|       HORIZ PRE       |       VERT PRE        |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///       4|                   LATITUDE                    |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///       6|                   LATITUDE                    |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///       8|                   LONGITUDE                   |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///      10|                   LONGITUDE                   |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///      12|                   ALTITUDE                    |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///      14|                   ALTITUDE                    |
+    ///        +--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+--+
+    ///                                                    (octet)
+    /// ```
+    LOC(LOC),
+
     /// ```text
     /// 3.3.9. MX RDATA format
     ///
@@ -579,6 +618,14 @@ pub enum RData {
     /// ```
     TXT(TXT),
 
+    /// ```text
+    /// 4.5.  URI RDATA Wire Format
+    ///
+    ///    The RDATA for a URI RR consists of a 2-octet Priority field, a
+    ///    2-octet Weight field, and a variable-length Target field.
+    /// ```
+    URI(URI),
+
     /// A DNSSEC- or SIG(0)- specific record. See `DNSSECRData` for details.
     ///
     /// These types are in `DNSSECRData` to make them easy to disable when
@@ -642,6 +689,14 @@ impl RData {
                 trace!("reading CNAME");
                 rdata::name::read(decoder).map(RData::CNAME)
             }
+            RecordType::HINFO => {
+                trace!("reading HINFO");
+                rdata::hinfo::read(decoder).map(RData::HINFO)
+            }
+            RecordType::LOC => {
+                trace!("reading LOC");
+                rdata::loc::read(decoder).map(RData::LOC)
+            }
             RecordType::ZERO => {
                 trace!("reading EMPTY");
                 return Ok(RData::ZERO);
@@ -694,6 +749,10 @@ impl RData {
                 trace!("reading TXT");
                 rdata::txt::read(decoder, rdata_length).map(RData::TXT)
             }
+            RecordType::URI => {
+                trace!("reading URI");
+                rdata::uri::read(decoder, rdata_length).map(RData::URI)
+            }
             #[cfg(feature = "dnssec")]
             RecordType::DNSSEC(record_type) => {
                 DNSSECRData::read(decoder, record_type, rdata_length).map(RData::DNSSEC)
@@ -790,8 +849,12 @@ impl RData {
         match *self {
             RData::A(address) => rdata::a::emit(encoder, address),
             RData::AAAA(ref address) => rdata::aaaa::emit(encoder, address),
+            // the target is never compressed, but is only lowercased for canonical form
             RData::ANAME(ref name) => {
-                encoder.with_canonical_names(|encoder| rdata::name::emit(encoder, name))
+                let is_canonical_names = encoder.is_canonical_names();
+                encoder.with_canonical_names(|encoder| {
+                    name.emit_with_lowercase(encoder, is_canonical_names)
+                })
             }
             RData::CAA(ref caa) => {
                 encoder.with_canonical_names(|encoder| rdata::caa::emit(encoder, caa))
@@ -800,12 +863,12 @@ impl RData {
             RData::CNAME(ref name) | RData::NS(ref name) | RData::PTR(ref name) => {
                 rdata::name::emit(encoder, name)
             }
+            RData::HINFO(ref hinfo) => rdata::hinfo::emit(encoder, hinfo),
+            RData::LOC(ref loc) => rdata::loc::emit(encoder, loc),
             RData::ZERO => Ok(()),
             // to_lowercase for rfc4034 and rfc6840
             RData::MX(ref mx) => rdata::mx::emit(encoder, mx),
-            RData::NAPTR(ref naptr) => {
-                encoder.with_canonical_names(|encoder| rdata::naptr::emit(encoder, naptr))
-            }
+            RData::NAPTR(ref naptr) => rdata::naptr::emit(encoder, naptr),
             RData::NULL(ref null) => rdata::null::emit(encoder, null),
             RData::OPENPGPKEY(ref openpgpkey) => {
                 encoder.with_canonical_names(|encoder| rdata::openpgpkey::emit(encoder, openpgpkey))
@@ -814,9 +877,7 @@ impl RData {
             // to_lowercase for rfc4034 and rfc6840
             RData::SOA(ref soa) => rdata::soa::emit(encoder, soa),
             // to_lowercase for rfc4034 and rfc6840
-            RData::SRV(ref srv) => {
-                encoder.with_canonical_names(|encoder| rdata::srv::emit(encoder, srv))
-            }
+            RData::SRV(ref srv) => rdata::srv::emit(encoder, srv),
             RData::SSHFP(ref sshfp) => {
                 encoder.with_canonical_names(|encoder| rdata::sshfp::emit(encoder, sshfp))
             }
@@ -824,8 +885,9 @@ impl RData {
                 encoder.with_canonical_names(|encoder| rdata::tlsa::emit(encoder, tlsa))
             }
             RData::TXT(ref txt) => rdata::txt::emit(encoder, txt),
+            RData::URI(ref uri) => rdata::uri::emit(encoder, uri),
             #[cfg(feature = "dnssec")]
-            RData::DNSSEC(ref rdata) => encoder.with_canonical_names(|encoder| rdata.emit(encoder)),
+            RData::DNSSEC(ref rdata) => rdata.emit(encoder),
             RData::Unknown { ref rdata, .. } => rdata::null::emit(encoder, rdata),
         }
     }
@@ -838,6 +900,8 @@ impl RData {
             RData::ANAME(..) => RecordType::ANAME,
             RData::CAA(..) => RecordType::CAA,
             RData::CNAME(..) => RecordType::CNAME,
+            RData::HINFO(..) => RecordType::HINFO,
+            RData::LOC(..) => RecordType::LOC,
             RData::MX(..) => RecordType::MX,
             RData::NAPTR(..) => RecordType::NAPTR,
             RData::NS(..) => RecordType::NS,
@@ -850,6 +914,7 @@ impl RData {
             RData::SSHFP(..) => RecordType::SSHFP,
             RData::TLSA(..) => RecordType::TLSA,
             RData::TXT(..) => RecordType::TXT,
+            RData::URI(..) => RecordType::URI,
             #[cfg(feature = "dnssec")]
             RData::DNSSEC(ref rdata) => RecordType::DNSSEC(DNSSECRData::to_record_type(rdata)),
             RData::Unknown { code, .. } => RecordType::Unknown(code),
@@ -880,6 +945,8 @@ impl fmt::Display for RData {
             RData::CAA(ref caa) => w(f, caa),
             // to_lowercase for rfc4034 and rfc6840
             RData::CNAME(ref name) | RData::NS(ref name) | RData::PTR(ref name) => w(f, name),
+            RData::HINFO(ref hinfo) => w(f, hinfo),
+            RData::LOC(ref loc) => w(f, loc),
             RData::ZERO => Ok(()),
             // to_lowercase for rfc4034 and rfc6840
             RData::MX(ref mx) => w(f, mx),
@@ -895,9 +962,18 @@ impl fmt::Display for RData {
             RData::SSHFP(ref sshfp) => w(f, sshfp),
             RData::TLSA(ref tlsa) => w(f, tlsa),
             RData::TXT(ref txt) => w(f, txt),
+            RData::URI(ref uri) => w(f, uri),
             #[cfg(feature = "dnssec")]
             RData::DNSSEC(ref rdata) => w(f, rdata),
-            RData::Unknown { ref rdata, .. } => w(f, rdata),
+            // RFC 3597 generic encoding, `\# <length> <hex data>`
+            RData::Unknown { ref rdata, .. } => {
+                let data = rdata.anything().unwrap_or(&[]);
+                write!(f, "\\# {}", data.len())?;
+                if !data.is_empty() {
+                    write!(f, " {}", to_hex(data))?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -934,6 +1010,52 @@ impl Ord for RData {
     }
 }
 
+/// A type of RData which has its own variant in `RData`, used to select records by type
+///
+/// See `resource::records_of_type`. The name only types, such as CNAME and NS, all hold a `Name`
+///  so they can't be told apart by the Rust type and are not included.
+pub trait RDataType: Sized {
+    /// The record type of records holding this data
+    fn record_type() -> RecordType;
+
+    /// Returns the data if the RData is of this type
+    fn try_borrow(rdata: &RData) -> Option<&Self>;
+}
+
+macro_rules! rdata_type {
+    ($data:ty, $variant:ident) => {
+        impl RDataType for $data {
+            fn record_type() -> RecordType {
+                RecordType::$variant
+            }
+
+            fn try_borrow(rdata: &RData) -> Option<&Self> {
+                match rdata {
+                    RData::$variant(data) => Some(data),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+rdata_type!(Ipv4Addr, A);
+rdata_type!(Ipv6Addr, AAAA);
+rdata_type!(CAA, CAA);
+rdata_type!(HINFO, HINFO);
+rdata_type!(LOC, LOC);
+rdata_type!(MX, MX);
+rdata_type!(NAPTR, NAPTR);
+rdata_type!(NULL, NULL);
+rdata_type!(OPENPGPKEY, OPENPGPKEY);
+rdata_type!(OPT, OPT);
+rdata_type!(SOA, SOA);
+rdata_type!(SRV, SRV);
+rdata_type!(SSHFP, SSHFP);
+rdata_type!(TLSA, TLSA);
+rdata_type!(TXT, TXT);
+rdata_type!(URI, URI);
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::dbg_macro, clippy::print_stdout)]
@@ -944,7 +1066,7 @@ mod tests {
 
     use super::*;
     use crate::rr::domain::Name;
-    use crate::rr::rdata::{MX, SOA, SRV, TXT};
+    use crate::rr::rdata::{MX, NULL, SOA, SRV, TXT};
     use crate::serialize::binary::bin_tests::test_emit_data_set;
     #[allow(clippy::useless_attribute)]
     #[allow(unused)]
@@ -1025,6 +1147,13 @@ mod tests {
                     b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
                 ],
             ),
+            (
+                RData::Unknown {
+                    code: 65280,
+                    rdata: NULL::with(vec![0x00, 0x01, 0xFE, 0xFF]),
+                },
+                vec![0x00, 0x01, 0xFE, 0xFF],
+            ),
         ]
     }
 
@@ -1113,6 +1242,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unknown_round_trip() {
+        for data in &[vec![], vec![0xDE, 0xAD, 0xBE, 0xEF]] {
+            let mut decoder = BinDecoder::new(data);
+            let rdata = RData::read(
+                &mut decoder,
+                RecordType::Unknown(65280),
+                Restrict::new(data.len() as u16),
+            )
+            .unwrap();
+            assert_eq!(rdata.to_record_type(), RecordType::Unknown(65280));
+
+            let mut bytes = Vec::new();
+            let mut encoder = BinEncoder::new(&mut bytes);
+            rdata.emit(&mut encoder).unwrap();
+            assert_eq!(&bytes, data);
+        }
+    }
+
+    #[test]
+    fn test_unknown_display() {
+        let rdata = RData::Unknown {
+            code: 9999,
+            rdata: NULL::with(vec![0xAB, 0xCD, 0xEF, 0x01, 0x23, 0x45]),
+        };
+        assert_eq!(rdata.to_string(), "\\# 6 abcdef012345");
+
+        let rdata = RData::Unknown {
+            code: 9999,
+            rdata: NULL::new(),
+        };
+        assert_eq!(rdata.to_string(), "\\# 0");
+    }
+
+    #[test]
+    fn test_read_compressed_name() {
+        // example.com. at offset 0, followed by the rdata: www + pointer to offset 0
+        let bytes: &[u8] = &[
+            7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0, 3, b'w', b'w',
+            b'w', 0xC0, 0x00,
+        ];
+        let target = Name::from_ascii("www.example.com.").unwrap();
+
+        for record_type in &[RecordType::CNAME, RecordType::NS, RecordType::PTR] {
+            let mut decoder = BinDecoder::new(bytes);
+            decoder.read_slice(13).expect("skip origin");
+
+            let rdata = RData::read(&mut decoder, *record_type, Restrict::new(6)).unwrap();
+            assert_eq!(rdata.to_record_type(), *record_type);
+            match rdata {
+                RData::CNAME(ref name) | RData::NS(ref name) | RData::PTR(ref name) => {
+                    assert_eq!(*name, target)
+                }
+                _ => panic!("unexpected rdata: {:?}", rdata),
+            }
+        }
+    }
+
+    #[test]
+    fn test_emit_preserves_case() {
+        let target = Name::from_ascii("Mail.ExAmPlE.com.").unwrap();
+        let rdatas = vec![
+            RData::ANAME(target.clone()),
+            RData::CNAME(target.clone()),
+            RData::MX(MX::new(10, target.clone())),
+            RData::SRV(SRV::new(1, 2, 3, target)),
+        ];
+
+        for rdata in rdatas {
+            // names are written as they are stored
+            let mut bytes = Vec::new();
+            let mut encoder = BinEncoder::new(&mut bytes);
+            rdata.emit(&mut encoder).unwrap();
+            assert!(
+                bytes.ends_with(b"\x04Mail\x07ExAmPlE\x03com\x00"),
+                "{:?}",
+                rdata
+            );
+
+            // and lowercased in canonical form
+            let mut bytes = Vec::new();
+            let mut encoder = BinEncoder::new(&mut bytes);
+            encoder.set_canonical_names(true);
+            rdata.emit(&mut encoder).unwrap();
+            assert!(
+                bytes.ends_with(b"\x04mail\x07example\x03com\x00"),
+                "{:?}",
+                rdata
+            );
+        }
+    }
+
     fn record_type_from_rdata(rdata: &RData) -> crate::rr::record_type::RecordType {
         match *rdata {
             RData::A(..) => RecordType::A,
@@ -1120,6 +1341,8 @@ mod tests {
             RData::ANAME(..) => RecordType::ANAME,
             RData::CAA(..) => RecordType::CAA,
             RData::CNAME(..) => RecordType::CNAME,
+            RData::HINFO(..) => RecordType::HINFO,
+            RData::LOC(..) => RecordType::LOC,
             RData::MX(..) => RecordType::MX,
             RData::NAPTR(..) => RecordType::NAPTR,
             RData::NS(..) => RecordType::NS,
@@ -1132,6 +1355,7 @@ mod tests {
             RData::SSHFP(..) => RecordType::SSHFP,
             RData::TLSA(..) => RecordType::TLSA,
             RData::TXT(..) => RecordType::TXT,
+            RData::URI(..) => RecordType::URI,
             #[cfg(feature = "dnssec")]
             RData::DNSSEC(ref rdata) => RecordType::DNSSEC(rdata.to_record_type()),
             RData::Unknown { code, .. } => RecordType::Unknown(code),
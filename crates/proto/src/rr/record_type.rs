@@ -62,11 +62,14 @@ pub enum RecordType {
     //  DHCID,      // 49 RFC 4701 DHCP identifier
     //  DNAME,      // 39 RFC 2672 Delegation Name
     //  HIP,        // 55 RFC 5205 Host Identity Protocol
+    /// RFC 1035[1] Host information
+    HINFO,
     //  IPSECKEY,   // 45 RFC 4025 IPsec Key
     /// RFC 1996 Incremental Zone Transfer
     IXFR,
     //  KX,         // 36 RFC 2230 Key eXchanger record
-    //  LOC,        // 29 RFC 1876 Location record
+    /// RFC 1876 Location record
+    LOC,
     /// RFC 1035[1] Mail exchange record
     MX,
     /// RFC 3403 Naming Authority Pointer
@@ -95,6 +98,8 @@ pub enum RecordType {
     //  TSIG,       // 250 RFC 2845 Transaction Signature
     /// RFC 1035[1] Text record
     TXT,
+    /// RFC 7553 Uniform Resource Identifier
+    URI,
 
     /// A DNSSEC- or SIG(0)- specific record type.
     ///
@@ -169,6 +174,8 @@ impl FromStr for RecordType {
             "ANAME" => Ok(RecordType::ANAME),
             "CAA" => Ok(RecordType::CAA),
             "CNAME" => Ok(RecordType::CNAME),
+            "HINFO" => Ok(RecordType::HINFO),
+            "LOC" => Ok(RecordType::LOC),
             "NULL" => Ok(RecordType::NULL),
             "MX" => Ok(RecordType::MX),
             "NAPTR" => Ok(RecordType::NAPTR),
@@ -180,12 +187,18 @@ impl FromStr for RecordType {
             "SSHFP" => Ok(RecordType::SSHFP),
             "TLSA" => Ok(RecordType::TLSA),
             "TXT" => Ok(RecordType::TXT),
+            "URI" => Ok(RecordType::URI),
             "ANY" | "*" => Ok(RecordType::ANY),
             "AXFR" => Ok(RecordType::AXFR),
+            "IXFR" => Ok(RecordType::IXFR),
             #[cfg(feature = "dnssec")]
-            "DNSKEY" | "DS" | "KEY" | "NSEC" | "NSEC3" | "NSEC3PARAM" | "RRSIG" | "SIG" => {
-                Ok(RecordType::DNSSEC(str.parse()?))
-            }
+            "CDNSKEY" | "CDS" | "DNSKEY" | "DS" | "KEY" | "NSEC" | "NSEC3" | "NSEC3PARAM"
+            | "RRSIG" | "SIG" => Ok(RecordType::DNSSEC(str.parse()?)),
+            // RFC 3597 generic type names, e.g. TYPE65280
+            _ if str.starts_with("TYPE") => str[4..]
+                .parse::<u16>()
+                .map(RecordType::from)
+                .map_err(|_| ProtoErrorKind::UnknownRecordTypeStr(str.to_string()).into()),
             _ => Err(ProtoErrorKind::UnknownRecordTypeStr(str.to_string()).into()),
         }
     }
@@ -208,9 +221,12 @@ impl From<u16> for RecordType {
             65305 => RecordType::ANAME,
             255 => RecordType::ANY,
             252 => RecordType::AXFR,
+            251 => RecordType::IXFR,
             257 => RecordType::CAA,
             5 => RecordType::CNAME,
             0 => RecordType::ZERO,
+            13 => RecordType::HINFO,
+            29 => RecordType::LOC,
             15 => RecordType::MX,
             35 => RecordType::NAPTR,
             2 => RecordType::NS,
@@ -223,7 +239,10 @@ impl From<u16> for RecordType {
             44 => RecordType::SSHFP,
             52 => RecordType::TLSA,
             16 => RecordType::TXT,
+            256 => RecordType::URI,
             #[cfg(feature = "dnssec")]
+            60/*CDNSKEY*/ |
+            59/*CDS*/ |
             48/*DNSKEY*/ |
             43/*DS*/ |
             25/*KEY*/ |
@@ -280,7 +299,9 @@ impl From<RecordType> for &'static str {
             RecordType::CAA => "CAA",
             RecordType::CNAME => "CNAME",
             RecordType::ZERO => "",
+            RecordType::HINFO => "HINFO",
             RecordType::IXFR => "IXFR",
+            RecordType::LOC => "LOC",
             RecordType::MX => "MX",
             RecordType::NAPTR => "NAPTR",
             RecordType::NS => "NS",
@@ -293,6 +314,7 @@ impl From<RecordType> for &'static str {
             RecordType::SSHFP => "SSHFP",
             RecordType::TLSA => "TLSA",
             RecordType::TXT => "TXT",
+            RecordType::URI => "URI",
             #[cfg(feature = "dnssec")]
             RecordType::DNSSEC(rt) => rt.into(),
             RecordType::Unknown(_) => "Unknown",
@@ -321,7 +343,9 @@ impl From<RecordType> for u16 {
             RecordType::CAA => 257,
             RecordType::CNAME => 5,
             RecordType::ZERO => 0,
+            RecordType::HINFO => 13,
             RecordType::IXFR => 251,
+            RecordType::LOC => 29,
             RecordType::MX => 15,
             RecordType::NAPTR => 35,
             RecordType::NS => 2,
@@ -334,6 +358,7 @@ impl From<RecordType> for u16 {
             RecordType::SSHFP => 44,
             RecordType::TLSA => 52,
             RecordType::TXT => 16,
+            RecordType::URI => 256,
             #[cfg(feature = "dnssec")]
             RecordType::DNSSEC(rt) => rt.into(),
             RecordType::Unknown(code) => code,
@@ -357,7 +382,11 @@ impl Ord for RecordType {
 
 impl Display for RecordType {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-        f.write_str(Into::<&str>::into(*self))
+        match *self {
+            // RFC 3597 generic type names, so that the type survives a round trip through text
+            RecordType::Unknown(code) => write!(f, "TYPE{}", code),
+            _ => f.write_str(Into::<&str>::into(*self)),
+        }
     }
 }
 
@@ -418,6 +447,8 @@ mod tests {
             "ANAME",
             "CAA",
             "CNAME",
+            "HINFO",
+            "LOC",
             "NULL",
             "MX",
             "NAPTR",
@@ -429,12 +460,16 @@ mod tests {
             "SSHFP",
             "TLSA",
             "TXT",
+            "URI",
             "ANY",
             "AXFR",
+            "IXFR",
         ];
 
         #[cfg(feature = "dnssec")]
         let dnssec_record_names = &[
+            "CDNSKEY",
+            "CDS",
             "DNSKEY",
             "DS",
             "KEY",
@@ -454,4 +489,43 @@ mod tests {
             assert!(rtypes.insert(rtype));
         }
     }
+
+    #[test]
+    fn test_u16_round_trip() {
+        let known = &[
+            (1, RecordType::A),
+            (5, RecordType::CNAME),
+            (33, RecordType::SRV),
+            (251, RecordType::IXFR),
+            (252, RecordType::AXFR),
+            (256, RecordType::URI),
+            (257, RecordType::CAA),
+        ];
+
+        for (code, rtype) in known {
+            assert_eq!(RecordType::from(*code), *rtype);
+            assert_eq!(u16::from(*rtype), *code);
+        }
+
+        // SVCB and HTTPS are not supported yet
+        assert_eq!(RecordType::from(64), RecordType::Unknown(64));
+        assert_eq!(u16::from(RecordType::Unknown(65)), 65);
+
+        for code in 0..=u16::max_value() {
+            assert_eq!(u16::from(RecordType::from(code)), code);
+        }
+    }
+
+    #[test]
+    fn test_generic_type_name() {
+        let rtype: RecordType = "TYPE9999".parse().unwrap();
+        assert_eq!(rtype, RecordType::Unknown(9999));
+        assert_eq!(rtype.to_string(), "TYPE9999");
+
+        // known types may also be written generically
+        assert_eq!("TYPE1".parse::<RecordType>().unwrap(), RecordType::A);
+
+        assert!("TYPE".parse::<RecordType>().is_err());
+        assert!("TYPE65536".parse::<RecordType>().is_err());
+    }
 }
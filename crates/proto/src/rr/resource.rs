@@ -22,6 +22,7 @@ use std::fmt;
 use crate::error::*;
 use crate::rr::dns_class::DNSClass;
 use crate::rr::rdata::NULL;
+use crate::rr::record_data::RDataType;
 #[allow(deprecated)]
 use crate::rr::IntoRecordSet;
 use crate::rr::Name;
@@ -264,6 +265,51 @@ impl Record {
     pub fn into_data(self) -> RData {
         self.rdata
     }
+
+    /// The length of the record when emitted without name compression
+    ///
+    /// Compression only ever makes a record shorter, so this is an upper bound on the space the
+    ///  record takes in a message. Names that are never compressed, like an SRV target, count in
+    ///  full either way.
+    pub fn max_encoded_len(&self) -> ProtoResult<usize> {
+        let mut bytes = Vec::new();
+        let mut encoder = BinEncoder::new(&mut bytes);
+        encoder.set_canonical_names(true);
+        self.emit(&mut encoder)?;
+
+        Ok(bytes.len())
+    }
+}
+
+/// Returns the data of each record holding a `T`, skipping records of any other type
+///
+/// Records must be of `T::record_type()` as well as holding a `T`, so the empty `NULL` data of a
+///  record built with `Record::with`, e.g. for an update, is not returned for `NULL`.
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use trust_dns_proto::rr::rdata::SRV;
+/// use trust_dns_proto::rr::{records_of_type, Name, RData, Record};
+///
+/// let name = Name::from_str("_ldap._tcp.example.com.").unwrap();
+/// let target = Name::from_str("ldap.example.com.").unwrap();
+/// let answers = vec![
+///     Record::from_rdata(name.clone(), 300, RData::SRV(SRV::new(0, 5, 389, target.clone()))),
+///     Record::from_rdata(target, 300, RData::A([192, 0, 2, 1].into())),
+/// ];
+///
+/// let srvs = records_of_type::<SRV>(&answers).collect::<Vec<_>>();
+/// assert_eq!(srvs.len(), 1);
+/// assert_eq!(srvs[0].port(), 389);
+/// ```
+pub fn records_of_type<'a, T: RDataType + 'a>(
+    records: &'a [Record],
+) -> impl Iterator<Item = &'a T> + 'a {
+    records
+        .iter()
+        .filter(|record| record.rr_type() == T::record_type())
+        .filter_map(|record| T::try_borrow(record.rdata()))
 }
 
 #[allow(deprecated)]
@@ -275,7 +321,10 @@ impl IntoRecordSet for Record {
 
 impl BinEncodable for Record {
     fn emit(&self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
-        self.name_labels.emit(encoder)?;
+        // owner names are lowercased in canonical form, RFC 4034 section 6.2
+        let is_canonical_names = encoder.is_canonical_names();
+        self.name_labels
+            .emit_with_lowercase(encoder, is_canonical_names)?;
         self.rr_type.emit(encoder)?;
 
         #[cfg(not(feature = "mdns"))]
@@ -298,12 +347,8 @@ impl BinEncodable for Record {
         // write the RData
         self.rdata.emit(encoder)?;
 
-        // get the length written
-        let len = encoder.len_since_place(&place);
-        assert!(len <= u16::max_value() as usize);
-
         // replace the location with the length
-        place.replace(encoder, len as u16)?;
+        place.replace_with_len(encoder)?;
         Ok(())
     }
 }
@@ -314,7 +359,7 @@ impl<'r> BinDecodable<'r> for Record {
     fn read(decoder: &mut BinDecoder<'r>) -> ProtoResult<Record> {
         // NAME            an owner name, i.e., the name of the node to which this
         //                 resource record pertains.
-        let name_labels: Name = Name::read(decoder)?;
+        let name_labels: Name = decoder.read_name()?;
 
         // TYPE            two octets containing one of the RR TYPE codes.
         let record_type: RecordType = RecordType::read(decoder)?;
@@ -342,12 +387,12 @@ impl<'r> BinDecodable<'r> for Record {
             #[cfg(feature = "mdns")]
             {
                 let dns_class_value =
-                    decoder.read_u16()?.unverified(/*DNSClass::from_u16 will verify the value*/);
+                    decoder.read_u16()?.unverified(/*DNSClass::from is always valid*/);
                 if dns_class_value & MDNS_ENABLE_CACHE_FLUSH > 0 {
                     mdns_cache_flush = true;
-                    DNSClass::from_u16(dns_class_value & !MDNS_ENABLE_CACHE_FLUSH)?
+                    DNSClass::from(dns_class_value & !MDNS_ENABLE_CACHE_FLUSH)
                 } else {
-                    DNSClass::from_u16(dns_class_value)?
+                    DNSClass::from(dns_class_value)
                 }
             }
         };
@@ -368,8 +413,12 @@ impl<'r> BinDecodable<'r> for Record {
         let rd_length: u16 = decoder
             .read_u16()?
             .verify_unwrap(|u| (*u as usize) <= decoder.len())
-            .map_err(|_| {
-                ProtoError::from("rdata length too large for remaining bytes, need: {} remain: {}")
+            .map_err(|u| {
+                ProtoError::from(format!(
+                    "rdata length too large for remaining bytes, need: {} remain: {}",
+                    u,
+                    decoder.len()
+                ))
             })?;
 
         // this is to handle updates, RFC 2136, which uses 0 to indicate certain aspects of
@@ -382,7 +431,23 @@ impl<'r> BinDecodable<'r> for Record {
             //                according to the TYPE and CLASS of the resource record.
             // Adding restrict to the rdata length because it's used for many calculations later
             //  and must be validated before hand
-            RData::read(decoder, record_type, Restrict::new(rd_length))?
+            //
+            // the rdata is read from a decoder bounded to rd_length, so that it can never read
+            //  into the following record.
+            let mut rdata_decoder = decoder.read_bounded(rd_length as usize)?;
+            let rdata = RData::read(&mut rdata_decoder, record_type, Restrict::new(rd_length))?;
+
+            // anything left over means the length and the rdata disagree
+            if !rdata_decoder.is_empty() {
+                return Err(format!(
+                    "{} rdata length {} is longer than its data by {} bytes",
+                    record_type,
+                    rd_length,
+                    rdata_decoder.len()
+                )
+                .into());
+            }
+            rdata
         };
 
         Ok(Record {
@@ -574,6 +639,7 @@ mod tests {
 
     use super::*;
     use crate::rr::dns_class::DNSClass;
+    use crate::rr::rdata::{NULL, SRV};
     use crate::rr::record_data::RData;
     use crate::rr::record_type::RecordType;
     use crate::rr::Name;
@@ -581,6 +647,78 @@ mod tests {
     #[allow(unused)]
     use crate::serialize::binary::*;
 
+    #[test]
+    fn test_records_of_type() {
+        use std::net::Ipv6Addr;
+
+        use crate::rr::rdata::MX;
+
+        let name = Name::from_str("example.com.").unwrap();
+        let target = Name::from_str("mail.example.com.").unwrap();
+        let records = vec![
+            Record::from_rdata(name.clone(), 300, RData::MX(MX::new(10, target.clone()))),
+            Record::from_rdata(target.clone(), 300, RData::A(Ipv4Addr::new(192, 0, 2, 1))),
+            Record::from_rdata(name.clone(), 300, RData::CNAME(target.clone())),
+            Record::from_rdata(target.clone(), 300, RData::A(Ipv4Addr::new(192, 0, 2, 2))),
+            Record::from_rdata(
+                name.clone(),
+                300,
+                RData::SRV(SRV::new(0, 1, 25, target.clone())),
+            ),
+        ];
+
+        assert_eq!(
+            records_of_type::<Ipv4Addr>(&records).collect::<Vec<_>>(),
+            vec![&Ipv4Addr::new(192, 0, 2, 1), &Ipv4Addr::new(192, 0, 2, 2)]
+        );
+        assert_eq!(
+            records_of_type::<MX>(&records).collect::<Vec<_>>(),
+            vec![&MX::new(10, target.clone())]
+        );
+        assert_eq!(
+            records_of_type::<SRV>(&records).collect::<Vec<_>>(),
+            vec![&SRV::new(0, 1, 25, target)]
+        );
+        assert_eq!(records_of_type::<Ipv6Addr>(&records).count(), 0);
+        assert_eq!(records_of_type::<NULL>(&[]).count(), 0);
+
+        // a record's type must match as well as its data
+        let delete = Record::with(name.clone(), RecordType::A, 0);
+        assert_eq!(records_of_type::<NULL>(&[delete.clone()]).count(), 0);
+        assert_eq!(records_of_type::<Ipv4Addr>(&[delete]).count(), 0);
+        let null = Record::from_rdata(name, 0, RData::NULL(NULL::with(vec![1])));
+        assert_eq!(records_of_type::<NULL>(&[null]).count(), 1);
+
+        assert_eq!(<SRV as RDataType>::record_type(), RecordType::SRV);
+        assert_eq!(<Ipv4Addr as RDataType>::record_type(), RecordType::A);
+    }
+
+    #[test]
+    fn test_read_rdata_length_mismatch() {
+        // ., SRV, IN, TTL 300, then the rdata length and an SRV of 0 1 53 .
+        let header: &[u8] = &[0, 0, 33, 0, 1, 0, 0, 1, 44];
+        let srv: &[u8] = &[0, 0, 0, 1, 0, 53, 0];
+
+        let read = |rd_length: u16, rdata: &[u8]| {
+            let bytes = [header, &rd_length.to_be_bytes(), rdata].concat();
+            let mut decoder = BinDecoder::new(&bytes);
+            Record::read(&mut decoder)
+        };
+
+        let record = read(7, srv).expect("the rdata length is exact");
+        assert_eq!(
+            record.rdata(),
+            &RData::SRV(SRV::new(0, 1, 53, Name::root()))
+        );
+
+        // too short for the SRV body
+        assert!(read(6, srv).is_err());
+        assert!(read(4, srv).is_err());
+
+        // longer than the SRV body, even though the bytes are there
+        assert!(read(8, &[srv, &[0]].concat()).is_err());
+    }
+
     #[test]
     fn test_emit_and_read() {
         let mut record = Record::new();
@@ -604,6 +742,144 @@ mod tests {
         assert_eq!(got, record);
     }
 
+    #[test]
+    fn test_unknown_type_round_trip() {
+        #[rustfmt::skip]
+        let bytes: &[u8] = &[
+            1, b'a', 0,             // a.
+            0x27, 0x0F,             // type 9999
+            0x00, 0x01,             // IN
+            0x00, 0x00, 0x0E, 0x10, // ttl 3600
+            0x00, 0x05,             // rdlength
+            0xDE, 0xAD, 0xBE, 0xEF, 0x00,
+        ];
+
+        let mut decoder = BinDecoder::new(bytes);
+        let record = Record::read(&mut decoder).unwrap();
+        assert_eq!(record.rr_type(), RecordType::Unknown(9999));
+        assert_eq!(
+            *record.rdata(),
+            RData::Unknown {
+                code: 9999,
+                rdata: NULL::with(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x00]),
+            }
+        );
+
+        let mut vec_bytes: Vec<u8> = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut vec_bytes);
+            record.emit(&mut encoder).unwrap();
+        }
+        assert_eq!(&vec_bytes[..], bytes);
+    }
+
+    #[test]
+    fn test_srv_round_trip() {
+        let record = Record::from_rdata(
+            Name::from_str("_ldap._tcp.example.com.").unwrap(),
+            86400,
+            RData::SRV(SRV::new(
+                10,
+                20,
+                389,
+                Name::from_str("ldap.example.com.").unwrap(),
+            )),
+        );
+
+        let mut vec_bytes: Vec<u8> = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut vec_bytes);
+            record.emit(&mut encoder).unwrap();
+        }
+
+        #[rustfmt::skip]
+        let expected: &[u8] = &[
+            5, b'_', b'l', b'd', b'a', b'p', 4, b'_', b't', b'c', b'p',
+            7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
+            0x00, 0x21,             // SRV
+            0x00, 0x01,             // IN
+            0x00, 0x01, 0x51, 0x80, // ttl 86400
+            0x00, 0x18,             // rdlength 24
+            0x00, 0x0A, 0x00, 0x14, 0x01, 0x85,
+            // the target is not compressed, even though example.com. was already written
+            4, b'l', b'd', b'a', b'p', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e',
+            3, b'c', b'o', b'm', 0,
+        ];
+        assert_eq!(&vec_bytes[..], expected);
+
+        let mut decoder = BinDecoder::new(&vec_bytes);
+        let got = Record::read(&mut decoder).unwrap();
+        assert_eq!(got, record);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_read_rdata_bounded_by_length() {
+        let record = Record::from_rdata(
+            Name::from_str("a.").unwrap(),
+            5,
+            RData::A(Ipv4Addr::new(192, 168, 0, 1)),
+        );
+
+        let mut vec_bytes: Vec<u8> = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut vec_bytes);
+            record.emit(&mut encoder).unwrap();
+        }
+        assert_eq!(vec_bytes.len(), 17);
+
+        // understate the rdata length, the rest of the address would be read from the next record
+        vec_bytes[11..13].copy_from_slice(&[0, 2]);
+        let mut decoder = BinDecoder::new(&vec_bytes);
+        assert!(Record::read(&mut decoder).is_err());
+
+        // overstated lengths are rejected before reading
+        vec_bytes[11..13].copy_from_slice(&[0, 5]);
+        let mut decoder = BinDecoder::new(&vec_bytes);
+        assert!(Record::read(&mut decoder).is_err());
+    }
+
+    #[test]
+    fn test_emit_compresses_shared_suffix() {
+        let www = Record::from_rdata(
+            Name::from_str("www.example.com.").unwrap(),
+            5,
+            RData::A(Ipv4Addr::new(192, 168, 0, 1)),
+        );
+        let mail = Record::from_rdata(
+            Name::from_str("mail.example.com.").unwrap(),
+            5,
+            RData::A(Ipv4Addr::new(192, 168, 0, 2)),
+        );
+
+        let mut vec_bytes: Vec<u8> = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut vec_bytes);
+            www.emit(&mut encoder).unwrap();
+            mail.emit(&mut encoder).unwrap();
+        }
+
+        // www record is 31 bytes, then `mail` followed by a pointer to `example.com.` at offset 4
+        assert_eq!(vec_bytes.len(), 52);
+        assert_eq!(&vec_bytes[31..38], &[4, b'm', b'a', b'i', b'l', 0xC0, 0x04]);
+
+        let mut decoder = BinDecoder::new(&vec_bytes);
+        assert_eq!(Record::read(&mut decoder).unwrap(), www);
+        assert_eq!(Record::read(&mut decoder).unwrap(), mail);
+
+        // canonical form disables compression
+        let mut vec_bytes: Vec<u8> = Vec::with_capacity(512);
+        {
+            let mut encoder = BinEncoder::new(&mut vec_bytes);
+            encoder.set_canonical_names(true);
+            www.emit(&mut encoder).unwrap();
+            mail.emit(&mut encoder).unwrap();
+        }
+
+        assert_eq!(vec_bytes.len(), 63);
+        assert!(!vec_bytes.contains(&0xC0));
+    }
+
     #[test]
     fn test_order() {
         let mut record = Record::new();
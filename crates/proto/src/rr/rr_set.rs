@@ -10,6 +10,7 @@ use std::vec;
 
 use log::info;
 
+use crate::rr::rdata::soa::serial_greater_than;
 use crate::rr::{DNSClass, Name, RData, Record, RecordType};
 
 #[cfg(feature = "dnssec")]
@@ -295,9 +296,9 @@ impl RecordSet {
                     match soa_record.rdata() {
                         &RData::SOA(ref existing_soa) => {
                             if let RData::SOA(ref new_soa) = *record.rdata() {
-                                if new_soa.serial() <= existing_soa.serial() {
+                                if !serial_greater_than(new_soa.serial(), existing_soa.serial()) {
                                     info!(
-                                        "update ignored serial out of data: {:?} <= {:?}",
+                                        "update ignored serial out of date: {:?} <= {:?}",
                                         new_soa, existing_soa
                                     );
                                     return false;
@@ -678,6 +679,27 @@ mod test {
             .any(|ref x| x == &&same_serial));
     }
 
+    #[test]
+    fn test_insert_soa_serial_wraps() {
+        let name = Name::from_str("example.com.").unwrap();
+        let mut rr_set = RecordSet::new(&name, RecordType::SOA, 0);
+
+        let soa = |serial| {
+            Record::from_rdata(
+                name.clone(),
+                3600,
+                RData::SOA(SOA::new(Name::root(), Name::root(), serial, 1, 2, 3, 4)),
+            )
+        };
+
+        assert!(rr_set.insert(soa(u32::max_value()), 0));
+        // u32::MAX + 1 wraps to 0, which is the newer serial
+        assert!(rr_set.insert(soa(0), 0));
+        assert!(!rr_set.insert(soa(u32::max_value()), 0));
+        // 2^31 away is undefined in RFC 1982, and not accepted as newer
+        assert!(!rr_set.insert(soa(1 << 31), 0));
+    }
+
     #[test]
     fn test_insert_cname() {
         let name = Name::from_str("web.example.com.").unwrap();
@@ -21,9 +21,9 @@ use std::fmt::Debug;
 
 fn get_character_data() -> Vec<(&'static str, Vec<u8>)> {
     vec![
-        ("", vec![0]),                      // base case, only the root
-        ("a", vec![1, b'a']),               // a single 'a' label
-        ("bc", vec![2, b'b', b'c']),        // two labels, 'a.bc'
+        ("", vec![0]),                    // base case, only the root
+        ("a", vec![1, b'a']),             // a single 'a' label
+        ("bc", vec![2, b'b', b'c']),      // two labels, 'a.bc'
         ("♥", vec![3, 0xE2, 0x99, 0xA5]), // two labels utf8, 'a.♥'
     ]
 }
@@ -46,6 +46,82 @@ fn emit_character_data() {
     });
 }
 
+#[test]
+fn character_data_limits() {
+    // the maximum of 255 octets round trips
+    let max = vec![b'a'; 255];
+    let mut bytes: Vec<u8> = Vec::new();
+    {
+        let mut encoder = BinEncoder::new(&mut bytes);
+        encoder.emit_character_data(&max).unwrap();
+    }
+    assert_eq!(bytes.len(), 256);
+    assert_eq!(bytes[0], 255);
+
+    let mut decoder = BinDecoder::new(&bytes);
+    assert_eq!(
+        decoder.read_character_data().unwrap().unverified(),
+        &max[..]
+    );
+    assert!(decoder.is_empty());
+
+    // one more is rejected before anything is written
+    let too_long = vec![b'a'; 256];
+    let mut bytes: Vec<u8> = Vec::new();
+    {
+        let mut encoder = BinEncoder::new(&mut bytes);
+        match *encoder.emit_character_data(&too_long).unwrap_err().kind() {
+            ProtoErrorKind::CharacterDataTooLong { max: 255, len: 256 } => (),
+            ref e => panic!("unexpected error: {:?}", e),
+        }
+    }
+    assert!(bytes.is_empty());
+
+    // a length octet past the end of the buffer is an error
+    let bytes = vec![3, b'a', b'b'];
+    let mut decoder = BinDecoder::new(&bytes);
+    assert!(decoder.read_character_data().is_err());
+
+    // and so is one past the requested maximum
+    let bytes = vec![3, b'a', b'b', b'c'];
+    let mut decoder = BinDecoder::new(&bytes);
+    assert!(decoder.read_character_data_max(Some(2)).is_err());
+}
+
+fn get_u8_data() -> Vec<(u8, Vec<u8>)> {
+    vec![
+        (0, vec![0x00]),
+        (1, vec![0x01]),
+        (0x80, vec![0x80]),
+        (u8::max_value(), vec![0xFF]),
+    ]
+}
+
+#[test]
+fn read_u8() {
+    test_read_data_set(get_u8_data(), |mut d| d.read_u8().map(Restrict::unverified));
+}
+
+#[test]
+fn emit_u8() {
+    test_emit_data_set(get_u8_data(), |ref mut e, d| e.emit_u8(d));
+}
+
+#[test]
+fn u8_round_trip() {
+    let mut bytes: Vec<u8> = Vec::new();
+    {
+        let mut encoder = BinEncoder::new(&mut bytes);
+        encoder.emit_u8(0xA5).unwrap();
+    }
+    assert_eq!(bytes, vec![0xA5]);
+
+    let mut decoder = BinDecoder::new(&bytes);
+    assert_eq!(decoder.read_u8().unwrap().unverified(), 0xA5);
+    assert!(decoder.is_empty());
+    assert!(decoder.read_u8().is_err());
+}
+
 fn get_u16_data() -> Vec<(u16, Vec<u8>)> {
     vec![
         (0, vec![0x00, 0x00]),
@@ -103,6 +179,8 @@ fn get_u32_data() -> Vec<(u32, Vec<u8>)> {
         (u32::max_value(), vec![0xFF, 0xFF, 0xFF, 0xFF]),
         (2147483648, vec![0x80, 0x00, 0x00, 0x00]),
         (i32::max_value() as u32, vec![0x7F, 0xFF, 0xFF, 0xFF]),
+        // every byte distinct, the most significant first
+        (0x1234_5678, vec![0x12, 0x34, 0x56, 0x78]),
     ]
 }
 
@@ -118,6 +196,35 @@ fn emit_u32() {
     test_emit_data_set(get_u32_data(), |ref mut e, d| e.emit_u32(d));
 }
 
+#[test]
+fn u32_round_trip() {
+    let values = [0, 1, 0x1234_5678, 0x8000_0000, u32::max_value()];
+
+    let mut bytes: Vec<u8> = Vec::new();
+    {
+        let mut encoder = BinEncoder::new(&mut bytes);
+        for value in &values {
+            encoder.emit_u32(*value).unwrap();
+        }
+        // mixed with a u16, which must stay aligned to the same byte order
+        encoder.emit_u16(0xABCD).unwrap();
+    }
+    assert_eq!(bytes.len(), values.len() * 4 + 2);
+    assert_eq!(&bytes[8..12], &[0x12, 0x34, 0x56, 0x78]);
+
+    let mut decoder = BinDecoder::new(&bytes);
+    for value in &values {
+        assert_eq!(decoder.read_u32().unwrap().unverified(), *value);
+    }
+    assert_eq!(decoder.read_u16().unwrap().unverified(), 0xABCD);
+    assert!(decoder.is_empty());
+
+    // fewer than four bytes remaining is an error, and nothing is consumed
+    let mut decoder = BinDecoder::new(&[0x12, 0x34, 0x56]);
+    assert!(decoder.read_u32().is_err());
+    assert_eq!(decoder.remaining(), 3);
+}
+
 pub fn test_read_data_set<E, F>(data_set: Vec<(E, Vec<u8>)>, read_func: F)
 where
     E: PartialEq<E> + Debug,
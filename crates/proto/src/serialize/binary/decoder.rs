@@ -14,8 +14,16 @@
  * limitations under the License.
  */
 
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Mutex};
+
 use crate::error::{ProtoError, ProtoErrorKind, ProtoResult};
-use crate::serialize::binary::Restrict;
+use crate::rr::domain::Name;
+use crate::serialize::binary::{BinDecodable, Restrict};
 
 /// This is non-destructive to the inner buffer, b/c for pointer types we need to perform a reverse
 ///  seek to lookup names
@@ -27,6 +35,10 @@ use crate::serialize::binary::Restrict;
 pub struct BinDecoder<'a> {
     buffer: &'a [u8],
     index: usize,
+    // names already read, by their offset in the buffer, when interning is enabled. This is shared
+    //  with clones and bounded decoders, which read from the same buffer
+    #[cfg(feature = "std")]
+    names: Option<Arc<Mutex<HashMap<usize, Name>>>>,
 }
 
 impl<'a> BinDecoder<'a> {
@@ -36,7 +48,86 @@ impl<'a> BinDecoder<'a> {
     ///
     /// * `buffer` - buffer from which all data will be read
     pub fn new(buffer: &'a [u8]) -> Self {
-        BinDecoder { buffer, index: 0 }
+        BinDecoder {
+            buffer,
+            index: 0,
+            #[cfg(feature = "std")]
+            names: None,
+        }
+    }
+
+    /// If set to true, then `read_name` will share the labels of a name that is a compression
+    ///  pointer with the name already read at that offset, rather than allocating them again
+    ///
+    /// This is off by default, it is useful for large responses where many records share an owner.
+    ///  Decoders from `clone` and `read_bounded` share the names read with this one. Interning
+    ///  requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn set_intern_names(&mut self, intern_names: bool) {
+        if !intern_names {
+            self.names = None;
+        } else if self.names.is_none() {
+            self.names = Some(Arc::new(Mutex::new(HashMap::new())));
+        }
+    }
+
+    /// Returns true if the decoder is interning names
+    #[cfg(feature = "std")]
+    pub fn is_interning_names(&self) -> bool {
+        self.names.is_some()
+    }
+
+    /// Returns true if the decoder is interning names, this is never the case without `std`
+    #[cfg(not(feature = "std"))]
+    pub fn is_interning_names(&self) -> bool {
+        false
+    }
+
+    /// Reads a name, sharing its labels with the name it points to if interning is enabled
+    ///
+    /// Names are keyed by their offset, so a name that is only a pointer, e.g. a record owner
+    ///  pointing to the query name, is looked up without being decoded again.
+    ///
+    /// Only those pointer-only names are deduplicated. A name with labels of its own is always
+    ///  decoded into new labels, even if it is equal to a name already read, and so is a name
+    ///  whose labels end in a pointer.
+    pub fn read_name(&mut self) -> ProtoResult<Name> {
+        #[cfg(feature = "std")]
+        {
+            if self.names.is_some() {
+                return self.read_interned_name();
+            }
+        }
+
+        Name::read(self)
+    }
+
+    #[cfg(feature = "std")]
+    fn read_interned_name(&mut self) -> ProtoResult<Name> {
+        let names = match self.names {
+            Some(ref names) => Arc::clone(names),
+            None => return Name::read(self),
+        };
+
+        let start = self.index;
+        let pointer = self
+            .peek_u16()
+            .map(|p| p.unverified(/*only used as a key to names already read*/))
+            .ok()
+            .filter(|p| p & 0xC000 == 0xC000)
+            .map(|p| usize::from(p & 0x3FFF))
+            .filter(|offset| *offset < start);
+
+        if let Some(offset) = pointer {
+            if let Some(name) = names.lock()?.get(&offset).cloned() {
+                self.read_u16()?;
+                return Ok(name);
+            }
+        }
+
+        let name = Name::read(self)?;
+        names.lock()?.insert(pointer.unwrap_or(start), name.clone());
+        Ok(name)
     }
 
     /// Pop one byte from the buffer
@@ -50,27 +141,37 @@ impl<'a> BinDecoder<'a> {
         }
     }
 
-    /// Returns the number of bytes in the buffer
+    /// Returns the number of bytes remaining to be read
+    ///
+    /// For a decoder from `read_bounded`, this stops at the bound, e.g. the end of the rdata, and
+    ///  not the end of the message.
     ///
     /// ```
     /// use trust_dns_proto::serialize::binary::BinDecoder;
     ///
     /// let deadbeef = b"deadbeef";
     /// let mut decoder = BinDecoder::new(deadbeef);
-    /// assert_eq!(decoder.len(), 8);
+    /// assert_eq!(decoder.remaining(), 8);
     /// decoder.read_slice(7).unwrap();
-    /// assert_eq!(decoder.len(), 1);
+    /// assert_eq!(decoder.remaining(), 1);
     /// ```
-    pub fn len(&self) -> usize {
+    pub fn remaining(&self) -> usize {
         self.buffer.len().saturating_sub(self.index)
     }
 
-    /// Returns `true` if the buffer is empty
+    /// Returns the number of bytes remaining to be read, the same as `remaining`
+    ///
+    /// This is not the length of the whole buffer, bytes already read are not counted.
+    pub fn len(&self) -> usize {
+        self.remaining()
+    }
+
+    /// Returns `true` if there are no bytes remaining to be read
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        self.remaining() == 0
     }
 
-    /// Peed one byte forward, without moving the current index forward
+    /// Peek one byte forward, without moving the current index forward
     pub fn peek(&self) -> Option<Restrict<u8>> {
         if self.index < self.buffer.len() {
             Some(Restrict::new(self.buffer[self.index]))
@@ -79,6 +180,32 @@ impl<'a> BinDecoder<'a> {
         }
     }
 
+    /// Peek one byte forward, without moving the current index forward
+    ///
+    /// Unlike `peek`, this is an error if the end of the buffer has been reached
+    pub fn peek_u8(&self) -> ProtoResult<Restrict<u8>> {
+        self.peek()
+            .ok_or_else(|| ProtoError::from("unexpected end of input reached"))
+    }
+
+    /// Peek the next 2 bytes as a u16, without moving the current index forward
+    ///
+    /// ```
+    /// use trust_dns_proto::serialize::binary::BinDecoder;
+    ///
+    /// let bytes = [0xC0, 0x0C];
+    /// let mut decoder = BinDecoder::new(&bytes);
+    /// assert_eq!(decoder.peek_u16().unwrap().unverified(), 0xC00C);
+    /// assert_eq!(decoder.read_u16().unwrap().unverified(), 0xC00C);
+    /// assert!(decoder.peek_u16().is_err());
+    /// ```
+    pub fn peek_u16(&self) -> ProtoResult<Restrict<u16>> {
+        match self.buffer.get(self.index..self.index + 2) {
+            Some(s) => Ok(Restrict::new(u16::from_be_bytes([s[0], s[1]]))),
+            None => Err("unexpected end of input reached".into()),
+        }
+    }
+
     /// Returns the current index in the buffer
     pub fn index(&self) -> usize {
         self.index
@@ -90,6 +217,8 @@ impl<'a> BinDecoder<'a> {
         BinDecoder {
             buffer: self.buffer,
             index: index_at as usize,
+            #[cfg(feature = "std")]
+            names: self.names.clone(),
         }
     }
 
@@ -142,7 +271,8 @@ impl<'a> BinDecoder<'a> {
     ///
     /// # Returns
     ///
-    /// The Vec of the specified length, otherwise an error
+    /// The Vec of the specified length, otherwise an error. The length is checked against the
+    ///  remaining buffer before anything is allocated.
     pub fn read_vec(&mut self, len: usize) -> ProtoResult<Restrict<Vec<u8>>> {
         self.read_slice(len).map(|s| s.map(ToOwned::to_owned))
     }
@@ -169,6 +299,33 @@ impl<'a> BinDecoder<'a> {
         Ok(Restrict::new(slice))
     }
 
+    /// Reads the next `len` bytes as a new decoder, which is not able to read past them
+    ///
+    /// The returned decoder shares the buffer up to its end, so pointers to earlier data in the
+    ///  message, e.g. for Name compression, are still valid. `self` is moved past the bytes.
+    ///
+    /// ```
+    /// use trust_dns_proto::serialize::binary::BinDecoder;
+    ///
+    /// let deadbeef = b"deadbeef";
+    /// let mut decoder = BinDecoder::new(deadbeef);
+    /// let mut dead = decoder.read_bounded(4).unwrap();
+    /// assert_eq!(dead.read_slice(4).unwrap().unverified(), b"dead");
+    /// assert!(dead.read_u8().is_err());
+    /// assert_eq!(decoder.read_slice(4).unwrap().unverified(), b"beef");
+    /// ```
+    pub fn read_bounded(&mut self, len: usize) -> ProtoResult<BinDecoder<'a>> {
+        let start = self.index;
+        self.read_slice(len)?;
+
+        Ok(BinDecoder {
+            buffer: &self.buffer[..self.index],
+            index: start,
+            #[cfg(feature = "std")]
+            names: self.names.clone(),
+        })
+    }
+
     /// Reads a slice from a previous index to the current
     pub fn slice_from(&self, index: usize) -> ProtoResult<&'a [u8]> {
         if index > self.index {
@@ -185,8 +342,8 @@ impl<'a> BinDecoder<'a> {
 
     /// Reads the next 2 bytes into u16
     ///
-    /// This performs a byte-by-byte manipulation, there
-    ///  which means endianness is implicitly handled (i.e. no network to little endian (intel), issues)
+    /// The bytes are in network byte order, big endian, the same as the encoder writes them, so the
+    ///  result doesn't depend on the endianness of the host.
     ///
     /// # Return
     ///
@@ -199,8 +356,8 @@ impl<'a> BinDecoder<'a> {
 
     /// Reads the next four bytes into i32.
     ///
-    /// This performs a byte-by-byte manipulation, there
-    ///  which means endianness is implicitly handled (i.e. no network to little endian (intel), issues)
+    /// The bytes are in network byte order, big endian, the same as the encoder writes them, so the
+    ///  result doesn't depend on the endianness of the host.
     ///
     /// # Return
     ///
@@ -213,8 +370,8 @@ impl<'a> BinDecoder<'a> {
 
     /// Reads the next four bytes into u32.
     ///
-    /// This performs a byte-by-byte manipulation, there
-    ///  which means endianness is implicitly handled (i.e. no network to little endian (intel), issues)
+    /// The bytes are in network byte order, big endian, the same as the encoder writes them, so the
+    ///  result doesn't depend on the endianness of the host.
     ///
     /// # Return
     ///
@@ -226,7 +383,7 @@ impl<'a> BinDecoder<'a> {
     }
 }
 
-#[cfg(tests)]
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -236,13 +393,13 @@ mod tests {
         let mut decoder = BinDecoder::new(deadbeef);
 
         let read = decoder.read_slice(4).expect("failed to read dead");
-        assert_eq!(read, "dead");
+        assert_eq!(read.unverified(), b"dead");
 
         let read = decoder.read_slice(2).expect("failed to read be");
-        assert_eq!(read, "be");
+        assert_eq!(read.unverified(), b"be");
 
         let read = decoder.read_slice(0).expect("failed to read nothing");
-        assert_eq!(read, "");
+        assert_eq!(read.unverified(), b"");
 
         // this should fail
         assert!(decoder.read_slice(3).is_err());
@@ -253,20 +410,173 @@ mod tests {
         let deadbeef = b"deadbeef";
         let mut decoder = BinDecoder::new(deadbeef);
 
-        decoder.read_slice_from(4).expect("failed to read dead");
+        decoder.read_slice(4).expect("failed to read dead");
         let read = decoder.slice_from(0).expect("failed to get slice");
-        assert_eq!(read, "dead");
+        assert_eq!(read, b"dead");
 
         decoder.read_slice(2).expect("failed to read be");
         let read = decoder.slice_from(4).expect("failed to get slice");
-        assert_eq!(read, "be");
+        assert_eq!(read, b"be");
 
         decoder.read_slice(0).expect("failed to read nothing");
         let read = decoder.slice_from(4).expect("failed to get slice");
-        assert_eq!(read, "be");
+        assert_eq!(read, b"be");
 
         // this should fail
-        assert!(decoder.slice_from(6).is_err());
+        assert!(decoder.slice_from(7).is_err());
         assert!(decoder.slice_from(10).is_err());
     }
+
+    #[test]
+    fn test_peek() {
+        let bytes = [0xC0, 0x0C, 0x01];
+        let mut decoder = BinDecoder::new(&bytes);
+
+        assert_eq!(decoder.peek_u8().unwrap().unverified(), 0xC0);
+        assert_eq!(decoder.peek_u16().unwrap().unverified(), 0xC00C);
+        assert_eq!(decoder.index(), 0);
+        assert_eq!(decoder.read_u16().unwrap().unverified(), 0xC00C);
+
+        // only one byte remains
+        assert!(decoder.peek_u16().is_err());
+        assert_eq!(decoder.peek_u8().unwrap().unverified(), 0x01);
+        assert_eq!(decoder.index(), 2);
+        assert_eq!(decoder.read_u8().unwrap().unverified(), 0x01);
+
+        assert!(decoder.peek_u8().is_err());
+        assert!(decoder.peek().is_none());
+    }
+
+    #[test]
+    fn test_read_bounded() {
+        let bytes = b"\x03abc\x03def";
+        let mut decoder = BinDecoder::new(bytes);
+
+        decoder.read_u8().unwrap();
+        let mut bounded = decoder.read_bounded(3).expect("failed to read bounded");
+        assert_eq!(decoder.index(), 4);
+        assert_eq!(bounded.index(), 1);
+        assert_eq!(bounded.len(), 3);
+
+        // can not read into the next character-string
+        assert!(bounded.read_slice(4).is_err());
+        assert!(bounded.clone(0).read_character_data().is_ok());
+        assert_eq!(bounded.read_slice(3).unwrap().unverified(), b"abc");
+        assert!(bounded.is_empty());
+        assert!(bounded.read_u8().is_err());
+
+        // the rest is still available on the original
+        assert_eq!(decoder.read_character_data().unwrap().unverified(), b"def");
+        assert!(decoder.read_bounded(1).is_err());
+    }
+
+    #[test]
+    fn test_remaining() {
+        let bytes = b"\x00\x01\x02\x03\x04\x05";
+        let mut decoder = BinDecoder::new(bytes);
+        assert_eq!(decoder.remaining(), 6);
+
+        decoder.read_u8().unwrap();
+        assert_eq!(decoder.remaining(), 5);
+        decoder.read_u16().unwrap();
+        assert_eq!(decoder.remaining(), 3);
+        assert_eq!(decoder.len(), decoder.remaining());
+
+        // peeking doesn't consume anything
+        decoder.peek_u16().unwrap();
+        assert_eq!(decoder.remaining(), 3);
+
+        // a bounded decoder ends at its bound, not the end of the buffer
+        let mut bounded = decoder.read_bounded(2).unwrap();
+        assert_eq!(bounded.remaining(), 2);
+        assert_eq!(decoder.remaining(), 1);
+        bounded.read_u8().unwrap();
+        assert_eq!(bounded.remaining(), 1);
+        assert!(!bounded.is_empty());
+        bounded.read_u8().unwrap();
+        assert_eq!(bounded.remaining(), 0);
+        assert!(bounded.is_empty());
+
+        // a failed read leaves it unchanged
+        assert!(decoder.read_u16().is_err());
+        assert_eq!(decoder.remaining(), 1);
+        decoder.read_u8().unwrap();
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn test_read_vec_overrun() {
+        let deadbeef = b"deadbeef";
+        let mut decoder = BinDecoder::new(deadbeef);
+
+        decoder.read_slice(4).expect("failed to read dead");
+
+        // a length from the wire, larger than what remains, must not allocate or move the index
+        assert!(decoder.read_vec(u16::max_value() as usize).is_err());
+        assert!(decoder.read_vec(usize::max_value()).is_err());
+        assert_eq!(decoder.index(), 4);
+
+        let read = decoder.read_vec(4).expect("failed to read beef");
+        assert_eq!(read.unverified(), b"beef".to_vec());
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_read_name_interned() {
+        // www.example.com., a pointer to it, WWW.example.com., a pointer to that, and two pointers
+        //  to example.com.
+        let bytes = b"\x03www\x07example\x03com\x00\xC0\x00\x03WWW\xC0\x04\xC0\x13\xC0\x04\xC0\x04";
+        let shared = |a: &Name, b: &Name| a[0].as_bytes().as_ptr() == b[0].as_bytes().as_ptr();
+
+        let mut decoder = BinDecoder::new(bytes);
+        assert!(!decoder.is_interning_names());
+        let first = decoder.read_name().unwrap();
+        let second = decoder.read_name().unwrap();
+        assert_eq!(first, second);
+        assert!(!shared(&first, &second));
+
+        let mut decoder = BinDecoder::new(bytes);
+        decoder.set_intern_names(true);
+        assert!(decoder.is_interning_names());
+        let first = decoder.read_name().unwrap();
+        let pointer = decoder.read_name().unwrap();
+        let upper = decoder.read_name().unwrap();
+        let upper_pointer = decoder.read_name().unwrap();
+        let example = decoder.read_name().unwrap();
+        let example_pointer = decoder.read_name().unwrap();
+        assert!(decoder.is_empty());
+
+        assert!(first.eq_case(&pointer));
+        assert!(shared(&first, &pointer));
+        assert!(upper.eq_case(&upper_pointer));
+        assert!(shared(&upper, &upper_pointer));
+
+        // case is preserved, so this is a different name
+        assert_eq!(first, upper);
+        assert!(!first.eq_case(&upper));
+        assert!(!shared(&first, &upper));
+
+        // the first pointer to example.com. is decoded, the next shares it
+        assert_eq!(example, Name::from_ascii("example.com.").unwrap());
+        assert!(shared(&example, &example_pointer));
+
+        // the names are shared with clones and bounded decoders
+        let mut clone = decoder.clone(17);
+        assert!(clone.is_interning_names());
+        assert!(shared(&first, &clone.read_name().unwrap()));
+
+        let mut decoder = BinDecoder::new(bytes);
+        decoder.set_intern_names(true);
+        let first = decoder.read_name().unwrap();
+        let mut bounded = decoder.read_bounded(2).unwrap();
+        assert!(bounded.is_interning_names());
+        assert!(shared(&first, &bounded.read_name().unwrap()));
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<BinDecoder<'static>>();
+    }
 }
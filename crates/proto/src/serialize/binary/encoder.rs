@@ -13,7 +13,9 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-use std::marker::PhantomData;
+use alloc::format;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 use crate::error::{ProtoErrorKind, ProtoResult};
 
@@ -22,6 +24,8 @@ use crate::op::Header;
 
 // this is private to make sure there is no accidental access to the inner buffer.
 mod private {
+    use alloc::vec::Vec;
+
     use crate::error::{ProtoErrorKind, ProtoResult};
 
     /// A wrapper for a buffer that guarantees writes never exceed a defined set of bytes
@@ -176,6 +180,9 @@ impl<'a> BinEncoder<'a> {
     }
 
     /// If set to true, then names will be written into the buffer in canonical form
+    ///
+    /// Canonical names are never compressed, so this also disables label pointers for any
+    ///  names emitted while it is set.
     pub fn set_canonical_names(&mut self, canonical_names: bool) {
         self.canonical_names = canonical_names;
     }
@@ -484,6 +491,29 @@ impl<T: EncodedSize> Place<T> {
     }
 }
 
+impl Place<u16> {
+    /// Writes back the number of bytes written since this place was created, e.g. an rdata length
+    ///
+    /// ```
+    /// use trust_dns_proto::serialize::binary::BinEncoder;
+    ///
+    /// let mut bytes: Vec<u8> = Vec::new();
+    /// let mut encoder = BinEncoder::new(&mut bytes);
+    /// let place = encoder.place::<u16>().unwrap();
+    /// encoder.emit_character_data("abc").unwrap();
+    /// place.replace_with_len(&mut encoder).unwrap();
+    /// assert_eq!(bytes, vec![0, 4, 3, b'a', b'b', b'c']);
+    /// ```
+    pub fn replace_with_len(self, encoder: &mut BinEncoder<'_>) -> ProtoResult<()> {
+        let len = encoder.len_since_place(&self);
+        if len > u16::max_value() as usize {
+            return Err(format!("length exceeds the maximum of a u16: {}", len).into());
+        }
+
+        self.replace(encoder, len as u16)
+    }
+}
+
 /// A type representing a rollback point in a stream
 pub struct Rollback {
     rollback_index: usize,
@@ -534,6 +564,30 @@ mod tests {
         msg.to_bytes().unwrap();
     }
 
+    #[test]
+    fn test_replace_with_len() {
+        use crate::rr::rdata::{txt, TXT};
+
+        let rdata = TXT::new(vec!["abc".to_string(), "defghi".to_string()]);
+
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder = BinEncoder::new(&mut bytes);
+        encoder.emit_u8(0xFF).unwrap();
+        let place = encoder.place::<u16>().unwrap();
+        txt::emit(&mut encoder, &rdata).unwrap();
+        place.replace_with_len(&mut encoder).unwrap();
+
+        assert_eq!(&bytes[..3], &[0xFF, 0, 11]);
+        assert_eq!(bytes.len(), 3 + 11);
+
+        // nothing written is a zero length
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder = BinEncoder::new(&mut bytes);
+        let place = encoder.place::<u16>().unwrap();
+        place.replace_with_len(&mut encoder).unwrap();
+        assert_eq!(bytes, vec![0, 0]);
+    }
+
     #[test]
     fn test_size_of() {
         assert_eq!(u16::size_of(), 2);
@@ -15,6 +15,9 @@
  */
 
 //! Binary serialization types
+//!
+//! The encoder and decoder only depend on `core` and `alloc`, anything that requires `std` is
+//!  behind the `std` feature.
 
 mod decoder;
 mod encoder;
@@ -28,6 +31,8 @@ pub use self::restrict::{Restrict, RestrictedMath, Verified};
 #[cfg(test)]
 pub mod bin_tests;
 
+use alloc::vec::Vec;
+
 use crate::error::*;
 
 /// A type which can be encoded into a DNS binary format
@@ -17,3 +17,4 @@
 //! Contains serialization libraries for `binary` and text, `txt`.
 
 pub mod binary;
+pub mod txt;
@@ -0,0 +1,221 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! encodings for binary fields in the presentation format of rdata
+
+use data_encoding::{DecodeError, Encoding, Specification};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Lowercase hex, decoding is case insensitive and ignores whitespace
+    pub static ref HEX: Encoding = {
+        let mut spec = Specification::new();
+        spec.symbols.push_str("0123456789abcdef");
+        spec.ignore.push_str(" \t\r\n");
+        spec.translate.from.push_str("ABCDEF");
+        spec.translate.to.push_str("abcdef");
+        spec.encoding().expect("error in HEX encoding")
+    };
+
+    /// Standard padded base64, decoding ignores whitespace
+    pub static ref BASE64: Encoding = {
+        let mut spec = Specification::new();
+        spec.symbols
+            .push_str("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/");
+        spec.padding = Some('=');
+        spec.ignore.push_str(" \t\r\n");
+        spec.encoding().expect("error in BASE64 encoding")
+    };
+
+    /// Lowercase unpadded base32 with the extended hex alphabet, decoding is case insensitive
+    pub static ref BASE32HEX: Encoding = {
+        let mut spec = Specification::new();
+        spec.symbols.push_str("0123456789abcdefghijklmnopqrstuv");
+        spec.translate.from.push_str("ABCDEFGHIJKLMNOPQRSTUV");
+        spec.translate.to.push_str("abcdefghijklmnopqrstuv");
+        spec.encoding().expect("error in BASE32HEX encoding")
+    };
+}
+
+/// Decodes a hex string, as used for TLSA, SSHFP, DS and generic rdata
+///
+/// Digits may be upper or lower case, and whitespace between them is ignored, as hex fields may be
+///  split across lines within parentheses. Odd numbers of digits and non-hex characters are errors.
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, DecodeError> {
+    HEX.decode(hex.as_bytes())
+}
+
+/// Encodes the data as a lowercase hex string, without any whitespace
+pub fn to_hex(data: &[u8]) -> String {
+    HEX.encode(data)
+}
+
+/// Decodes a base64 string, as used for DNSKEY, RRSIG and OPENPGPKEY
+///
+/// Whitespace is ignored, as base64 fields may be split across lines within parentheses. The
+///  input must be padded with `=` to a multiple of four characters, and the unused bits of the
+///  final character must be zero.
+pub fn from_base64(base64: &str) -> Result<Vec<u8>, DecodeError> {
+    BASE64.decode(base64.as_bytes())
+}
+
+/// Encodes the data as padded base64, without any whitespace
+pub fn to_base64(data: &[u8]) -> String {
+    BASE64.encode(data)
+}
+
+/// Decodes a base32hex string, as used for the NSEC3 next hashed owner name
+///
+/// This is the extended hex alphabet of RFC 4648 section 7, `0-9` then `a-v`, which preserves the
+///  sort order of the encoded data. Digits may be upper or lower case, there is no padding.
+pub fn from_base32hex(base32hex: &str) -> Result<Vec<u8>, DecodeError> {
+    BASE32HEX.decode(base32hex.as_bytes())
+}
+
+/// Encodes the data as lowercase, unpadded base32hex
+pub fn to_base32hex(data: &[u8]) -> String {
+    BASE32HEX.encode(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[]), "");
+        assert_eq!(to_hex(&[0x00, 0x0f, 0xa0, 0xff]), "000fa0ff");
+
+        assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+        assert_eq!(from_hex("000fa0ff").unwrap(), vec![0x00, 0x0f, 0xa0, 0xff]);
+        assert_eq!(from_hex("000FA0Ff").unwrap(), vec![0x00, 0x0f, 0xa0, 0xff]);
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(from_hex(&to_hex(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_hex_whitespace() {
+        assert_eq!(
+            from_hex("00 0f\ta0\r\nff").unwrap(),
+            vec![0x00, 0x0f, 0xa0, 0xff]
+        );
+        // whitespace may even fall within a byte
+        assert_eq!(from_hex(" 0 00f ").unwrap(), vec![0x00, 0x0f]);
+        assert_eq!(from_hex(" \n ").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_base64() {
+        // RFC 4648, section 10
+        for (data, base64) in &[
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(to_base64(data.as_bytes()), *base64);
+            assert_eq!(from_base64(base64).unwrap(), data.as_bytes());
+        }
+
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(from_base64(&to_base64(&data)).unwrap(), data);
+    }
+
+    #[test]
+    fn test_base64_whitespace() {
+        assert_eq!(from_base64("Zm9v YmFy").unwrap(), b"foobar");
+        assert_eq!(from_base64("Zm\t9vY\r\nm E=").unwrap(), b"fooba");
+        assert_eq!(from_base64(" Zg==\n").unwrap(), b"f");
+    }
+
+    #[test]
+    fn test_base64_padding() {
+        // missing padding
+        assert!(from_base64("Zg").is_err());
+        assert!(from_base64("Zm8").is_err());
+        assert!(from_base64("Zm9vYmE").is_err());
+        // too much padding
+        assert!(from_base64("Zm8==").is_err());
+        assert!(from_base64("Zm9v====").is_err());
+        // non-zero trailing bits
+        assert!(from_base64("Zh==").is_err());
+        // not in the alphabet
+        assert!(from_base64("Zm9v-_==").is_err());
+    }
+
+    #[test]
+    fn test_base32hex() {
+        // RFC 4648, section 10, without the padding
+        for (data, base32hex) in &[
+            ("", ""),
+            ("f", "CO"),
+            ("fo", "CPNG"),
+            ("foo", "CPNMU"),
+            ("foob", "CPNMUOG"),
+            ("fooba", "CPNMUOJ1"),
+            ("foobar", "CPNMUOJ1E8"),
+        ] {
+            assert_eq!(to_base32hex(data.as_bytes()), base32hex.to_lowercase());
+            assert_eq!(from_base32hex(base32hex).unwrap(), data.as_bytes());
+            assert_eq!(
+                from_base32hex(&base32hex.to_lowercase()).unwrap(),
+                data.as_bytes()
+            );
+        }
+
+        // not the standard base32 alphabet, in which "foobar" is "MZXW6YTBOI"
+        assert!(from_base32hex("MZXW6YTBOI").is_err());
+        // padding is not used
+        assert!(from_base32hex("CO======").is_err());
+        // no encoding has a length of 1, 3 or 6 mod 8
+        assert!(from_base32hex("C").is_err());
+        assert!(from_base32hex("CPN").is_err());
+        assert!(from_base32hex("CPNMUO").is_err());
+    }
+
+    #[test]
+    fn test_base32hex_round_trip() {
+        // every length up to two blocks, so every amount of trailing bits is exercised
+        let data = (0..=255).rev().collect::<Vec<u8>>();
+        for len in 0..=10 {
+            let encoded = to_base32hex(&data[..len]);
+            assert_eq!(from_base32hex(&encoded).unwrap(), &data[..len]);
+        }
+        assert_eq!(from_base32hex(&to_base32hex(&data)).unwrap(), data);
+
+        // the encoding preserves the sort order, which NSEC3 chains depend on
+        let mut hashes = vec![
+            vec![0xff, 0x00],
+            vec![0x00, 0xff],
+            vec![0x80],
+            vec![0x7f, 0xff],
+        ];
+        let mut encoded = hashes.iter().map(|h| to_base32hex(h)).collect::<Vec<_>>();
+        hashes.sort();
+        encoded.sort();
+        assert_eq!(
+            encoded,
+            hashes.iter().map(|h| to_base32hex(h)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_hex_invalid() {
+        // odd length
+        assert!(from_hex("000").is_err());
+        assert!(from_hex("00 0").is_err());
+        // non-hex characters
+        assert!(from_hex("0g").is_err());
+        assert!(from_hex("0x00").is_err());
+        assert!(from_hex("00-0f").is_err());
+        assert!(from_hex("00\u{e9}0").is_err());
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Text serialization helpers shared by the presentation formats of record data
+
+mod encoding;
+
+pub use self::encoding::{
+    from_base32hex, from_base64, from_hex, to_base32hex, to_base64, to_hex, BASE32HEX, BASE64, HEX,
+};
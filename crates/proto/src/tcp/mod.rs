@@ -19,7 +19,7 @@ mod tcp_client_stream;
 mod tcp_stream;
 
 pub use self::tcp_client_stream::{TcpClientConnect, TcpClientStream};
-pub use self::tcp_stream::{Connect, DnsTcpStream, TcpStream};
+pub use self::tcp_stream::{read_framed_message, Connect, DnsTcpStream, TcpStream};
 
 #[cfg(feature = "tokio-runtime")]
 #[doc(hidden)]
@@ -21,6 +21,7 @@ use futures_util::{self, future::Future, ready, FutureExt};
 use log::debug;
 
 use crate::error::*;
+use crate::serialize::binary::BinDecoder;
 use crate::xfer::{BufStreamHandle, SerialMessage, StreamReceiver};
 use crate::Time;
 
@@ -427,6 +428,81 @@ impl<S: DnsTcpStream> Stream for TcpStream<S> {
     }
 }
 
+/// Reads a single, 2 byte length prefixed, DNS message from a blocking `reader`, e.g. `std::net::TcpStream`
+///
+/// The message is read into `buffer`, replacing its contents, and the returned decoder is limited
+///  to exactly the bytes of that message. Short reads are retried until the whole message is read.
+///
+/// # Arguments
+///
+/// * `reader` - source of the length prefixed messages
+/// * `buffer` - storage for the message, can be reused across calls
+pub fn read_framed_message<'a, R: io::Read>(
+    reader: &mut R,
+    buffer: &'a mut Vec<u8>,
+) -> io::Result<BinDecoder<'a>> {
+    let mut len = [0u8; 2];
+    reader.read_exact(&mut len)?;
+    let len = u16::from_be_bytes(len) as usize;
+    debug!("got length: {}", len);
+
+    buffer.clear();
+    buffer.resize(len, 0);
+    reader.read_exact(buffer)?;
+
+    Ok(BinDecoder::new(buffer))
+}
+
+#[cfg(test)]
+mod framed_tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::op::{Message, Query};
+    use crate::rr::{Name, RecordType};
+    use crate::serialize::binary::{BinDecodable, BinEncodable};
+
+    #[test]
+    fn test_read_framed_message_split() {
+        let mut message = Message::new();
+        message.add_query(Query::query(
+            Name::from_ascii("www.example.com.").unwrap(),
+            RecordType::A,
+        ));
+        let bytes = message.to_bytes().unwrap();
+
+        let mut framed = (bytes.len() as u16).to_be_bytes().to_vec();
+        framed.extend_from_slice(&bytes);
+        let one = framed.clone();
+        framed.extend_from_slice(&one);
+
+        // the first read ends in the middle of the first message
+        let (first, second) = framed.split_at(7);
+        let mut reader = first.chain(second);
+        let mut buffer = Vec::new();
+
+        for _ in 0..2 {
+            let mut decoder = read_framed_message(&mut reader, &mut buffer).unwrap();
+            let read = Message::read(&mut decoder).unwrap();
+            assert!(decoder.is_empty());
+            assert_eq!(read.queries(), message.queries());
+        }
+
+        // the stream is done
+        assert_eq!(
+            read_framed_message(&mut reader, &mut buffer)
+                .err()
+                .unwrap()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        // a truncated message is an error
+        let mut reader = &framed[..10];
+        assert!(read_framed_message(&mut reader, &mut buffer).is_err());
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "tokio-runtime")]
 mod tests {
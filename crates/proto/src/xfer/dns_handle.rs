@@ -85,7 +85,7 @@ pub trait DnsHandle: 'static + Clone + Send + Sync + Unpin {
 
         // TODO: This is not the final ID, it's actually set in the poll method of DNS future
         //  should we just remove this?
-        let id: u16 = rand::random();
+        let id: u16 = Message::random_id();
 
         message.add_query(query);
         message
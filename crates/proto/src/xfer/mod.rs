@@ -28,6 +28,7 @@ pub mod dns_response;
 pub mod dnssec_dns_handle;
 pub mod retry_dns_handle;
 mod serial_message;
+pub mod zone_transfer;
 
 pub use self::dns_exchange::{
     DnsExchange, DnsExchangeBackground, DnsExchangeConnect, DnsExchangeSend,
@@ -40,6 +41,7 @@ pub use self::dns_response::{DnsResponse, DnsResponseFuture};
 pub use self::dnssec_dns_handle::DnssecDnsHandle;
 pub use self::retry_dns_handle::RetryDnsHandle;
 pub use self::serial_message::SerialMessage;
+pub use self::zone_transfer::{AxfrParser, AxfrStream};
 
 /// Ignores the result of a send operation and logs and ignores errors
 fn ignore_send<M, E: Debug>(result: Result<M, E>) {
@@ -0,0 +1,335 @@
+// Copyright 2015-2020 Benjamin Fry <benjaminfry@me.com>
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Streaming of the records in an AXFR zone transfer
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::vec;
+
+use futures_util::stream::{Stream, StreamExt};
+
+use crate::error::*;
+use crate::op::ResponseCode;
+use crate::rr::{Record, RecordType};
+use crate::xfer::SerialMessage;
+
+/// Checks the records of an AXFR response in order, tracking the opening and terminating SOA
+///
+/// [RFC 5936](https://tools.ietf.org/html/rfc5936#section-2.2), DNS Zone Transfer Protocol (AXFR), June 2010
+///
+/// ```text
+/// 2.2.  AXFR Response
+///
+///    ...
+///
+///    The AXFR server MUST begin the response with the SOA RR of the zone
+///    and MUST end the response with the same SOA RR, possibly spread over
+///    multiple DNS messages.
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AxfrParser {
+    soa: Option<Record>,
+    complete: bool,
+    failed: bool,
+}
+
+impl AxfrParser {
+    /// Returns a new parser, expecting the opening SOA
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The SOA record that opened the transfer, if it has been read
+    pub fn soa(&self) -> Option<&Record> {
+        self.soa.as_ref()
+    }
+
+    /// Returns true once the terminating SOA has been read
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Reads the next record of the transfer
+    ///
+    /// # Return value
+    ///
+    /// `false` if this is the terminating SOA, which is a repeat of the first record and not part
+    ///  of the zone, otherwise `true`. It is an error if the first record isn't an SOA, or if any
+    ///  record follows the terminating SOA. Once an error is returned, every later record is an
+    ///  error as well.
+    pub fn read_record(&mut self, record: &Record) -> ProtoResult<bool> {
+        let result = self.check_record(record);
+        self.failed |= result.is_err();
+        result
+    }
+
+    fn check_record(&mut self, record: &Record) -> ProtoResult<bool> {
+        if self.failed {
+            return Err("zone transfer has already failed".into());
+        }
+
+        if self.complete {
+            return Err(format!("record after the end of the zone transfer: {}", record).into());
+        }
+
+        match self.soa {
+            None if record.rr_type() == RecordType::SOA => {
+                self.soa = Some(record.clone());
+                Ok(true)
+            }
+            None => Err(format!("zone transfer did not begin with an SOA: {}", record).into()),
+            Some(ref soa) if record.rr_type() == RecordType::SOA => {
+                if record.name() != soa.name() || record.rdata() != soa.rdata() {
+                    return Err(format!(
+                        "zone transfer ended with a different SOA: {}, expected: {}",
+                        record, soa
+                    )
+                    .into());
+                }
+
+                self.complete = true;
+                Ok(false)
+            }
+            Some(_) => Ok(true),
+        }
+    }
+}
+
+/// A stream of the records of an AXFR response, read from the messages of a TCP connection
+///
+/// Only one message is held at a time, so the zone is never buffered as a whole. The opening SOA
+///  is the first record yielded, the stream ends after the terminating SOA is read, and that SOA
+///  is not yielded again. The stream also ends after the first error it yields.
+#[must_use = "streams do nothing unless polled"]
+pub struct AxfrStream<S> {
+    messages: S,
+    parser: AxfrParser,
+    records: vec::IntoIter<Record>,
+    done: bool,
+}
+
+impl<S> AxfrStream<S>
+where
+    S: Stream<Item = ProtoResult<SerialMessage>> + Unpin,
+{
+    /// Reads the records of the transfer from the response messages, e.g. a `TcpClientStream`
+    pub fn new(messages: S) -> Self {
+        AxfrStream {
+            messages,
+            parser: AxfrParser::new(),
+            records: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+
+    /// The SOA record that opened the transfer, if it has been read
+    pub fn soa(&self) -> Option<&Record> {
+        self.parser.soa()
+    }
+
+    /// Returns true once the terminating SOA has been read
+    pub fn is_complete(&self) -> bool {
+        self.parser.is_complete()
+    }
+
+    fn poll_record(&mut self, cx: &mut Context<'_>) -> Poll<Option<ProtoResult<Record>>> {
+        loop {
+            if let Some(record) = self.records.next() {
+                match self.parser.read_record(&record) {
+                    Ok(true) => return Poll::Ready(Some(Ok(record))),
+                    Ok(false) => continue,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                }
+            }
+
+            if self.parser.is_complete() {
+                return Poll::Ready(None);
+            }
+
+            let mut message = match futures_util::ready!(self.messages.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message.to_message()?,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    return Poll::Ready(Some(Err(
+                        "zone transfer ended before the terminating SOA".into()
+                    )))
+                }
+            };
+
+            if message.response_code() != ResponseCode::NoError {
+                return Poll::Ready(Some(Err(format!(
+                    "zone transfer failed: {}",
+                    message.response_code()
+                )
+                .into())));
+            }
+
+            self.records = message.take_answers().into_iter();
+        }
+    }
+}
+
+impl<S> Stream for AxfrStream<S>
+where
+    S: Stream<Item = ProtoResult<SerialMessage>> + Unpin,
+{
+    type Item = ProtoResult<Record>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        let next = futures_util::ready!(self.poll_record(cx));
+        if !matches!(next, Some(Ok(_))) {
+            self.done = true;
+        }
+
+        Poll::Ready(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use futures_executor::block_on;
+    use futures_util::stream;
+
+    use super::*;
+    use crate::op::{Message, MessageType};
+    use crate::rr::rdata::SOA;
+    use crate::rr::{Name, RData};
+
+    fn soa(serial: u32) -> Record {
+        Record::from_rdata(
+            Name::from_str("example.com.").unwrap(),
+            3600,
+            RData::SOA(SOA::new(
+                Name::from_str("ns.example.com.").unwrap(),
+                Name::from_str("hostmaster.example.com.").unwrap(),
+                serial,
+                7200,
+                3600,
+                1_209_600,
+                3600,
+            )),
+        )
+    }
+
+    fn a(name: &str, octet: u8) -> Record {
+        Record::from_rdata(
+            Name::from_str(name).unwrap(),
+            3600,
+            RData::A([192, 0, 2, octet].into()),
+        )
+    }
+
+    fn serial_message(answers: Vec<Record>) -> ProtoResult<SerialMessage> {
+        let mut message = Message::new();
+        message.set_message_type(MessageType::Response);
+        message.insert_answers(answers);
+
+        Ok(SerialMessage::new(
+            message.to_vec()?,
+            ([127, 0, 0, 1], 53).into(),
+        ))
+    }
+
+    fn transfer(messages: Vec<ProtoResult<SerialMessage>>) -> Vec<ProtoResult<Record>> {
+        block_on(AxfrStream::new(stream::iter(messages)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_axfr_stream() {
+        let records = vec![
+            soa(1),
+            a("www.example.com.", 1),
+            a("mail.example.com.", 2),
+            a("ftp.example.com.", 3),
+        ];
+
+        // spread over three messages, ending with the same SOA
+        let mut axfr = AxfrStream::new(stream::iter(vec![
+            serial_message(records[..2].to_vec()),
+            serial_message(records[2..3].to_vec()),
+            serial_message(vec![records[3].clone(), soa(1)]),
+            // nothing is read after the terminating SOA
+            Err("unreachable".into()),
+        ]));
+
+        for expected in &records {
+            assert!(!axfr.is_complete());
+            let record = block_on(axfr.next())
+                .unwrap()
+                .expect("failed to read record");
+            assert_eq!(&record, expected);
+        }
+
+        assert!(block_on(axfr.next()).is_none());
+        assert!(axfr.is_complete());
+        assert_eq!(axfr.soa(), Some(&records[0]));
+    }
+
+    #[test]
+    fn test_axfr_stream_errors() {
+        // the stream ends before the terminating SOA
+        let records = transfer(vec![serial_message(vec![soa(1), a("www.example.com.", 1)])]);
+        assert_eq!(records.len(), 3);
+        assert!(records[2].is_err());
+
+        // doesn't begin with the SOA, the later SOA isn't taken as the opening one
+        let records = transfer(vec![
+            serial_message(vec![a("www.example.com.", 1), soa(1)]),
+            serial_message(vec![a("mail.example.com.", 2), soa(1)]),
+        ]);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].is_err());
+
+        // ends with a different SOA
+        let records = transfer(vec![serial_message(vec![soa(1), soa(2)])]);
+        assert_eq!(records.len(), 2);
+        assert!(records[1].is_err());
+
+        // a record after the terminating SOA
+        let records = transfer(vec![serial_message(vec![
+            soa(1),
+            soa(1),
+            a("www.example.com.", 1),
+        ])]);
+        assert_eq!(records.len(), 2);
+        assert!(records[1].is_err());
+
+        // the connection failed, nothing more is read from it
+        let records = transfer(vec![
+            serial_message(vec![soa(1)]),
+            Err("connection reset".into()),
+            serial_message(vec![soa(1)]),
+        ]);
+        assert_eq!(records.len(), 2);
+        assert!(records[1].is_err());
+
+        // the server refused the transfer
+        let mut message = Message::new();
+        message
+            .set_message_type(MessageType::Response)
+            .set_response_code(ResponseCode::Refused);
+        let refused = SerialMessage::new(message.to_vec().unwrap(), ([127, 0, 0, 1], 53).into());
+        let records = transfer(vec![Ok(refused)]);
+        assert_eq!(records.len(), 1);
+        assert!(records[0].is_err());
+    }
+
+    #[test]
+    fn test_axfr_parser_failed() {
+        let mut parser = AxfrParser::new();
+        assert!(parser.read_record(&a("www.example.com.", 1)).is_err());
+        assert!(parser.read_record(&soa(1)).is_err());
+        assert!(parser.soa().is_none());
+    }
+}
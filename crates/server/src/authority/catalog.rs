@@ -108,7 +108,9 @@ impl RequestHandler for Catalog {
                     our_version,
                     req_edns.version()
                 );
+                // the high bits of the extended response code are only carried in the OPT record
                 response_header.set_response_code(ResponseCode::BADVERS);
+                resp_edns.set_rcode_high(ResponseCode::BADVERS.high());
                 response.edns(resp_edns);
 
                 // TODO: should ResponseHandle consume self?
@@ -90,39 +90,43 @@ impl FileAuthority {
     /// Read given file line by line and recursively invokes reader for
     /// $INCLUDE directives
     ///
+    /// The current origin is tracked through `$ORIGIN` lines, so that an `$INCLUDE` with a
+    /// domain name can set the origin for the included file, and the origin of this file is
+    /// restored after the included file, as required by RFC 1035.
+    ///
     /// TODO: it looks hacky as far we effectively duplicate parser's functionallity
     /// (at least partially) and performing lexing twice.
     /// Better solution requires us to change lexer to deal
     /// with Lines-like iterator instead of String buf (or capability to combine a few
     /// lexer instances into a single lexer).
-    ///
-    /// TODO: $INCLUDE could specify domain name -- to support on-flight swap for Origin
-    /// value we definitely need to rethink and rework loader/parser/lexer
     fn read_file(
         zone_path: PathBuf,
         buf: &mut String,
         state: FileReaderState,
+        origin: Option<Name>,
     ) -> Result<(), String> {
         let file = File::open(&zone_path)
             .map_err(|e| format!("failed to read {}: {:?}", zone_path.display(), e))?;
         let reader = BufReader::new(file);
+        let mut origin = origin;
         for line in reader.lines() {
             let content = line.unwrap();
             let mut lexer = Lexer::new(&content);
 
             match (lexer.next_token(), lexer.next_token(), lexer.next_token()) {
+                (Ok(Some(Token::Origin)), Ok(Some(Token::CharData(name))), _) => {
+                    let name = String::from_utf8(name)
+                        .map_err(|e| format!("bad $ORIGIN in {}: {}", zone_path.display(), e))?;
+                    origin = Some(Name::parse(&name, origin.as_ref()).map_err(|e| {
+                        format!("bad $ORIGIN {} in {}: {}", name, zone_path.display(), e)
+                    })?);
+                    buf.push_str(&content);
+                }
                 (
                     Ok(Some(Token::Include)),
                     Ok(Some(Token::CharData(include_path))),
-                    Ok(Some(Token::CharData(_domain))),
+                    include_domain,
                 ) => {
-                    return Err(format!(
-                        "Domain name for $INCLUDE is not supported at {}, trying to include {}",
-                        zone_path.display(),
-                        include_path
-                    ));
-                }
-                (Ok(Some(Token::Include)), Ok(Some(Token::CharData(include_path))), _) => {
                     // RFC1035 (section 5) does not specify how filename for $INCLUDE
                     // should be resolved into file path. The underlying code implements the
                     // following:
@@ -132,6 +136,9 @@ impl FileAuthority {
                     // TODO: Inlining files specified using non-relative path might potentially introduce
                     // security issue in some cases (e.g. when working with zone files from untrusted sources)
                     // and should probably be configurable by user.
+                    let include_path = String::from_utf8(include_path).map_err(|e| {
+                        format!("bad $INCLUDE path in {}: {}", zone_path.display(), e)
+                    })?;
                     let include_path = Path::new(&include_path);
                     let include_zone_path = if include_path.is_absolute() {
                         include_path.to_path_buf()
@@ -145,6 +152,24 @@ impl FileAuthority {
                         return Err(format!("Max depth level for nested $INCLUDE is reached at {}, trying to include {}", zone_path.display(), include_zone_path.display()));
                     }
 
+                    // the origin for the included file, which is relative to the current origin
+                    let include_origin = match include_domain {
+                        Ok(Some(Token::CharData(domain))) => {
+                            let domain = String::from_utf8(domain).map_err(|e| {
+                                format!("bad $INCLUDE domain in {}: {}", zone_path.display(), e)
+                            })?;
+                            Some(Name::parse(&domain, origin.as_ref()).map_err(|e| {
+                                format!(
+                                    "bad $INCLUDE domain {} in {}: {}",
+                                    domain,
+                                    zone_path.display(),
+                                    e
+                                )
+                            })?)
+                        }
+                        _ => origin.clone(),
+                    };
+
                     let mut include_buf = String::new();
 
                     info!(
@@ -153,12 +178,22 @@ impl FileAuthority {
                         zone_path.display()
                     );
 
+                    if let Some(ref include_origin) = include_origin {
+                        include_buf.push_str(&format!("$ORIGIN {}\n", include_origin.to_ascii()));
+                    }
+
                     FileAuthority::read_file(
                         include_zone_path,
                         &mut include_buf,
                         state.next_level(),
+                        include_origin,
                     )?;
                     buf.push_str(&include_buf);
+
+                    // the included file must not change the origin of this one
+                    if let Some(ref origin) = origin {
+                        buf.push_str(&format!("\n$ORIGIN {}", origin.to_ascii()));
+                    }
                 }
                 _ => {
                     buf.push_str(&content);
@@ -187,8 +222,13 @@ impl FileAuthority {
 
         // TODO: this should really use something to read line by line or some other method to
         //  keep the usage down. and be a custom lexer...
-        FileAuthority::read_file(zone_path, &mut buf, FileReaderState::new())
-            .map_err(|e| format!("failed to read {}: {:?}", &config.zone_file_path, e))?;
+        FileAuthority::read_file(
+            zone_path,
+            &mut buf,
+            FileReaderState::new(),
+            Some(origin.clone()),
+        )
+        .map_err(|e| format!("failed to read {}: {:?}", &config.zone_file_path, e))?;
 
         let lexer = Lexer::new(&buf);
         let (origin, records) = Parser::new()
@@ -416,4 +456,46 @@ mod tests {
             _ => panic!("wrong rdata type returned"),
         }
     }
+
+    #[test]
+    fn test_load_zone_include_origin() {
+        let config = FileConfig {
+            zone_file_path:
+                "../../tests/test-data/named_test_configs/include_origin.example.com.zone"
+                    .to_string(),
+        };
+        let authority = FileAuthority::try_from_config(
+            Name::from_str("example.com.").unwrap(),
+            ZoneType::Primary,
+            false,
+            None,
+            &config,
+        )
+        .expect("failed to load file");
+
+        let lookup_a = |name: &str| {
+            block_on(Authority::lookup(
+                &authority,
+                &LowerName::from_str(name).unwrap(),
+                RecordType::A,
+                false,
+                SupportedAlgorithms::new(),
+            ))
+            .expect("lookup failed")
+            .into_iter()
+            .next()
+            .map(|record| record.rdata().clone())
+        };
+
+        // records in the included file are relative to the domain given to $INCLUDE
+        assert_eq!(
+            lookup_a("www.sub.example.com."),
+            Some(RData::A(Ipv4Addr::new(127, 0, 0, 6)))
+        );
+        // and the origin of the including file is restored afterwards
+        assert_eq!(
+            lookup_a("after.example.com."),
+            Some(RData::A(Ipv4Addr::new(127, 0, 0, 7)))
+        );
+    }
 }
@@ -448,6 +448,76 @@ a       A       127.0.0.1
     assert!(InMemoryAuthority::new(origin, records, ZoneType::Primary, false).is_err());
 }
 
+#[test]
+fn test_parse_error_line() {
+    let lexer = Lexer::new(
+        r###"
+@   IN  SOA     venera      action\.domains (
+                            20     ; SERIAL
+                            7200   ; REFRESH
+                            600    ; RETRY
+                            3600000; EXPIRE
+                            60)    ; MINIMUM
+
+a       A       127.0.0.1
+_sip._tcp   SRV     10 20
+b       A       127.0.0.2
+"###,
+    );
+
+    let error = Parser::new()
+        .parse(lexer, Some(Name::from_str("isi.edu").unwrap()), None)
+        .expect_err("the SRV record is missing its port and target");
+
+    assert_eq!(error.line(), Some(10));
+    assert!(
+        error.to_string().starts_with("error at line 10: "),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_lenient_parse() {
+    let zone = r###"
+@   IN  SOA     venera      action\.domains (
+                            20     ; SERIAL
+                            7200   ; REFRESH
+                            600    ; RETRY
+                            3600000; EXPIRE
+                            60)    ; MINIMUM
+
+a       A       127.0.0.1
+b       A       not.an.address
+c       A       127.0.0.3
+"###;
+    let origin = Name::from_str("isi.edu.").unwrap();
+
+    // strict is the default, the whole zone fails
+    let mut parser = Parser::new();
+    assert!(!parser.is_lenient());
+    let error = parser
+        .parse(Lexer::new(zone), Some(origin.clone()), None)
+        .expect_err("the A record for b is invalid");
+    assert_eq!(error.line(), Some(10));
+
+    // lenient skips the bad record and keeps the rest
+    let mut parser = Parser::new();
+    parser.set_lenient(true);
+    let (_, records) = parser
+        .parse(Lexer::new(zone), Some(origin), None)
+        .expect("the bad record should be skipped");
+
+    let names = records
+        .keys()
+        .map(|key| key.name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["isi.edu.", "a.isi.edu.", "c.isi.edu."]);
+
+    assert_eq!(parser.errors().len(), 1);
+    assert_eq!(parser.errors()[0].line(), Some(10));
+}
+
 #[test]
 fn test_bad_cname_at_a() {
     let lexer = Lexer::new(
@@ -523,3 +593,254 @@ fn test_named_root() {
     assert!(records.contains_key(&key));
     assert_eq!(records[&key].dns_class(), DNSClass::IN)
 }
+
+#[test]
+fn test_hinfo_quoted() {
+    let lexer = Lexer::new(
+        r###"
+host    3600    IN  HINFO   "Intel Core i7" "Debian GNU/Linux"
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.com.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("host.example.com.").unwrap()),
+        RecordType::HINFO,
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    if let RData::HINFO(ref hinfo) = *record.rdata() {
+        assert_eq!(hinfo.cpu(), b"Intel Core i7");
+        assert_eq!(hinfo.os(), b"Debian GNU/Linux");
+        assert_eq!(hinfo.to_string(), r#""Intel Core i7" "Debian GNU/Linux""#);
+    } else {
+        panic!("Not an HINFO record!!!")
+    }
+}
+
+#[test]
+fn test_generic_rdata() {
+    let lexer = Lexer::new(
+        r###"
+a       3600    IN  TYPE731 \# 6 abcd (
+                                 ef 01 23 45 )
+b       3600    IN  TYPE731 \# 0
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("a.example.").unwrap()),
+        RecordType::Unknown(731),
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    assert_eq!(record.rdata().to_string(), r"\# 6 abcdef012345");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("b.example.").unwrap()),
+        RecordType::Unknown(731),
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    assert_eq!(record.rdata().to_string(), r"\# 0");
+}
+
+#[test]
+fn test_generic_class() {
+    let lexer = Lexer::new(
+        r###"
+a.example.      3600    CLASS32     TYPE731         \# 6 abcd ef012345
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("a.example.").unwrap()),
+        RecordType::Unknown(731),
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    assert_eq!(record.dns_class(), DNSClass::Unknown(32));
+    assert_eq!(record.dns_class().to_string(), "CLASS32");
+}
+
+#[test]
+fn test_origin_and_ttl_directives() {
+    let lexer = Lexer::new(
+        r###"
+$ORIGIN example.com.
+$TTL 3600
+www         IN  A   127.0.0.1
+mail    60  IN  A   127.0.0.2
+ftp         IN  A   127.0.0.3
+$ORIGIN sub
+host        IN  A   127.0.0.4
+$TTL 1h30m
+$ORIGIN other.example.
+@           IN  A   127.0.0.5
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, None, None)
+        .expect("failed to parse");
+
+    let lookup = |name: &str| {
+        let key = RrKey::new(
+            LowerName::from(Name::from_str(name).unwrap()),
+            RecordType::A,
+        );
+        let record = records[&key].records_without_rrsigs().next().unwrap();
+        (record.ttl(), record.rdata().to_string())
+    };
+
+    assert_eq!(lookup("www.example.com."), (3600, "127.0.0.1".to_string()));
+    assert_eq!(lookup("mail.example.com."), (60, "127.0.0.2".to_string()));
+    // an explicit TTL does not replace the $TTL default
+    assert_eq!(lookup("ftp.example.com."), (3600, "127.0.0.3".to_string()));
+    // a relative $ORIGIN is appended to the current origin
+    assert_eq!(
+        lookup("host.sub.example.com."),
+        (3600, "127.0.0.4".to_string())
+    );
+    assert_eq!(lookup("other.example."), (5400, "127.0.0.5".to_string()));
+}
+
+#[test]
+fn test_last_stated_ttl() {
+    let lexer = Lexer::new(
+        r###"
+$ORIGIN example.com.
+www     60  IN  A   127.0.0.1
+ftp         IN  A   127.0.0.2
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, None, None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("ftp.example.com.").unwrap()),
+        RecordType::A,
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    assert_eq!(record.ttl(), 60);
+}
+
+#[test]
+fn test_multi_line_quoted_txt() {
+    let lexer = Lexer::new(
+        r###"
+mail._domainkey 3600    IN  TXT ( "v=DKIM1; k=rsa; "    ; first part
+                                  "p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQ" )
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.com.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("mail._domainkey.example.com.").unwrap()),
+        RecordType::TXT,
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    if let RData::TXT(ref txt) = *record.rdata() {
+        assert_eq!(
+            txt.txt_data(),
+            &[
+                b"v=DKIM1; k=rsa; ".to_vec().into_boxed_slice(),
+                b"p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQ"
+                    .to_vec()
+                    .into_boxed_slice(),
+            ]
+        );
+    } else {
+        panic!("Not a TXT record!!!")
+    }
+}
+
+#[test]
+fn test_txt_decimal_escapes() {
+    let lexer = Lexer::new(
+        r###"
+escaped 3600    IN  TXT "\065\066\067"
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.com.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("escaped.example.com.").unwrap()),
+        RecordType::TXT,
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    if let RData::TXT(ref txt) = *record.rdata() {
+        assert_eq!(txt.txt_data(), &[b"ABC".to_vec().into_boxed_slice()]);
+    } else {
+        panic!("Not a TXT record!!!")
+    }
+
+    // octets above 127 are kept as is, they need not be UTF-8
+    let lexer = Lexer::new(
+        r###"
+escaped 3600    IN  TXT "\200" "\195\169"
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("example.com.").unwrap()), None)
+        .expect("failed to parse");
+
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    if let RData::TXT(ref txt) = *record.rdata() {
+        assert_eq!(
+            txt.txt_data(),
+            &[
+                vec![200].into_boxed_slice(),
+                "é".as_bytes().to_vec().into_boxed_slice()
+            ]
+        );
+    } else {
+        panic!("Not a TXT record!!!")
+    }
+}
+
+#[test]
+fn test_loc() {
+    let lexer = Lexer::new(
+        r###"
+cambridge-net   3600    IN  LOC   42 21 54 N 71 06 18 W -24m 30m
+"###,
+    );
+
+    let (_, records) = Parser::new()
+        .parse(lexer, Some(Name::from_str("kei.com.").unwrap()), None)
+        .expect("failed to parse");
+
+    let key = RrKey::new(
+        LowerName::from(Name::from_str("cambridge-net.kei.com.").unwrap()),
+        RecordType::LOC,
+    );
+    let record = records[&key].records_without_rrsigs().next().unwrap();
+    if let RData::LOC(ref loc) = *record.rdata() {
+        assert!((loc.latitude_degrees() - 42.365).abs() < 1e-9);
+        assert!((loc.longitude_degrees() + 71.105).abs() < 1e-9);
+        assert!((loc.altitude_meters() + 24.0).abs() < 1e-9);
+        assert_eq!(
+            loc.to_string(),
+            "42 21 54.000 N 71 6 18.000 W -24.00m 30m 10000m 10m"
+        );
+    } else {
+        panic!("Not a LOC record!!!")
+    }
+}